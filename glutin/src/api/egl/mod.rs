@@ -13,6 +13,7 @@ use std::ffi::{CStr, CString};
 use std::ops::{Deref, DerefMut};
 use std::os::raw;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use glutin_egl_sys as ffi;
 use libloading;
@@ -33,11 +34,11 @@ use winit::dpi;
 
 use self::make_current_guard::MakeCurrentGuard;
 use crate::api::dlloader::{SymTrait, SymWrapper};
-#[cfg(not(target_os = "windows"))]
-use crate::Rect;
 use crate::{
-    Api, ContextError, CreationError, GlAttributes, GlRequest, PixelFormat,
-    PixelFormatRequirements, ReleaseBehavior, Robustness, VSyncError, VSyncMode,
+    Api, Backend, CaveatTolerance, ColorBufferType, ContextError, CreationError, CreationReport,
+    FrameTimestamps, GlAttributes, GlRequest, MultisampleResolve, PixelFormat,
+    PixelFormatRequirements, Priority, Rect, ReleaseBehavior, Robustness, ShareToken, SwapBehavior,
+    TransparentType, VSyncError, VSyncMode,
 };
 
 #[derive(Clone)]
@@ -127,6 +128,75 @@ lazy_static! {
     pub static ref EGL: Option<Egl> = Egl::new().ok();
 }
 
+type PresentationTimeAndroidType = unsafe extern "system" fn(
+    ffi::egl::types::EGLDisplay,
+    ffi::egl::types::EGLSurface,
+    i64,
+) -> ffi::egl::types::EGLBoolean;
+
+type GetNextFrameIdAndroidType = unsafe extern "system" fn(
+    ffi::egl::types::EGLDisplay,
+    ffi::egl::types::EGLSurface,
+    *mut u64,
+) -> ffi::egl::types::EGLBoolean;
+
+type GetFrameTimestampsAndroidType = unsafe extern "system" fn(
+    ffi::egl::types::EGLDisplay,
+    ffi::egl::types::EGLSurface,
+    u64,
+    ffi::egl::types::EGLint,
+    *const ffi::egl::types::EGLint,
+    *mut i64,
+) -> ffi::egl::types::EGLBoolean;
+
+// From `EGL_ANDROID_get_frame_timestamps`, which the generated bindings lack.
+const TIMESTAMPS_ANDROID: ffi::egl::types::EGLenum = 0x3430;
+const REQUESTED_PRESENT_TIME_ANDROID: ffi::egl::types::EGLint = 0x3434;
+const RENDERING_COMPLETE_TIME_ANDROID: ffi::egl::types::EGLint = 0x3435;
+const COMPOSITION_LATCH_TIME_ANDROID: ffi::egl::types::EGLint = 0x3436;
+const DISPLAY_PRESENT_TIME_ANDROID: ffi::egl::types::EGLint = 0x343A;
+
+/// Looks up the EGL function `name`, which must be nul-terminated.
+fn egl_proc_address(
+    name: &[u8],
+) -> Option<ffi::egl::types::__eglMustCastToProperFunctionPointerType> {
+    let egl = EGL.as_ref()?;
+    let addr = unsafe { egl.GetProcAddress(name.as_ptr() as *const _) };
+    if addr.is_null() {
+        None
+    } else {
+        Some(addr)
+    }
+}
+
+lazy_static! {
+    /// `eglPresentationTimeANDROID`, looked up by hand as the generated
+    /// bindings have no type for its `EGLnsecsANDROID` parameter.
+    static ref PRESENTATION_TIME_ANDROID: Option<PresentationTimeAndroidType> =
+        egl_proc_address(b"eglPresentationTimeANDROID\0").map(|addr| unsafe {
+            std::mem::transmute::<
+                ffi::egl::types::__eglMustCastToProperFunctionPointerType,
+                PresentationTimeAndroidType,
+            >(addr)
+        });
+    /// `eglGetNextFrameIdANDROID`, looked up by hand for the same reason.
+    static ref GET_NEXT_FRAME_ID_ANDROID: Option<GetNextFrameIdAndroidType> =
+        egl_proc_address(b"eglGetNextFrameIdANDROID\0").map(|addr| unsafe {
+            std::mem::transmute::<
+                ffi::egl::types::__eglMustCastToProperFunctionPointerType,
+                GetNextFrameIdAndroidType,
+            >(addr)
+        });
+    /// `eglGetFrameTimestampsANDROID`, looked up by hand for the same reason.
+    static ref GET_FRAME_TIMESTAMPS_ANDROID: Option<GetFrameTimestampsAndroidType> =
+        egl_proc_address(b"eglGetFrameTimestampsANDROID\0").map(|addr| unsafe {
+            std::mem::transmute::<
+                ffi::egl::types::__eglMustCastToProperFunctionPointerType,
+                GetFrameTimestampsAndroidType,
+            >(addr)
+        });
+}
+
 /// Specifies the type of display passed as `native_display`.
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -149,16 +219,89 @@ pub enum NativeDisplay {
 #[derive(Debug)]
 pub struct Context {
     display: ffi::egl::types::EGLDisplay,
+    /// Keeps `display` initialized for as long as the context, its surface
+    /// and its fences need it.
+    display_ref: DisplayRef,
     context: ffi::egl::types::EGLContext,
+    config_id: ffi::egl::types::EGLConfig,
+    /// The config the context itself was created with, `EGL_NO_CONFIG_KHR`
+    /// if it isn't tied to `config_id`.
+    context_config: ffi::egl::types::EGLConfig,
     surface: Option<parking_lot::Mutex<ffi::egl::types::EGLSurface>>,
+    /// Whether `surface` is a pbuffer, which `resize_pbuffer()` may replace.
+    pbuffer: bool,
+    /// The window `surface` was created for, which `recreate_surface()`
+    /// creates a new one for.
+    native_window: Option<ffi::EGLNativeWindowType>,
+    /// Whether `replace_surface()` swapped in a surface of the caller's,
+    /// which `pbuffer` and `native_window` no longer describe.
+    surface_replaced: std::sync::atomic::AtomicBool,
+    /// The context this one was created sharing with, if any.
+    share: ffi::EGLContext,
+    egl_version: (ffi::egl::types::EGLint, ffi::egl::types::EGLint),
+    extensions: Vec<String>,
     api: Api,
+    version: (u8, u8),
+    robustness: Robustness,
+    debug: bool,
+    memory_purge_handling: bool,
+    release_behavior: ReleaseBehavior,
+    /// The priority that was asked for, which `recreate()` asks for again.
+    priority: Option<Priority>,
+    raw_context_attributes: Vec<(i32, i32)>,
+    finish_on_drop: bool,
     pixel_format: PixelFormat,
     swap_interval_range: SwapIntervalRange,
+    vsync: parking_lot::Mutex<VSyncMode>,
+    /// A fence inserted after the last swap, once `would_block_on_swap()`
+    /// asked for them.
+    swap_fence: parking_lot::Mutex<Option<EglSync>>,
+    track_swaps: std::sync::atomic::AtomicBool,
+    /// The stream fed by `surface`, kept alive until the surface is gone.
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    ))]
+    _stream: Option<EglStream>,
 }
 
 #[derive(Debug)]
 struct SwapIntervalRange(i32, i32);
 
+impl SwapIntervalRange {
+    /// The smallest non-negative swap interval in this range, as negative
+    /// (adaptive) intervals still wait for vblank.
+    fn lowest_interval(&self) -> i32 {
+        self.0.max(0)
+    }
+
+    fn contains(&self, interval: i32) -> bool {
+        interval >= self.0 && interval <= self.1
+    }
+}
+
+/// The vsync modes to try in order, which is just [`GlAttributes::vsync`]
+/// unless a fallback chain was requested.
+fn desired_vsync_modes<T>(opengl: &GlAttributes<T>) -> &[VSyncMode] {
+    if opengl.vsync_fallback.is_empty() {
+        std::slice::from_ref(&opengl.vsync)
+    } else {
+        &opengl.vsync_fallback
+    }
+}
+
+type EglVersion = (ffi::egl::types::EGLint, ffi::egl::types::EGLint);
+
+/// Builds a [`CreationError::EglError`] for `call` from `eglGetError()`, so
+/// this must run right after the call that failed.
+fn egl_error(call: &'static str) -> CreationError {
+    let egl = EGL.as_ref().unwrap();
+    CreationError::EglError { call, code: unsafe { egl.GetError() } as u32 }
+}
+
 fn get_egl_version(
     display: ffi::egl::types::EGLDisplay,
 ) -> Result<(ffi::egl::types::EGLint, ffi::egl::types::EGLint), CreationError> {
@@ -168,13 +311,122 @@ fn get_egl_version(
         let mut minor: ffi::egl::types::EGLint = std::mem::zeroed();
 
         if egl.Initialize(display, &mut major, &mut minor) == 0 {
-            return Err(CreationError::OsError("eglInitialize failed".to_string()));
+            return Err(egl_error("eglInitialize"));
         }
 
         Ok((major, minor))
     }
 }
 
+/// How many [`DisplayRef`]s exist for a display, and whether to terminate
+/// it once they are gone.
+struct DisplayRefCount {
+    count: usize,
+    /// Whether glutin was the one to initialize the display.
+    owned: bool,
+    /// Whether any context on the display opted into termination.
+    terminate: bool,
+}
+
+lazy_static! {
+    static ref DISPLAY_REFS: Mutex<HashMap<usize, DisplayRefCount>> = Mutex::new(HashMap::new());
+}
+
+/// Keeps an EGL display initialized, and terminates it once the last
+/// reference is dropped if that was asked for.
+///
+/// `eglGetDisplay` returns the same `EGLDisplay` for the same native display,
+/// and `eglInitialize` doesn't count how often it was called, so a single
+/// `eglTerminate` pulls the display from under everyone using it, including
+/// other libraries that started using it after glutin. That's why displays
+/// are only terminated on request, and never if someone else initialized
+/// them first. Otherwise they stay initialized, which leaks a little.
+#[derive(Debug)]
+struct DisplayRef(ffi::egl::types::EGLDisplay);
+
+impl DisplayRef {
+    /// Initializes `display` and takes a reference to it, returning its
+    /// version as well.
+    fn acquire(
+        display: ffi::egl::types::EGLDisplay,
+        terminate: bool,
+    ) -> Result<(Self, EglVersion), CreationError> {
+        let egl = EGL.as_ref().unwrap();
+        // Held across `eglInitialize`, so the last reference can't terminate
+        // the display in between.
+        let mut refs = DISPLAY_REFS.lock().unwrap();
+        let owned = match refs.get(&(display as usize)) {
+            Some(entry) => entry.owned,
+            // Only fails with `EGL_NOT_INITIALIZED`, since the display is valid.
+            None => unsafe { egl.QueryString(display, ffi::egl::VENDOR as i32).is_null() },
+        };
+
+        let egl_version = get_egl_version(display)?;
+        let entry = refs.entry(display as usize).or_insert(DisplayRefCount {
+            count: 0,
+            owned,
+            terminate: false,
+        });
+        entry.count += 1;
+        entry.terminate |= terminate;
+        Ok((DisplayRef(display), egl_version))
+    }
+}
+
+impl Clone for DisplayRef {
+    fn clone(&self) -> Self {
+        DISPLAY_REFS.lock().unwrap().get_mut(&(self.0 as usize)).unwrap().count += 1;
+        DisplayRef(self.0)
+    }
+}
+
+impl Drop for DisplayRef {
+    fn drop(&mut self) {
+        let mut refs = DISPLAY_REFS.lock().unwrap();
+        let entry = refs.get_mut(&(self.0 as usize)).unwrap();
+        entry.count -= 1;
+        if entry.count == 0 {
+            let entry = refs.remove(&(self.0 as usize)).unwrap();
+            if entry.owned && entry.terminate {
+                unsafe {
+                    EGL.as_ref().unwrap().Terminate(self.0);
+                }
+            }
+        }
+    }
+}
+
+/// Gets and initializes the EGL display for `native_display`, returning a
+/// reference to it along with its version and extensions.
+///
+/// `terminate` opts into terminating the display, see [`DisplayRef`].
+fn init_display(
+    native_display: &NativeDisplay,
+    terminate: bool,
+) -> Result<(DisplayRef, EglVersion, Vec<String>), CreationError> {
+    let egl = EGL.as_ref().unwrap();
+    // calling `eglGetDisplay` or equivalent
+    let display = get_native_display(native_display);
+
+    if display.is_null() {
+        return Err(CreationError::OsError("Could not create EGL display object".to_string()));
+    }
+
+    let (display_ref, egl_version) = DisplayRef::acquire(display, terminate)?;
+
+    // the list of extensions supported by the client once initialized is
+    // different from the list of extensions obtained earlier
+    let extensions = if egl_version >= (1, 2) {
+        let p = unsafe { CStr::from_ptr(egl.QueryString(display, ffi::egl::EXTENSIONS as i32)) };
+        let list = String::from_utf8(p.to_bytes().to_vec()).unwrap_or_default();
+        list.split(' ').map(|e| e.to_string()).collect::<Vec<_>>()
+    } else {
+        vec![]
+    };
+
+    Ok((display_ref, egl_version, extensions))
+}
+
 unsafe fn bind_and_get_api<'a>(
     opengl: &'a GlAttributes<&'a Context>,
     egl_version: (ffi::egl::types::EGLint, ffi::egl::types::EGLint),
@@ -223,14 +475,22 @@ unsafe fn bind_and_get_api<'a>(
                 Ok((Some(opengles_version), Api::OpenGlEs))
             }
         }
+        GlRequest::GlesThenGl { opengles_version, opengl_version } => {
+            if egl_version < (1, 2) || egl.BindAPI(ffi::egl::OPENGL_ES_API) != 0 {
+                Ok((Some(opengles_version), Api::OpenGlEs))
+            } else if egl_version >= (1, 4) && egl.BindAPI(ffi::egl::OPENGL_API) != 0 {
+                Ok((Some(opengl_version), Api::OpenGl))
+            } else {
+                Err(CreationError::OpenGlVersionNotSupported)
+            }
+        }
     }
 }
 
-fn get_native_display(native_display: &NativeDisplay) -> *const raw::c_void {
+/// The client extensions, which are queried without any display.
+fn client_extensions() -> Vec<String> {
     let egl = EGL.as_ref().unwrap();
-    // the first step is to query the list of extensions without any display, if
-    // supported
-    let dp_extensions = unsafe {
+    unsafe {
         let p = egl.QueryString(ffi::egl::NO_DISPLAY, ffi::egl::EXTENSIONS as i32);
 
         // this possibility is available only with EGL 1.5 or
@@ -243,7 +503,14 @@ fn get_native_display(native_display: &NativeDisplay) -> *const raw::c_void {
             let list = String::from_utf8(p.to_bytes().to_vec()).unwrap_or_default();
             list.split(' ').map(|e| e.to_string()).collect::<Vec<_>>()
         }
-    };
+    }
+}
+
+fn get_native_display(native_display: &NativeDisplay) -> *const raw::c_void {
+    let egl = EGL.as_ref().unwrap();
+    // the first step is to query the list of extensions without any display, if
+    // supported
+    let dp_extensions = client_extensions();
 
     let has_dp_extension = |e: &str| dp_extensions.iter().any(|s| s == e);
 
@@ -365,6 +632,14 @@ pub enum SurfaceType {
     PBuffer,
     Window,
     Surfaceless,
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    ))]
+    Stream,
 }
 
 impl Context {
@@ -385,36 +660,35 @@ impl Context {
         F: FnMut(
             Vec<ffi::egl::types::EGLConfig>,
             ffi::egl::types::EGLDisplay,
-        ) -> Result<ffi::egl::types::EGLConfig, ()>,
+        ) -> Result<ffi::egl::types::EGLConfig, CreationError>,
     {
-        let egl = EGL.as_ref().unwrap();
-        // calling `eglGetDisplay` or equivalent
-        let display = get_native_display(&native_display);
+        let (display_ref, egl_version, extensions) =
+            init_display(&native_display, opengl.terminate_display)?;
+        let display = display_ref.0;
 
-        if display.is_null() {
-            return Err(CreationError::OsError("Could not create EGL display object".to_string()));
+        if pf_reqs.release_behavior == ReleaseBehavior::None
+            && !extensions.iter().any(|s| s == "EGL_KHR_context_flush_control")
+        {
+            return Err(CreationError::NotSupported(
+                "EGL_KHR_context_flush_control not supported".to_string(),
+            ));
+        }
+        if opengl.no_config_context && !extensions.iter().any(|s| s == "EGL_KHR_no_config_context")
+        {
+            return Err(CreationError::NotSupported(
+                "EGL_KHR_no_config_context not supported".to_string(),
+            ));
         }
-
-        let egl_version = get_egl_version(display)?;
-
-        // the list of extensions supported by the client once initialized is
-        // different from the list of extensions obtained earlier
-        let extensions = if egl_version >= (1, 2) {
-            let p =
-                unsafe { CStr::from_ptr(egl.QueryString(display, ffi::egl::EXTENSIONS as i32)) };
-            let list = String::from_utf8(p.to_bytes().to_vec()).unwrap_or_default();
-            list.split(' ').map(|e| e.to_string()).collect::<Vec<_>>()
-        } else {
-            vec![]
-        };
 
         // binding the right API and choosing the version
         let (version, api) = unsafe { bind_and_get_api(opengl, egl_version)? };
+        let srgb = surface_srgb(&egl_version, &extensions, api, pf_reqs, surface_type)?;
 
-        let (config_id, pixel_format, swap_interval_range) = unsafe {
+        let (config_id, mut pixel_format, swap_interval_range) = unsafe {
             choose_fbconfig(
                 display,
                 &egl_version,
+                &extensions,
                 api,
                 version,
                 pf_reqs,
@@ -423,17 +697,92 @@ impl Context {
                 config_selector,
             )?
         };
+        // Asked for when creating the surface, then checked against it.
+        pixel_format.srgb = srgb;
+        pixel_format.double_buffer = pf_reqs.double_buffer != Some(false);
+        // Core EGL has no stereo configs, but `EGL_EXT_multiview_window`
+        // can give window surfaces a left and a right view. Without it,
+        // stereo is silently dropped.
+        pixel_format.stereoscopy = pf_reqs.stereoscopy
+            && surface_type == SurfaceType::Window
+            && extensions.iter().any(|s| s == "EGL_EXT_multiview_window");
 
         Ok(ContextPrototype {
             opengl,
             display,
+            display_ref,
             egl_version,
             extensions,
             api,
             version,
             config_id,
             pixel_format,
+            srgb_required: pf_reqs.srgb_required,
             swap_interval_range,
+            release_behavior: pf_reqs.release_behavior,
+        })
+    }
+
+    /// Starts building a context without any config through
+    /// `EGL_KHR_no_config_context`. The only way to finish it is
+    /// [`ContextPrototype::finish_surfaceless()`], so this also checks for
+    /// `EGL_KHR_surfaceless_context` right away.
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    ))]
+    pub fn new_no_config<'a>(
+        opengl: &'a GlAttributes<&'a Context>,
+        native_display: NativeDisplay,
+    ) -> Result<ContextPrototype<'a>, CreationError> {
+        let (display_ref, egl_version, extensions) =
+            init_display(&native_display, opengl.terminate_display)?;
+        let display = display_ref.0;
+
+        for extension in &["EGL_KHR_no_config_context", "EGL_KHR_surfaceless_context"] {
+            if !extensions.iter().any(|s| s == extension) {
+                return Err(CreationError::NotSupported(format!("{} not supported", extension)));
+            }
+        }
+
+        let (version, api) = unsafe { bind_and_get_api(opengl, egl_version)? };
+
+        // There is no framebuffer, so there is nothing to describe.
+        let pixel_format = PixelFormat {
+            hardware_accelerated: true,
+            color_bits: 0,
+            alpha_bits: 0,
+            depth_bits: 0,
+            float_depth_attachments: false,
+            stencil_bits: 0,
+            packed_depth_stencil: false,
+            stereoscopy: false,
+            double_buffer: false,
+            multisampling: None,
+            srgb: false,
+            native_renderable: false,
+            bindable_to_texture: false,
+            color_buffer_type: ColorBufferType::Rgb,
+            transparent_type: TransparentType::None,
+            transparent_color: (0, 0, 0),
+        };
+
+        Ok(ContextPrototype {
+            opengl,
+            display,
+            display_ref,
+            egl_version,
+            extensions,
+            api,
+            version,
+            config_id: ffi::egl::NO_CONFIG_KHR,
+            pixel_format,
+            srgb_required: false,
+            swap_interval_range: SwapIntervalRange(0, 0),
+            release_behavior: ReleaseBehavior::Flush,
         })
     }
 
@@ -452,6 +801,10 @@ impl Context {
     }
 
     pub unsafe fn make_current(&self) -> Result<(), ContextError> {
+        // The bound API is per-thread state, and another context may have
+        // changed it since this one was created.
+        self.bind_api()?;
+
         let egl = EGL.as_ref().unwrap();
         let surface = self.surface.as_ref().map(|s| *s.lock()).unwrap_or(ffi::egl::NO_SURFACE);
         let ret = egl.MakeCurrent(self.display, surface, surface, self.context);
@@ -459,6 +812,61 @@ impl Context {
         self.check_make_current(Some(ret))
     }
 
+    pub unsafe fn make_current_asymmetric(&self, read: &Context) -> Result<(), ContextError> {
+        let egl = EGL.as_ref().unwrap();
+
+        let (draw_surface, read_surface) = match (self.surface.as_ref(), read.surface.as_ref()) {
+            (Some(draw), Some(read)) => (*draw.lock(), *read.lock()),
+            _ => {
+                return Err(ContextError::OsError(
+                    "make_current_asymmetric: both contexts need a surface".to_string(),
+                ))
+            }
+        };
+
+        // `eglMakeCurrent` fails with `EGL_BAD_MATCH` otherwise, which
+        // `check_make_current` would treat as fatal.
+        if self.display != read.display {
+            return Err(ContextError::OsError(
+                "make_current_asymmetric: the read surface belongs to another EGL display"
+                    .to_string(),
+            ));
+        }
+
+        let mut draw_config_id = 0;
+        let mut read_config_id = 0;
+        if egl.QueryContext(
+            self.display,
+            self.context,
+            ffi::egl::CONFIG_ID as ffi::egl::types::EGLint,
+            &mut draw_config_id,
+        ) == ffi::egl::FALSE
+            || egl.QuerySurface(
+                self.display,
+                read_surface,
+                ffi::egl::CONFIG_ID as ffi::egl::types::EGLint,
+                &mut read_config_id,
+            ) == ffi::egl::FALSE
+        {
+            return Err(ContextError::OsError(format!(
+                "make_current_asymmetric: querying EGL_CONFIG_ID failed (eglGetError returned 0x{:x})",
+                egl.GetError()
+            )));
+        }
+        if draw_config_id != read_config_id {
+            return Err(ContextError::OsError(
+                "make_current_asymmetric: the read surface was created with another EGL config"
+                    .to_string(),
+            ));
+        }
+
+        self.bind_api()?;
+
+        let ret = egl.MakeCurrent(self.display, draw_surface, read_surface, self.context);
+
+        self.check_make_current(Some(ret))
+    }
+
     pub unsafe fn make_not_current(&self) -> Result<(), ContextError> {
         let egl = EGL.as_ref().unwrap();
 
@@ -469,24 +877,230 @@ impl Context {
             false
         };
 
-        if surface_eq || egl.GetCurrentContext() == self.context {
-            let ret = egl.MakeCurrent(
+        // Nothing to release if this context wasn't current anyway.
+        if !surface_eq && egl.GetCurrentContext() != self.context {
+            return Ok(());
+        }
+
+        let ret = egl.MakeCurrent(
+            self.display,
+            ffi::egl::NO_SURFACE,
+            ffi::egl::NO_SURFACE,
+            ffi::egl::NO_CONTEXT,
+        );
+        if ret != 0 {
+            return Ok(());
+        }
+
+        // This usually runs in cleanup paths, so report failures instead of
+        // panicking like `check_make_current` does, e.g. when the context or
+        // display was already destroyed behind our back.
+        match egl.GetError() as u32 {
+            ffi::egl::CONTEXT_LOST => Err(ContextError::ContextLost),
+            ffi::egl::BAD_CONTEXT => Err(ContextError::OsError(
+                "eglMakeCurrent failed: the context is not valid".to_string(),
+            )),
+            ffi::egl::BAD_DISPLAY | ffi::egl::NOT_INITIALIZED => Err(ContextError::OsError(
+                "eglMakeCurrent failed: the display is not valid".to_string(),
+            )),
+            err => Err(ContextError::OsError(format!(
+                "eglMakeCurrent failed (eglGetError returned 0x{:x})",
+                err
+            ))),
+        }
+    }
+
+    pub fn bind_api(&self) -> Result<(), ContextError> {
+        // `eglBindAPI` was introduced in EGL 1.2; before that only OpenGL ES
+        // existed.
+        if self.egl_version < (1, 2) {
+            return Ok(());
+        }
+
+        let egl = EGL.as_ref().unwrap();
+        let api = match self.api {
+            Api::OpenGl => ffi::egl::OPENGL_API,
+            Api::OpenGlEs => ffi::egl::OPENGL_ES_API,
+            Api::WebGl => unreachable!(),
+        };
+
+        if unsafe { egl.BindAPI(api) } == 0 {
+            Err(ContextError::OsError(format!("eglBindAPI failed: 0x{:x}", unsafe {
+                egl.GetError()
+            })))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Replaces both the window surface and the context with new ones for the
+    /// same window and config, e.g. once a reset has lost them.
+    ///
+    /// The new context is created with the version, robustness and debug flag
+    /// the old one ended up with, shares with nothing, and is made current if
+    /// the old one was. The vsync mode is applied to the new surface.
+    ///
+    /// If this fails, the old context and surface are gone and whatever could
+    /// be created is kept: at worst neither exists, and making the context
+    /// current fails until a later call succeeds.
+    pub fn recreate(&mut self) -> Result<(), CreationError> {
+        self.rebuild(std::ptr::null())?;
+        self.share = std::ptr::null();
+        Ok(())
+    }
+
+    /// Like [`recreate()`][Self::recreate()], but the new context shares with
+    /// the context the old one was created sharing with, which must still
+    /// exist.
+    pub fn recreate_surface(&mut self) -> Result<(), CreationError> {
+        let egl = EGL.as_ref().unwrap();
+        let mut value = 0;
+        if !self.share.is_null()
+            && unsafe {
+                egl.QueryContext(
+                    self.display,
+                    self.share,
+                    ffi::egl::CONFIG_ID as ffi::egl::types::EGLint,
+                    &mut value,
+                )
+            } == ffi::egl::FALSE
+        {
+            return Err(CreationError::OsError(
+                "the context this one shared with no longer exists".to_string(),
+            ));
+        }
+        self.rebuild(self.share)
+    }
+
+    /// Destroys the context and its window surface, and creates new ones
+    /// sharing with `share`.
+    ///
+    /// Fails right away once the surface was swapped out with
+    /// [`replace_surface()`][Self::replace_surface()], as glutin no longer
+    /// knows which window it belongs to.
+    fn rebuild(&mut self, share: ffi::EGLContext) -> Result<(), CreationError> {
+        if self.surface_replaced.load(std::sync::atomic::Ordering::Relaxed) {
+            return Err(CreationError::NotSupported(
+                "the surface was replaced by the caller".to_string(),
+            ));
+        }
+        let nwin = match self.native_window {
+            Some(nwin) => nwin,
+            None => {
+                return Err(CreationError::NotSupported(
+                    "only window surfaces can be recreated".to_string(),
+                ))
+            }
+        };
+        let egl = EGL.as_ref().unwrap();
+        let was_current = self.is_current();
+
+        unsafe {
+            if was_current {
+                egl.MakeCurrent(
+                    self.display,
+                    ffi::egl::NO_SURFACE,
+                    ffi::egl::NO_SURFACE,
+                    ffi::egl::NO_CONTEXT,
+                );
+            }
+            if self.context != ffi::egl::NO_CONTEXT {
+                egl.DestroyContext(self.display, self.context);
+                self.context = ffi::egl::NO_CONTEXT;
+            }
+
+            // A window can only have one surface at a time, so the old one has
+            // to go first.
+            let mut surface = self.surface.as_ref().unwrap().lock();
+            if *surface != ffi::egl::NO_SURFACE {
+                egl.DestroySurface(self.display, *surface);
+            }
+            let attrs = surface_attribs(&self.pixel_format, Vec::new());
+            *surface = egl.CreateWindowSurface(self.display, self.config_id, nwin, attrs.as_ptr());
+            if surface.is_null() {
+                *surface = ffi::egl::NO_SURFACE;
+                return Err(egl_error("eglCreateWindowSurface"));
+            }
+
+            let (context, robustness, debug) = create_context(
+                self.display,
+                &self.egl_version,
+                &self.extensions,
+                self.api,
+                self.version,
+                self.context_config,
+                self.debug,
+                self.robustness,
+                self.memory_purge_handling,
+                self.release_behavior,
+                self.priority,
+                &self.raw_context_attributes,
+                share,
+            )?;
+            self.context = context;
+            self.robustness = robustness;
+            self.debug = debug;
+
+            let guard = MakeCurrentGuard::new(self.display, *surface, *surface, self.context)
+                .map_err(CreationError::OsError)?;
+            egl.SwapInterval(self.display, self.vsync.lock().get_swap_interval());
+            drop(guard);
+            drop(surface);
+
+            if was_current {
+                self.make_current().map_err(|err| CreationError::OsError(err.to_string()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Swaps the surface this context renders to for `new_surface`, keeping
+    /// the context itself.
+    ///
+    /// The context is released first and the old surface destroyed, so a
+    /// window that only allows one surface at a time can be given a new one.
+    /// It's made current again with the vsync mode reapplied if it was
+    /// current before. The context takes ownership of `new_surface`, and
+    /// [`recreate_surface()`][Self::recreate_surface()] and
+    /// [`resize_pbuffer()`][Self::resize_pbuffer()] no longer apply to it.
+    pub unsafe fn replace_surface(
+        &self,
+        new_surface: ffi::egl::types::EGLSurface,
+    ) -> Result<(), ContextError> {
+        let mut surface = match self.surface {
+            Some(ref surface) => surface.lock(),
+            None => return Err(ContextError::FunctionUnavailable),
+        };
+        let egl = EGL.as_ref().unwrap();
+        let was_current = self.is_current();
+
+        if was_current {
+            egl.MakeCurrent(
                 self.display,
                 ffi::egl::NO_SURFACE,
                 ffi::egl::NO_SURFACE,
                 ffi::egl::NO_CONTEXT,
             );
+        }
+        if *surface != ffi::egl::NO_SURFACE {
+            egl.DestroySurface(self.display, *surface);
+        }
+        *surface = new_surface;
+        self.surface_replaced.store(true, std::sync::atomic::Ordering::Relaxed);
+        drop(surface);
 
-            self.check_make_current(Some(ret))
-        } else {
-            self.check_make_current(None)
+        if was_current {
+            self.make_current()?;
+            egl.SwapInterval(self.display, self.vsync.lock().get_swap_interval());
         }
+        Ok(())
     }
 
     #[inline]
     pub fn is_current(&self) -> bool {
         let egl = EGL.as_ref().unwrap();
-        unsafe { egl.GetCurrentContext() == self.context }
+        // A failed `recreate()` leaves no context to be current.
+        self.context != ffi::egl::NO_CONTEXT && unsafe { egl.GetCurrentContext() == self.context }
     }
 
     #[inline]
@@ -502,75 +1116,258 @@ impl Context {
 
     pub fn set_vsync_mode(&self, mode: VSyncMode) -> Result<(), VSyncError> {
         unsafe {
+            let egl = EGL.as_ref().unwrap();
             let surface = self.surface.as_ref().map(|s| *s.lock()).unwrap_or(ffi::egl::NO_SURFACE);
-            let _guard = MakeCurrentGuard::new(self.display, surface, surface, self.context)
-                .map_err(|e| VSyncError::ContextError(ContextError::OsError(e)))?;
 
-            let egl = EGL.as_ref().unwrap();
-            if egl.SwapInterval(self.display, mode.get_swap_interval())
-                == ffi::egl::FALSE
-            {
+            let _guard = if is_bound(
+                self.context,
+                surface,
+                egl.GetCurrentContext(),
+                egl.GetCurrentSurface(ffi::egl::DRAW as ffi::egl::types::EGLint),
+            ) {
+                None
+            } else {
+                let guard = MakeCurrentGuard::new(self.display, surface, surface, self.context)
+                    .map_err(|e| VSyncError::ContextError(ContextError::OsError(e)))?;
+                Some(guard)
+            };
+
+            if egl.SwapInterval(self.display, mode.get_swap_interval()) == ffi::egl::FALSE {
                 panic!("finish_impl: eglSwapInterval failed: 0x{:x}", egl.GetError());
             }
+            *self.vsync.lock() = mode;
 
             Ok(())
         }
     }
 
     #[inline]
-    pub unsafe fn raw_handle(&self) -> ffi::egl::types::EGLContext {
-        self.context
+    pub fn get_vsync_mode(&self) -> VSyncMode {
+        *self.vsync.lock()
     }
 
+    /// The smallest and largest swap intervals the config supports.
     #[inline]
-    pub unsafe fn get_egl_display(&self) -> ffi::egl::types::EGLDisplay {
-        self.display
+    pub fn vsync_range(&self) -> (i32, i32) {
+        let SwapIntervalRange(min, max) = self.swap_interval_range;
+        (min, max)
     }
 
-    #[inline]
-    pub fn get_proc_address(&self, addr: &str) -> *const core::ffi::c_void {
-        let egl = EGL.as_ref().unwrap();
-        let addr = CString::new(addr.as_bytes()).unwrap();
-        let addr = addr.as_ptr();
-        unsafe { egl.GetProcAddress(addr) as *const _ }
-    }
+    /// Queries the priority the driver actually gave the context.
+    pub fn context_priority(&self) -> Option<Priority> {
+        if !self.extensions.iter().any(|s| s == "EGL_IMG_context_priority") {
+            return None;
+        }
 
-    #[inline]
-    pub fn swap_buffers(&self) -> Result<(), ContextError> {
         let egl = EGL.as_ref().unwrap();
-        let surface = self.surface.as_ref().unwrap().lock();
-        if *surface == ffi::egl::NO_SURFACE {
-            return Err(ContextError::ContextLost);
+        let mut value = 0;
+        if unsafe {
+            egl.QueryContext(
+                self.display,
+                self.context,
+                ffi::egl::CONTEXT_PRIORITY_LEVEL_IMG as ffi::egl::types::EGLint,
+                &mut value,
+            )
+        } == ffi::egl::FALSE
+        {
+            return None;
         }
 
-        let ret = unsafe { egl.SwapBuffers(self.display, *surface) };
-
-        if ret == 0 {
-            match unsafe { egl.GetError() } as u32 {
-                ffi::egl::CONTEXT_LOST => Err(ContextError::ContextLost),
-                err => {
-                    panic!("swap_buffers: eglSwapBuffers failed (eglGetError returned 0x{:x})", err)
-                }
-            }
-        } else {
-            Ok(())
+        match value as ffi::egl::types::EGLenum {
+            ffi::egl::CONTEXT_PRIORITY_LOW_IMG => Some(Priority::Low),
+            ffi::egl::CONTEXT_PRIORITY_MEDIUM_IMG => Some(Priority::Medium),
+            ffi::egl::CONTEXT_PRIORITY_HIGH_IMG => Some(Priority::High),
+            _ => None,
         }
     }
 
     #[inline]
-    #[cfg(not(target_os = "windows"))]
-    pub fn swap_buffers_with_damage(&self, rects: &[Rect]) -> Result<(), ContextError> {
-        let egl = EGL.as_ref().unwrap();
+    pub unsafe fn raw_handle(&self) -> ffi::egl::types::EGLContext {
+        self.context
+    }
 
-        if !egl.SwapBuffersWithDamageKHR.is_loaded() {
-            return Err(ContextError::FunctionUnavailable);
-        }
+    #[inline]
+    pub fn share_token(&self) -> ShareToken {
+        unsafe { ShareToken::from_raw(self.display, self.config_id, self.context) }
+    }
 
+    /// The display and config this context was created with.
+    #[inline]
+    pub unsafe fn raw_config(&self) -> (ffi::egl::types::EGLDisplay, ffi::egl::types::EGLConfig) {
+        (self.display, self.config_id)
+    }
+
+    /// The `EGL_CONFIG_ID` of the config this context was created with, or 0
+    /// if it was created without one.
+    pub fn config_id(&self) -> i32 {
+        let egl = EGL.as_ref().unwrap();
+        match unsafe {
+            query_config_attribs(egl, self.display, self.config_id, &[ffi::egl::CONFIG_ID])
+        } {
+            Ok(attribs) => attribs[0],
+            Err(_) => 0,
+        }
+    }
+
+    /// The extensions of the display, as queried when the context was built.
+    #[inline]
+    pub fn egl_extensions(&self) -> &[String] {
+        &self.extensions
+    }
+
+    #[inline]
+    pub fn egl_version(&self) -> (i32, i32) {
+        self.egl_version
+    }
+
+    #[inline]
+    pub unsafe fn get_egl_display(&self) -> ffi::egl::types::EGLDisplay {
+        self.display
+    }
+
+    /// The surface bound for reading if `read`, otherwise for drawing, if
+    /// this context is current on the calling thread.
+    #[inline]
+    pub fn current_surface(&self, read: bool) -> Option<ffi::egl::types::EGLSurface> {
+        let egl = EGL.as_ref().unwrap();
+        let which = if read { ffi::egl::READ } else { ffi::egl::DRAW };
+        unsafe {
+            if egl.GetCurrentContext() != self.context {
+                return None;
+            }
+            let surface = egl.GetCurrentSurface(which as ffi::egl::types::EGLint);
+            if surface == ffi::egl::NO_SURFACE {
+                None
+            } else {
+                Some(surface)
+            }
+        }
+    }
+
+    /// The surface this context was created with, whether or not it is
+    /// current.
+    #[inline]
+    pub unsafe fn raw_surface(&self) -> Option<ffi::egl::types::EGLSurface> {
+        let surface = *self.surface.as_ref()?.lock();
+        if surface == ffi::egl::NO_SURFACE {
+            None
+        } else {
+            Some(surface)
+        }
+    }
+
+    /// Whether this context can be made current without a surface.
+    ///
+    /// The GL side is checked by briefly making the context current without a
+    /// surface, which only happens once the display advertises
+    /// `EGL_KHR_surfaceless_context`.
+    #[inline]
+    pub fn supports_surfaceless(&self) -> bool {
+        self.extensions.iter().any(|s| s == "EGL_KHR_surfaceless_context")
+            && self.gl_supports_surfaceless()
+    }
+
+    /// Whether the GL side of this surfaceless context can be made current,
+    /// as `EGL_KHR_surfaceless_context` only covers EGL.
+    ///
+    /// OpenGL ES before 3.0 needs `GL_OES_surfaceless_context` for that.
+    fn gl_supports_surfaceless(&self) -> bool {
+        const GL_VERSION: u32 = 0x1F02;
+        const GL_EXTENSIONS: u32 = 0x1F03;
+
+        let _guard = match MakeCurrentGuard::new(
+            self.display,
+            ffi::egl::NO_SURFACE,
+            ffi::egl::NO_SURFACE,
+            self.context,
+        ) {
+            Ok(guard) => guard,
+            Err(_) => return false,
+        };
+
+        if self.api != Api::OpenGlEs {
+            return true;
+        }
+
+        let ptr = self.get_proc_address("glGetString");
+        if ptr.is_null() {
+            return false;
+        }
+        let get_string = unsafe {
+            std::mem::transmute::<*const core::ffi::c_void, extern "system" fn(u32) -> *const u8>(
+                ptr,
+            )
+        };
+        let get = |name| {
+            let string = get_string(name);
+            if string.is_null() {
+                String::new()
+            } else {
+                unsafe { CStr::from_ptr(string as *const _) }.to_string_lossy().into_owned()
+            }
+        };
+
+        // e.g. "OpenGL ES 3.2 Mesa 22.0.0"
+        let major = get(GL_VERSION)
+            .trim_start_matches("OpenGL ES")
+            .trim_start_matches("-CM")
+            .trim_start()
+            .split('.')
+            .next()
+            .and_then(|major| major.parse::<u8>().ok());
+        if major >= Some(3) {
+            return true;
+        }
+        get(GL_EXTENSIONS).split(' ').any(|ext| ext == "GL_OES_surfaceless_context")
+    }
+
+    #[inline]
+    pub fn get_proc_address(&self, addr: &str) -> *const core::ffi::c_void {
+        let egl = EGL.as_ref().unwrap();
+        let addr = CString::new(addr.as_bytes()).unwrap();
+        let addr = addr.as_ptr();
+        unsafe { egl.GetProcAddress(addr) as *const _ }
+    }
+
+    #[inline]
+    pub fn swap_buffers(&self) -> Result<(), ContextError> {
+        let egl = EGL.as_ref().unwrap();
         let surface = self.surface.as_ref().unwrap().lock();
         if *surface == ffi::egl::NO_SURFACE {
             return Err(ContextError::ContextLost);
         }
 
+        let ret = unsafe { egl.SwapBuffers(self.display, *surface) };
+
+        if ret == 0 {
+            match unsafe { egl.GetError() } as u32 {
+                ffi::egl::CONTEXT_LOST => Err(ContextError::ContextLost),
+                err => {
+                    panic!("swap_buffers: eglSwapBuffers failed (eglGetError returned 0x{:x})", err)
+                }
+            }
+        } else {
+            self.track_swap();
+            Ok(())
+        }
+    }
+
+    #[inline]
+    pub fn swap_buffers_with_damage(&self, rects: &[Rect]) -> Result<(), ContextError> {
+        let egl = EGL.as_ref().unwrap();
+
+        if !self.swap_buffers_with_damage_supported() {
+            return Err(ContextError::FunctionUnavailable);
+        }
+
+        let surface = match self.surface {
+            Some(ref surface) => surface.lock(),
+            None => return Err(ContextError::FunctionUnavailable),
+        };
+        if *surface == ffi::egl::NO_SURFACE {
+            return Err(ContextError::ContextLost);
+        }
+
         let mut ffirects: Vec<ffi::egl::types::EGLint> = Vec::with_capacity(rects.len() * 4);
 
         for rect in rects {
@@ -581,12 +1378,21 @@ impl Context {
         }
 
         let ret = unsafe {
-            egl.SwapBuffersWithDamageKHR(
-                self.display,
-                *surface,
-                ffirects.as_mut_ptr(),
-                rects.len() as ffi::egl::types::EGLint,
-            )
+            if self.swap_buffers_with_damage_khr_supported() {
+                egl.SwapBuffersWithDamageKHR(
+                    self.display,
+                    *surface,
+                    ffirects.as_mut_ptr(),
+                    rects.len() as ffi::egl::types::EGLint,
+                )
+            } else {
+                egl.SwapBuffersWithDamageEXT(
+                    self.display,
+                    *surface,
+                    ffirects.as_mut_ptr(),
+                    rects.len() as ffi::egl::types::EGLint,
+                )
+            }
         };
 
         if ret == ffi::egl::FALSE {
@@ -597,15 +1403,52 @@ impl Context {
                 }
             }
         } else {
+            self.track_swap();
             Ok(())
         }
     }
 
+    /// Whether the display advertises `EGL_KHR_swap_buffers_with_damage` or
+    /// `EGL_EXT_swap_buffers_with_damage`, which some drivers only ship.
+    /// The entry point alone isn't enough, as libEGL may export it for
+    /// drivers that don't implement it, e.g. with ANGLE on Windows.
     #[inline]
-    #[cfg(not(target_os = "windows"))]
     pub fn swap_buffers_with_damage_supported(&self) -> bool {
+        let egl = EGL.as_ref().unwrap();
+        self.swap_buffers_with_damage_khr_supported()
+            || egl.SwapBuffersWithDamageEXT.is_loaded()
+                && self.extensions.iter().any(|s| s == "EGL_EXT_swap_buffers_with_damage")
+    }
+
+    /// Whether the `KHR` variant can be used, which is preferred over the
+    /// `EXT` one.
+    fn swap_buffers_with_damage_khr_supported(&self) -> bool {
         let egl = EGL.as_ref().unwrap();
         egl.SwapBuffersWithDamageKHR.is_loaded()
+            && self.extensions.iter().any(|s| s == "EGL_KHR_swap_buffers_with_damage")
+    }
+
+    /// Replaces the fence marking the end of the last frame, if
+    /// `would_block_on_swap()` was ever called.
+    fn track_swap(&self) {
+        if self.track_swaps.load(std::sync::atomic::Ordering::Relaxed) {
+            *self.swap_fence.lock() = self.create_fence().ok();
+        }
+    }
+
+    /// Guesses whether the next swap would block on vsync, from whether the
+    /// GPU is still busy with the frame before it.
+    ///
+    /// Swaps are only tracked from the first call on, which returns `false`.
+    pub fn would_block_on_swap(&self) -> bool {
+        if self.vsync.lock().get_swap_interval() == 0 {
+            return false;
+        }
+        self.track_swaps.store(true, std::sync::atomic::Ordering::Relaxed);
+        match *self.swap_fence.lock() {
+            Some(ref fence) => !fence.wait(0).unwrap_or(true),
+            None => false,
+        }
     }
 
     #[inline]
@@ -614,9 +1457,372 @@ impl Context {
     }
 
     #[inline]
-    pub fn buffer_age(&self) -> u32 {
+    pub fn creation_report(&self) -> CreationReport {
+        CreationReport {
+            backend: Backend::Egl,
+            backend_version: Some((self.egl_version.0 as u8, self.egl_version.1 as u8)),
+            api: self.api,
+            version: Some(self.version),
+            robustness: Some(self.robustness),
+            debug: Some(self.debug),
+            extensions: self.extensions.clone(),
+            pixel_format: self.pixel_format.clone(),
+        }
+    }
+
+    /// The lines only EGL can contribute to a diagnostic report.
+    pub fn diagnostic_details(&self) -> Vec<(&'static str, String)> {
+        let egl = EGL.as_ref().unwrap();
+        let query_string = |name: ffi::egl::types::EGLenum| unsafe {
+            let p = egl.QueryString(self.display, name as ffi::egl::types::EGLint);
+            if p.is_null() {
+                String::new()
+            } else {
+                CStr::from_ptr(p).to_string_lossy().into_owned()
+            }
+        };
+        // Contexts without a config, see `EGL_KHR_no_config_context`, have
+        // nothing to query.
+        let config_id = match unsafe {
+            query_config_attribs(egl, self.display, self.config_id, &[ffi::egl::CONFIG_ID])
+        } {
+            Ok(attribs) => attribs[0].to_string(),
+            Err(_) => "none".to_string(),
+        };
+        let SwapIntervalRange(min, max) = self.swap_interval_range;
+
+        vec![
+            ("EGL vendor", query_string(ffi::egl::VENDOR)),
+            ("EGL client APIs", query_string(ffi::egl::CLIENT_APIS)),
+            ("EGL config ID", config_id),
+            ("Swap interval range", format!("{}..={}", min, max)),
+        ]
+    }
+
+    #[inline]
+    pub fn surface_size(&self) -> Option<dpi::PhysicalSize<u32>> {
         let egl = EGL.as_ref().unwrap();
-        let surface = self.surface.as_ref().unwrap().lock();
+        let surface = *self.surface.as_ref()?.lock();
+        if surface == ffi::egl::NO_SURFACE {
+            return None;
+        }
+
+        let (mut width, mut height) = (0, 0);
+        unsafe {
+            if egl.QuerySurface(self.display, surface, ffi::egl::WIDTH as i32, &mut width)
+                == ffi::egl::FALSE
+                || egl.QuerySurface(self.display, surface, ffi::egl::HEIGHT as i32, &mut height)
+                    == ffi::egl::FALSE
+            {
+                return None;
+            }
+        }
+
+        Some(dpi::PhysicalSize::new(width as u32, height as u32))
+    }
+
+    /// Replaces the pbuffer with a new one of `size`, rebinding it if it was
+    /// bound on the calling thread. The contents are lost.
+    pub fn resize_pbuffer(&self, size: dpi::PhysicalSize<u32>) -> Result<(), ContextError> {
+        let mut surface = match self.surface {
+            Some(ref surface) if self.pbuffer => surface.lock(),
+            _ => return Err(ContextError::FunctionUnavailable),
+        };
+        // Checked under the lock `replace_surface()` sets it under.
+        if self.surface_replaced.load(std::sync::atomic::Ordering::Relaxed) {
+            return Err(ContextError::FunctionUnavailable);
+        }
+
+        let egl = EGL.as_ref().unwrap();
+        let attrs = surface_attribs(
+            &self.pixel_format,
+            vec![
+                ffi::egl::WIDTH as raw::c_int,
+                size.width as raw::c_int,
+                ffi::egl::HEIGHT as raw::c_int,
+                size.height as raw::c_int,
+            ],
+        );
+
+        unsafe {
+            let new_surface =
+                egl.CreatePbufferSurface(self.display, self.config_id, attrs.as_ptr());
+            if new_surface.is_null() || new_surface == ffi::egl::NO_SURFACE {
+                return Err(ContextError::OsError(format!(
+                    "eglCreatePbufferSurface failed: 0x{:x}",
+                    egl.GetError()
+                )));
+            }
+
+            if egl.GetCurrentContext() == self.context {
+                let rebind = |current: ffi::egl::types::EGLSurface| {
+                    if current == *surface {
+                        new_surface
+                    } else {
+                        current
+                    }
+                };
+                let draw = rebind(egl.GetCurrentSurface(ffi::egl::DRAW as i32));
+                let read = rebind(egl.GetCurrentSurface(ffi::egl::READ as i32));
+                if egl.MakeCurrent(self.display, draw, read, self.context) == ffi::egl::FALSE {
+                    let err = egl.GetError();
+                    egl.DestroySurface(self.display, new_surface);
+                    return Err(ContextError::OsError(format!(
+                        "eglMakeCurrent failed: 0x{:x}",
+                        err
+                    )));
+                }
+            }
+
+            egl.DestroySurface(self.display, *surface);
+            *surface = new_surface;
+        }
+        Ok(())
+    }
+
+    pub fn finish_with_timeout(&self, timeout: Duration) -> Result<bool, ContextError> {
+        // Durations that don't fit saturate to `EGL_FOREVER_KHR`.
+        let timeout = timeout.as_nanos().min(ffi::egl::FOREVER as u128) as u64;
+        self.create_fence()?.wait(timeout)
+    }
+
+    /// Inserts a fence into this context's command stream, which the calling
+    /// thread must have made current.
+    pub fn create_fence(&self) -> Result<EglSync, ContextError> {
+        let egl = EGL.as_ref().unwrap();
+
+        if !self.extensions.iter().any(|s| s == "EGL_KHR_fence_sync")
+            || !egl.CreateSyncKHR.is_loaded()
+        {
+            return Err(ContextError::FunctionUnavailable);
+        }
+
+        let sync =
+            unsafe { egl.CreateSyncKHR(self.display, ffi::egl::SYNC_FENCE_KHR, std::ptr::null()) };
+        if sync == ffi::egl::NO_SYNC {
+            return Err(ContextError::OsError(format!(
+                "eglCreateSyncKHR failed: 0x{:x}",
+                unsafe { egl.GetError() }
+            )));
+        }
+        Ok(EglSync { display: self.display, sync, _display_ref: self.display_ref.clone() })
+    }
+
+    /// Waits up to `timeout_ns` for `sync` to be signaled, then swaps
+    /// whether it was or not.
+    pub fn swap_buffers_after(&self, sync: &EglSync, timeout_ns: u64) -> Result<(), ContextError> {
+        if sync.display != self.display {
+            return Err(ContextError::OsError(
+                "swap_buffers_after: the fence belongs to another EGL display".to_string(),
+            ));
+        }
+        sync.wait(timeout_ns)?;
+        self.swap_buffers()
+    }
+
+    /// Sets the time at which the compositor should present the frame
+    /// submitted by the next `eglSwapBuffers`.
+    pub fn set_presentation_time(&self, nanos: i64) -> Result<(), ContextError> {
+        let presentation_time = match *PRESENTATION_TIME_ANDROID {
+            Some(f) if self.extensions.iter().any(|s| s == "EGL_ANDROID_presentation_time") => f,
+            _ => return Err(ContextError::FunctionUnavailable),
+        };
+
+        let surface = match self.surface {
+            Some(ref surface) => surface.lock(),
+            None => return Err(ContextError::FunctionUnavailable),
+        };
+        if *surface == ffi::egl::NO_SURFACE {
+            return Err(ContextError::ContextLost);
+        }
+
+        unsafe {
+            if presentation_time(self.display, *surface, nanos) == ffi::egl::FALSE {
+                return Err(ContextError::OsError(format!(
+                    "eglPresentationTimeANDROID failed: 0x{:x}",
+                    EGL.as_ref().unwrap().GetError()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the window surface if the display advertises
+    /// `EGL_ANDROID_get_frame_timestamps`, after turning timestamp collection
+    /// on for it.
+    fn frame_timestamps_surface(
+        &self,
+    ) -> Result<parking_lot::MutexGuard<'_, ffi::egl::types::EGLSurface>, ContextError> {
+        if !self.extensions.iter().any(|s| s == "EGL_ANDROID_get_frame_timestamps") {
+            return Err(ContextError::FunctionUnavailable);
+        }
+        let surface = match self.surface {
+            Some(ref surface) => surface.lock(),
+            None => return Err(ContextError::FunctionUnavailable),
+        };
+        if *surface == ffi::egl::NO_SURFACE {
+            return Err(ContextError::ContextLost);
+        }
+
+        // Frames are only timed from here on.
+        let egl = EGL.as_ref().unwrap();
+        unsafe {
+            egl.SurfaceAttrib(
+                self.display,
+                *surface,
+                TIMESTAMPS_ANDROID as ffi::egl::types::EGLint,
+                ffi::egl::TRUE as ffi::egl::types::EGLint,
+            );
+        }
+        Ok(surface)
+    }
+
+    /// Returns the id of the frame the next `eglSwapBuffers` submits.
+    pub fn next_frame_id(&self) -> Result<u64, ContextError> {
+        let get_next_frame_id =
+            (*GET_NEXT_FRAME_ID_ANDROID).ok_or(ContextError::FunctionUnavailable)?;
+        let surface = self.frame_timestamps_surface()?;
+
+        let mut frame_id = 0;
+        unsafe {
+            if get_next_frame_id(self.display, *surface, &mut frame_id) == ffi::egl::FALSE {
+                return Err(ContextError::OsError(format!(
+                    "eglGetNextFrameIdANDROID failed: 0x{:x}",
+                    EGL.as_ref().unwrap().GetError()
+                )));
+            }
+        }
+        Ok(frame_id)
+    }
+
+    /// Queries when the frame `frame_id` went through each stage of
+    /// presentation.
+    pub fn frame_timestamps(&self, frame_id: u64) -> Result<FrameTimestamps, ContextError> {
+        let get_frame_timestamps =
+            (*GET_FRAME_TIMESTAMPS_ANDROID).ok_or(ContextError::FunctionUnavailable)?;
+        let surface = self.frame_timestamps_surface()?;
+
+        let names = [
+            REQUESTED_PRESENT_TIME_ANDROID,
+            RENDERING_COMPLETE_TIME_ANDROID,
+            COMPOSITION_LATCH_TIME_ANDROID,
+            DISPLAY_PRESENT_TIME_ANDROID,
+        ];
+        let mut values = [0; 4];
+        unsafe {
+            if get_frame_timestamps(
+                self.display,
+                *surface,
+                frame_id,
+                names.len() as ffi::egl::types::EGLint,
+                names.as_ptr(),
+                values.as_mut_ptr(),
+            ) == ffi::egl::FALSE
+            {
+                return Err(ContextError::OsError(format!(
+                    "eglGetFrameTimestampsANDROID failed: 0x{:x}",
+                    EGL.as_ref().unwrap().GetError()
+                )));
+            }
+        }
+
+        // `EGL_TIMESTAMP_PENDING_ANDROID` and `EGL_TIMESTAMP_INVALID_ANDROID`
+        // are negative.
+        let time = |value: i64| if value < 0 { None } else { Some(value) };
+        Ok(FrameTimestamps {
+            requested_present_time: time(values[0]),
+            rendering_complete_time: time(values[1]),
+            composition_latch_time: time(values[2]),
+            display_present_time: time(values[3]),
+        })
+    }
+
+    /// Sets `EGL_SWAP_BEHAVIOR` on the surface, which only flips a flag and
+    /// can be done every frame.
+    pub fn set_swap_behavior_preserved(&self, preserved: bool) -> Result<(), ContextError> {
+        let surface = match self.surface {
+            Some(ref surface) => surface.lock(),
+            None => return Err(ContextError::FunctionUnavailable),
+        };
+
+        let egl = EGL.as_ref().unwrap();
+        let (attribute, value) = swap_behavior_attrib(preserved);
+        unsafe {
+            if egl.SurfaceAttrib(self.display, *surface, attribute, value) == ffi::egl::FALSE {
+                return Err(ContextError::OsError(format!(
+                    "eglSurfaceAttrib failed: 0x{:x}",
+                    egl.GetError()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn swap_behavior(&self) -> Result<SwapBehavior, ContextError> {
+        let value = self.query_surface_attrib(ffi::egl::SWAP_BEHAVIOR)?;
+        Ok(if value == ffi::egl::BUFFER_PRESERVED as ffi::egl::types::EGLint {
+            SwapBehavior::Preserved
+        } else {
+            SwapBehavior::Destroyed
+        })
+    }
+
+    #[inline]
+    pub fn multisample_resolve(&self) -> Result<MultisampleResolve, ContextError> {
+        let value = self.query_surface_attrib(ffi::egl::MULTISAMPLE_RESOLVE)?;
+        Ok(if value == ffi::egl::MULTISAMPLE_RESOLVE_BOX as ffi::egl::types::EGLint {
+            MultisampleResolve::Box
+        } else {
+            MultisampleResolve::Default
+        })
+    }
+
+    /// Reads `attrib` of the surface with `eglQuerySurface`, failing with
+    /// [`ContextError::FunctionUnavailable`] for surfaceless contexts.
+    fn query_surface_attrib(
+        &self,
+        attrib: ffi::egl::types::EGLenum,
+    ) -> Result<ffi::egl::types::EGLint, ContextError> {
+        let surface = match self.surface {
+            Some(ref surface) => surface.lock(),
+            None => return Err(ContextError::FunctionUnavailable),
+        };
+
+        let egl = EGL.as_ref().unwrap();
+        let mut value = 0;
+        unsafe {
+            if egl.QuerySurface(
+                self.display,
+                *surface,
+                attrib as ffi::egl::types::EGLint,
+                &mut value,
+            ) == ffi::egl::FALSE
+            {
+                return Err(ContextError::OsError(format!(
+                    "eglQuerySurface failed: 0x{:x}",
+                    egl.GetError()
+                )));
+            }
+        }
+        Ok(value)
+    }
+
+    #[inline]
+    pub fn buffer_age_supported(&self) -> bool {
+        // Drivers advertise the `EGL_BUFFER_AGE_EXT` attribute through either
+        // extension.
+        self.extensions.iter().any(|s| s == "EGL_EXT_buffer_age" || s == "EGL_KHR_partial_update")
+    }
+
+    #[inline]
+    pub fn buffer_age(&self) -> Option<u32> {
+        if !self.buffer_age_supported() {
+            return None;
+        }
+
+        let egl = EGL.as_ref().unwrap();
+        let surface = self.surface.as_ref()?.lock();
 
         let mut buffer_age = 0;
         let result = unsafe {
@@ -629,9 +1835,9 @@ impl Context {
         };
 
         if result == ffi::egl::FALSE {
-            0
+            None
         } else {
-            buffer_age as u32
+            Some(buffer_age as u32)
         }
     }
 }
@@ -645,6 +1851,14 @@ impl Drop for Context {
             // https://stackoverflow.com/questions/54402688/recreate-eglcreatewindowsurface-with-same-native-window
             let egl = EGL.as_ref().unwrap();
             let surface = self.surface.as_ref().map(|s| *s.lock()).unwrap_or(ffi::egl::NO_SURFACE);
+
+            // A failed `recreate()` leaves only the surface behind.
+            if self.context == ffi::egl::NO_CONTEXT {
+                if surface != ffi::egl::NO_SURFACE {
+                    egl.DestroySurface(self.display, surface);
+                }
+                return;
+            }
             // Ok, so we got to call `glFinish` before destroying the context
             // to ensure it actually gets destroyed. This requires making the
             // this context current.
@@ -654,10 +1868,14 @@ impl Drop for Context {
 
             guard.if_any_same_then_invalidate(surface, surface, self.context);
 
-            let gl_finish_fn = self.get_proc_address("glFinish");
-            assert!(!gl_finish_fn.is_null());
-            let gl_finish_fn = std::mem::transmute::<_, extern "system" fn()>(gl_finish_fn);
-            gl_finish_fn();
+            // Unless the user vouched for the GPU being idle already.
+            if self.finish_on_drop {
+                let gl_finish_fn = self.get_proc_address("glFinish");
+                assert!(!gl_finish_fn.is_null());
+                let gl_finish_fn =
+                    std::mem::transmute::<*const raw::c_void, extern "system" fn()>(gl_finish_fn);
+                gl_finish_fn();
+            }
 
             egl.DestroyContext(self.display, self.context);
             self.context = ffi::egl::NO_CONTEXT;
@@ -667,59 +1885,8 @@ impl Drop for Context {
                 *surface = ffi::egl::NO_SURFACE;
             }
 
-            // In a reasonable world, we could uncomment the line bellow.
-            //
-            // This is no such world. Lets talk about something.
-            //
-            // You see, every call to `get_native_display` returns the exact
-            // same display, just look at the docs:
-            //
-            // "Multiple calls made to eglGetDisplay with the same display_id
-            // will return the same EGLDisplay handle."
-            //
-            // My EGL implementation does not do any ref counting, nor do the
-            // EGL docs mention ref counting anywhere. In fact, the docs state
-            // that there will be *no effect*, which, in a way, implies no ref
-            // counting:
-            //
-            // "Initializing an already initialized EGL display connection has
-            // no effect besides returning the version numbers."
-            //
-            // So, if we terminate the display, other people who are using it
-            // won't be so happy.
-            //
-            // Well, how did I stumble on this issue you might ask...
-            //
-            // In this case, the "other people" was us, for it appears my EGL
-            // implementation does not follow the docs,  or maybe I'm misreading
-            // them. You see, according to the egl docs:
-            //
-            // "To release the current context without assigning a new one, set
-            // context to EGL_NO_CONTEXT and set draw and read to
-            // EGL_NO_SURFACE.  [...] ******This is the only case where an
-            // uninitialized display may be passed to eglMakeCurrent.******"
-            // (Emphasis mine).
-            //
-            // Well, my computer returns EGL_BAD_DISPLAY if the display passed
-            // to eglMakeCurrent is uninitialized, which allowed to me to spot
-            // this issue.
-            //
-            // I would have assumed that if EGL was going to provide us with
-            // the same EGLDisplay that they'd at least do
-            // some ref counting, but they don't.
-            //
-            // FIXME: Technically we are leaking resources, not much we can do.
-            // Yeah, we could have a global static that does ref counting
-            // ourselves, but what if some other library is using the display.
-            //
-            // On unix operating systems, we could preload a little lib that
-            // does ref counting on that level, but:
-            //      A) What about other platforms?
-            //      B) Do you *really* want all glutin programs to preload a
-            //      library?
-            //      C) Who the hell is going to maintain that?
-            //
-            // egl.Terminate(self.display);
+            // `display_ref` terminates the display once nothing else of
+            // glutin's uses it any more.
         }
     }
 }
@@ -728,13 +1895,18 @@ impl Drop for Context {
 pub struct ContextPrototype<'a> {
     opengl: &'a GlAttributes<&'a Context>,
     display: ffi::egl::types::EGLDisplay,
+    display_ref: DisplayRef,
     egl_version: (ffi::egl::types::EGLint, ffi::egl::types::EGLint),
     extensions: Vec<String>,
     api: Api,
     version: Option<(u8, u8)>,
     config_id: ffi::egl::types::EGLConfig,
     pixel_format: PixelFormat,
+    /// Whether sRGB was asked for explicitly, so surfaces may not fall back
+    /// to a linear colorspace.
+    srgb_required: bool,
     swap_interval_range: SwapIntervalRange,
+    release_behavior: ReleaseBehavior,
 }
 
 #[cfg(any(
@@ -780,20 +1952,22 @@ impl<'a> ContextPrototype<'a> {
         get_native_visual_id(self.display, self.config_id)
     }
 
-    pub fn finish(self, nwin: ffi::EGLNativeWindowType) -> Result<Context, CreationError> {
+    pub fn finish(mut self, nwin: ffi::EGLNativeWindowType) -> Result<Context, CreationError> {
         let egl = EGL.as_ref().unwrap();
-        let surface = unsafe {
-            let surface =
-                egl.CreateWindowSurface(self.display, self.config_id, nwin, std::ptr::null());
-            if surface.is_null() {
-                return Err(CreationError::OsError("eglCreateWindowSurface failed".to_string()));
-            }
-            surface
-        };
+        let (display, config_id) = (self.display, self.config_id);
+        let surface = self.create_surface(Vec::new(), |attrs| unsafe {
+            egl.CreateWindowSurface(display, config_id, nwin, attrs.as_ptr())
+        });
+        if surface.is_null() {
+            return Err(egl_error("eglCreateWindowSurface"));
+        }
 
-        self.finish_impl(Some(surface))
+        let mut context = self.finish_impl(Some(surface))?;
+        context.native_window = Some(nwin);
+        Ok(context)
     }
 
+    /// Like [`finish()`][Self::finish()], for an X11 `Window`.
     #[cfg(any(
         target_os = "linux",
         target_os = "dragonfly",
@@ -801,14 +1975,87 @@ impl<'a> ContextPrototype<'a> {
         target_os = "netbsd",
         target_os = "openbsd",
     ))]
+    #[cfg(feature = "x11")]
+    pub fn finish_x11(self, xwin: raw::c_ulong) -> Result<Context, CreationError> {
+        if xwin == 0 {
+            return Err(CreationError::OsError("the X11 window is `None`".to_string()));
+        }
+        self.finish(xwin as ffi::EGLNativeWindowType)
+    }
+
+    /// Like [`finish()`][Self::finish()], for a `wl_surface` of the given
+    /// size.
+    ///
+    /// # Safety
+    ///
+    /// `wl_surface` must be a valid `wl_surface` that outlives the returned
+    /// `WlEglSurface`. The `WlEglSurface` backs the EGL surface, so it must
+    /// outlive the context.
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    ))]
+    #[cfg(feature = "wayland")]
+    pub unsafe fn finish_wayland(
+        self,
+        wl_surface: *mut raw::c_void,
+        width: u32,
+        height: u32,
+    ) -> Result<(Context, wayland_egl::WlEglSurface), CreationError> {
+        if wl_surface.is_null() {
+            return Err(CreationError::OsError("the wl_surface is null".to_string()));
+        }
+        let egl_surface = wayland_egl::WlEglSurface::new_from_raw(
+            wl_surface as *mut _,
+            width as i32,
+            height as i32,
+        );
+        let context = self.finish(egl_surface.ptr() as ffi::EGLNativeWindowType)?;
+        Ok((context, egl_surface))
+    }
+
+    /// Like [`finish()`][Self::finish()], for the `ANativeWindow` of a
+    /// window.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must refer to a valid `ANativeWindow` that outlives the
+    /// context.
+    #[cfg(target_os = "android")]
+    pub unsafe fn finish_android(
+        self,
+        handle: raw_window_handle::AndroidNdkWindowHandle,
+    ) -> Result<Context, CreationError> {
+        if handle.a_native_window.is_null() {
+            return Err(CreationError::OsError("the ANativeWindow is null".to_string()));
+        }
+        self.finish(handle.a_native_window as ffi::EGLNativeWindowType)
+    }
+
+    #[cfg(any(
+        target_os = "android",
+        target_os = "windows",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    ))]
     pub fn finish_surfaceless(self) -> Result<Context, CreationError> {
-        // FIXME: Also check for the GL_OES_surfaceless_context *CONTEXT*
-        // extension
         if !self.extensions.iter().any(|s| s == "EGL_KHR_surfaceless_context") {
-            Err(CreationError::NotSupported("EGL surfaceless not supported".to_string()))
-        } else {
-            self.finish_impl(None)
+            return Err(CreationError::NotSupported("EGL surfaceless not supported".to_string()));
+        }
+
+        let context = self.finish_impl(None)?;
+        if !context.gl_supports_surfaceless() {
+            return Err(CreationError::NotSupported(
+                "surfaceless context not supported by the GL implementation".to_string(),
+            ));
         }
+        Ok(context)
     }
 
     #[cfg(any(
@@ -820,10 +2067,61 @@ impl<'a> ContextPrototype<'a> {
         target_os = "netbsd",
         target_os = "openbsd",
     ))]
-    pub fn finish_pbuffer(self, size: dpi::PhysicalSize<u32>) -> Result<Context, CreationError> {
+    pub fn finish_pbuffer(
+        mut self,
+        size: dpi::PhysicalSize<u32>,
+    ) -> Result<Context, CreationError> {
         let size: (u32, u32) = size.into();
 
         let egl = EGL.as_ref().unwrap();
+        let (display, config_id) = (self.display, self.config_id);
+        let attrs = vec![
+            ffi::egl::WIDTH as raw::c_int,
+            size.0 as raw::c_int,
+            ffi::egl::HEIGHT as raw::c_int,
+            size.1 as raw::c_int,
+        ];
+        let surface = self.create_surface(attrs, |attrs| unsafe {
+            egl.CreatePbufferSurface(display, config_id, attrs.as_ptr())
+        });
+        if surface.is_null() {
+            return Err(egl_error("eglCreatePbufferSurface"));
+        }
+
+        let mut context = self.finish_impl(Some(surface))?;
+        context.pbuffer = true;
+        Ok(context)
+    }
+
+    /// Creates a context rendering into `stream` through a producer surface
+    /// of the given size.
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    ))]
+    pub fn finish_stream(
+        self,
+        stream: EglStream,
+        size: dpi::PhysicalSize<u32>,
+    ) -> Result<Context, CreationError> {
+        let egl = EGL.as_ref().unwrap();
+        if !self.extensions.iter().any(|s| s == "EGL_KHR_stream_producer_eglsurface")
+            || !egl.CreateStreamProducerSurfaceKHR.is_loaded()
+        {
+            return Err(CreationError::NotSupported(
+                "EGL_KHR_stream_producer_eglsurface is not supported".to_string(),
+            ));
+        }
+        if stream.display != self.display {
+            return Err(CreationError::PlatformSpecific(
+                "The stream belongs to a different EGL display".to_string(),
+            ));
+        }
+
+        let size: (u32, u32) = size.into();
         let attrs = &[
             ffi::egl::WIDTH as raw::c_int,
             size.0 as raw::c_int,
@@ -833,38 +2131,91 @@ impl<'a> ContextPrototype<'a> {
         ];
 
         let surface = unsafe {
-            let surface = egl.CreatePbufferSurface(self.display, self.config_id, attrs.as_ptr());
+            let surface = egl.CreateStreamProducerSurfaceKHR(
+                self.display,
+                self.config_id,
+                stream.stream,
+                attrs.as_ptr(),
+            );
             if surface.is_null() || surface == ffi::egl::NO_SURFACE {
-                return Err(CreationError::OsError("eglCreatePbufferSurface failed".to_string()));
+                return Err(egl_error("eglCreateStreamProducerSurfaceKHR"));
             }
             surface
         };
 
-        self.finish_impl(Some(surface))
+        let mut context = self.finish_impl(Some(surface))?;
+        context._stream = Some(stream);
+        Ok(context)
+    }
+
+    /// Terminates `attrs` for `eglCreate*Surface`, after asking for an sRGB
+    /// or stereo surface if the pixel format wants one.
+    fn surface_attribs(&self, attrs: Vec<raw::c_int>) -> Vec<raw::c_int> {
+        surface_attribs(&self.pixel_format, attrs)
+    }
+
+    /// Calls `create` with `attrs` completed by
+    /// [`surface_attribs()`][Self::surface_attribs()], dropping stereo and
+    /// then an sRGB colorspace that wasn't explicitly asked for from the
+    /// pixel format while the driver rejects them.
+    ///
+    /// Returns null if even the last attempt failed, with the EGL error of
+    /// that attempt pending.
+    fn create_surface(
+        &mut self,
+        attrs: Vec<raw::c_int>,
+        create: impl Fn(&[raw::c_int]) -> ffi::egl::types::EGLSurface,
+    ) -> ffi::egl::types::EGLSurface {
+        loop {
+            let surface = create(&self.surface_attribs(attrs.clone()));
+            if !surface.is_null() && surface != ffi::egl::NO_SURFACE {
+                return surface;
+            }
+            // The driver may not manage two views, or an sRGB colorspace,
+            // with this config.
+            if self.pixel_format.stereoscopy {
+                self.pixel_format.stereoscopy = false;
+            } else if self.pixel_format.srgb && !self.srgb_required {
+                self.pixel_format.srgb = false;
+            } else {
+                return std::ptr::null();
+            }
+        }
     }
 
     fn finish_impl(
         self,
         surface: Option<ffi::egl::types::EGLSurface>,
     ) -> Result<Context, CreationError> {
-        let share = match self.opengl.sharing {
-            Some(ctx) => ctx.context,
-            None => std::ptr::null(),
-        };
-
-        let context = unsafe {
+        let share = share_context(
+            self.display,
+            self.opengl.sharing.map(|ctx| (ctx.display, ctx.context)),
+            self.opengl.external_share.map(|token| (token.display(), token.context())),
+        )?;
+
+        // The surface still uses the chosen config, but the context can later
+        // be made current with surfaces of any other config.
+        let context_config =
+            if self.opengl.no_config_context { ffi::egl::NO_CONFIG_KHR } else { self.config_id };
+
+        let ((context, robustness, debug), version) = unsafe {
             if let Some(version) = self.version {
-                create_context(
+                let ctx = create_context(
                     self.display,
                     &self.egl_version,
                     &self.extensions,
                     self.api,
                     version,
-                    self.config_id,
+                    context_config,
                     self.opengl.debug,
                     self.opengl.robustness,
+                    self.opengl.memory_purge_handling,
+                    self.release_behavior,
+                    self.opengl.priority,
+                    &self.opengl.raw_context_attributes,
                     share,
-                )?
+                )?;
+                (ctx, version)
             } else if self.api == Api::OpenGlEs {
                 if let Ok(ctx) = create_context(
                     self.display,
@@ -872,24 +2223,32 @@ impl<'a> ContextPrototype<'a> {
                     &self.extensions,
                     self.api,
                     (2, 0),
-                    self.config_id,
+                    context_config,
                     self.opengl.debug,
                     self.opengl.robustness,
+                    self.opengl.memory_purge_handling,
+                    self.release_behavior,
+                    self.opengl.priority,
+                    &self.opengl.raw_context_attributes,
                     share,
                 ) {
-                    ctx
+                    (ctx, (2, 0))
                 } else if let Ok(ctx) = create_context(
                     self.display,
                     &self.egl_version,
                     &self.extensions,
                     self.api,
                     (1, 0),
-                    self.config_id,
+                    context_config,
                     self.opengl.debug,
                     self.opengl.robustness,
+                    self.opengl.memory_purge_handling,
+                    self.release_behavior,
+                    self.opengl.priority,
+                    &self.opengl.raw_context_attributes,
                     share,
                 ) {
-                    ctx
+                    (ctx, (1, 0))
                 } else {
                     return Err(CreationError::OpenGlVersionNotSupported);
                 }
@@ -899,72 +2258,1017 @@ impl<'a> ContextPrototype<'a> {
                 &self.extensions,
                 self.api,
                 (3, 2),
-                self.config_id,
+                context_config,
                 self.opengl.debug,
                 self.opengl.robustness,
+                self.opengl.memory_purge_handling,
+                self.release_behavior,
+                self.opengl.priority,
+                &self.opengl.raw_context_attributes,
                 share,
             ) {
-                ctx
+                (ctx, (3, 2))
             } else if let Ok(ctx) = create_context(
                 self.display,
                 &self.egl_version,
                 &self.extensions,
                 self.api,
                 (3, 1),
-                self.config_id,
+                context_config,
                 self.opengl.debug,
                 self.opengl.robustness,
+                self.opengl.memory_purge_handling,
+                self.release_behavior,
+                self.opengl.priority,
+                &self.opengl.raw_context_attributes,
                 share,
             ) {
-                ctx
+                (ctx, (3, 1))
             } else if let Ok(ctx) = create_context(
                 self.display,
                 &self.egl_version,
                 &self.extensions,
                 self.api,
                 (1, 0),
-                self.config_id,
+                context_config,
                 self.opengl.debug,
                 self.opengl.robustness,
+                self.opengl.memory_purge_handling,
+                self.release_behavior,
+                self.opengl.priority,
+                &self.opengl.raw_context_attributes,
                 share,
             ) {
-                ctx
+                (ctx, (1, 0))
             } else {
                 return Err(CreationError::OpenGlVersionNotSupported);
             }
         };
 
-        if let Some(surface) = surface {
-            // VSync defaults to enabled; disable it if it was not requested.
-            // if !self.opengl.vsync {
-            let _guard = MakeCurrentGuard::new(self.display, surface, surface, context)
-                .map_err(CreationError::OsError)?;
+        let vsync = if self.opengl.lowest_latency {
+            VSyncMode::SwapInterval(self.swap_interval_range.lowest_interval() as i8)
+        } else {
+            desired_vsync_modes(self.opengl)
+                .iter()
+                .copied()
+                .find(|mode| self.swap_interval_range.contains(mode.get_swap_interval()))
+                .unwrap_or(self.opengl.vsync)
+        };
+
+        let mut render_buffer = ffi::egl::BACK_BUFFER as ffi::egl::types::EGLint;
+        if let Some(surface) = surface {
+            // VSync defaults to enabled; disable it if it was not requested.
+            // if !self.opengl.vsync {
+            let _guard = MakeCurrentGuard::new(self.display, surface, surface, context)
+                .map_err(CreationError::OsError)?;
+
+            let egl = EGL.as_ref().unwrap();
+            unsafe {
+                if egl.SwapInterval(self.display, vsync.get_swap_interval()) == ffi::egl::FALSE {
+                    panic!("finish_impl: eglSwapInterval failed: 0x{:x}", egl.GetError());
+                }
+                // The surface only reports what was asked for, the context
+                // reports which buffer it actually renders to, but only while
+                // it is bound.
+                egl.QueryContext(
+                    self.display,
+                    context,
+                    ffi::egl::RENDER_BUFFER as ffi::egl::types::EGLint,
+                    &mut render_buffer,
+                );
+            }
+            // }
+        }
+
+        // Drivers may fall back to a linear surface, so trust the surface
+        // rather than what was asked for.
+        let mut pixel_format = self.pixel_format;
+        if let Some(surface) = surface {
+            let egl = EGL.as_ref().unwrap();
+            pixel_format.double_buffer =
+                render_buffer != ffi::egl::SINGLE_BUFFER as ffi::egl::types::EGLint;
+            let mut colorspace = 0;
+            pixel_format.srgb = unsafe {
+                egl.QuerySurface(
+                    self.display,
+                    surface,
+                    ffi::egl::GL_COLORSPACE_KHR as ffi::egl::types::EGLint,
+                    &mut colorspace,
+                ) != ffi::egl::FALSE
+                    && colorspace == ffi::egl::GL_COLORSPACE_SRGB_KHR as ffi::egl::types::EGLint
+            };
+            if pixel_format.stereoscopy {
+                let mut view_count = 0;
+                pixel_format.stereoscopy = unsafe {
+                    egl.QuerySurface(
+                        self.display,
+                        surface,
+                        ffi::egl::MULTIVIEW_VIEW_COUNT_EXT as ffi::egl::types::EGLint,
+                        &mut view_count,
+                    ) != ffi::egl::FALSE
+                        && view_count >= 2
+                };
+            }
+        }
+
+        Ok(Context {
+            display: self.display,
+            display_ref: self.display_ref,
+            context,
+            config_id: self.config_id,
+            context_config,
+            surface: surface.map(parking_lot::Mutex::new),
+            pbuffer: false,
+            native_window: None,
+            surface_replaced: std::sync::atomic::AtomicBool::new(false),
+            share,
+            egl_version: self.egl_version,
+            extensions: self.extensions,
+            api: self.api,
+            version,
+            robustness,
+            debug,
+            memory_purge_handling: self.opengl.memory_purge_handling,
+            release_behavior: self.release_behavior,
+            priority: self.opengl.priority,
+            raw_context_attributes: self.opengl.raw_context_attributes.clone(),
+            finish_on_drop: self.opengl.finish_on_drop,
+            pixel_format,
+            swap_interval_range: self.swap_interval_range,
+            vsync: parking_lot::Mutex::new(vsync),
+            swap_fence: parking_lot::Mutex::new(None),
+            track_swaps: std::sync::atomic::AtomicBool::new(false),
+            #[cfg(any(
+                target_os = "linux",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "netbsd",
+                target_os = "openbsd",
+            ))]
+            _stream: None,
+        })
+    }
+}
+
+/// Builds the attribute list passed to `eglChooseConfig` for `pf_reqs`.
+fn config_descriptor(
+    egl_version: &(ffi::egl::types::EGLint, ffi::egl::types::EGLint),
+    extensions: &[String],
+    api: Api,
+    version: Option<(u8, u8)>,
+    pf_reqs: &PixelFormatRequirements,
+    surface_type: SurfaceType,
+) -> Result<Vec<raw::c_int>, CreationError> {
+    let mut out: Vec<raw::c_int> = Vec::with_capacity(37);
+
+    if egl_version >= &(1, 2) {
+        out.push(ffi::egl::COLOR_BUFFER_TYPE as raw::c_int);
+        out.push(match pf_reqs.color_buffer_type {
+            ColorBufferType::Rgb => ffi::egl::RGB_BUFFER as raw::c_int,
+            ColorBufferType::Luminance => ffi::egl::LUMINANCE_BUFFER as raw::c_int,
+        });
+    } else if pf_reqs.color_buffer_type == ColorBufferType::Luminance {
+        return Err(CreationError::NoAvailablePixelFormat);
+    }
+
+    // Fixed point is the default component type.
+    if pf_reqs.float_color_buffer {
+        if extensions.iter().any(|s| s == "EGL_EXT_pixel_format_float") {
+            out.push(ffi::egl::COLOR_COMPONENT_TYPE_EXT as raw::c_int);
+            out.push(ffi::egl::COLOR_COMPONENT_TYPE_FLOAT_EXT as raw::c_int);
+        } else {
+            return Err(CreationError::NoAvailablePixelFormat);
+        }
+    }
+
+    out.push(ffi::egl::SURFACE_TYPE as raw::c_int);
+    let surface_type = match surface_type {
+        SurfaceType::Window => ffi::egl::WINDOW_BIT,
+        SurfaceType::PBuffer => ffi::egl::PBUFFER_BIT,
+        SurfaceType::Surfaceless => 0,
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd",
+        ))]
+        SurfaceType::Stream => ffi::egl::STREAM_BIT_KHR,
+    };
+    out.push(surface_type as raw::c_int);
+
+    match (api, version) {
+        (Api::OpenGlEs, Some((3, _))) => {
+            if egl_version < &(1, 3) {
+                return Err(CreationError::NoAvailablePixelFormat);
+            }
+            out.push(ffi::egl::RENDERABLE_TYPE as raw::c_int);
+            out.push(ffi::egl::OPENGL_ES3_BIT as raw::c_int);
+            out.push(ffi::egl::CONFORMANT as raw::c_int);
+            out.push(ffi::egl::OPENGL_ES3_BIT as raw::c_int);
+        }
+        (Api::OpenGlEs, Some((2, _))) => {
+            if egl_version < &(1, 3) {
+                return Err(CreationError::NoAvailablePixelFormat);
+            }
+            out.push(ffi::egl::RENDERABLE_TYPE as raw::c_int);
+            out.push(ffi::egl::OPENGL_ES2_BIT as raw::c_int);
+            out.push(ffi::egl::CONFORMANT as raw::c_int);
+            out.push(ffi::egl::OPENGL_ES2_BIT as raw::c_int);
+        }
+        (Api::OpenGlEs, _) => {
+            if egl_version >= &(1, 3) {
+                out.push(ffi::egl::RENDERABLE_TYPE as raw::c_int);
+                out.push(ffi::egl::OPENGL_ES_BIT as raw::c_int);
+                out.push(ffi::egl::CONFORMANT as raw::c_int);
+                out.push(ffi::egl::OPENGL_ES_BIT as raw::c_int);
+            }
+        }
+        (Api::OpenGl, _) => {
+            if egl_version < &(1, 3) {
+                return Err(CreationError::NoAvailablePixelFormat);
+            }
+            out.push(ffi::egl::RENDERABLE_TYPE as raw::c_int);
+            out.push(ffi::egl::OPENGL_BIT as raw::c_int);
+            out.push(ffi::egl::CONFORMANT as raw::c_int);
+            out.push(ffi::egl::OPENGL_BIT as raw::c_int);
+        }
+        (_, _) => unimplemented!(),
+    };
+
+    // `EGL_CONFIG_CAVEAT` only matches a single value, so the other
+    // tolerances are filtered in `choose_fbconfig`.
+    if let Some(tolerance) = pf_reqs.caveat_tolerance {
+        if tolerance == CaveatTolerance::NoneOnly {
+            out.push(ffi::egl::CONFIG_CAVEAT as raw::c_int);
+            out.push(ffi::egl::NONE as raw::c_int);
+        }
+    } else if let Some(hardware_accelerated) = pf_reqs.hardware_accelerated {
+        out.push(ffi::egl::CONFIG_CAVEAT as raw::c_int);
+        out.push(if hardware_accelerated {
+            ffi::egl::NONE as raw::c_int
+        } else {
+            ffi::egl::SLOW_CONFIG as raw::c_int
+        });
+    }
+
+    if let (Some(color), ColorBufferType::Luminance) =
+        (pf_reqs.color_bits, pf_reqs.color_buffer_type)
+    {
+        out.push(ffi::egl::LUMINANCE_SIZE as raw::c_int);
+        out.push(color as raw::c_int);
+    } else if let Some((red, green, blue)) = pf_reqs.color_component_bits {
+        out.push(ffi::egl::RED_SIZE as raw::c_int);
+        out.push(red as raw::c_int);
+        out.push(ffi::egl::GREEN_SIZE as raw::c_int);
+        out.push(green as raw::c_int);
+        out.push(ffi::egl::BLUE_SIZE as raw::c_int);
+        out.push(blue as raw::c_int);
+    } else if let Some(color) = pf_reqs.color_bits {
+        out.push(ffi::egl::RED_SIZE as raw::c_int);
+        out.push((color / 3) as raw::c_int);
+        out.push(ffi::egl::GREEN_SIZE as raw::c_int);
+        out.push((color / 3 + if color % 3 != 0 { 1 } else { 0 }) as raw::c_int);
+        out.push(ffi::egl::BLUE_SIZE as raw::c_int);
+        out.push((color / 3 + if color % 3 == 2 { 1 } else { 0 }) as raw::c_int);
+    }
+
+    if let Some(alpha) = pf_reqs.alpha_bits {
+        out.push(ffi::egl::ALPHA_SIZE as raw::c_int);
+        out.push(alpha as raw::c_int);
+    }
+
+    if let Some(depth) = pf_reqs.depth_bits {
+        out.push(ffi::egl::DEPTH_SIZE as raw::c_int);
+        out.push(depth as raw::c_int);
+    }
+
+    if let Some(stencil) = pf_reqs.stencil_bits {
+        out.push(ffi::egl::STENCIL_SIZE as raw::c_int);
+        out.push(stencil as raw::c_int);
+    }
+
+    // Only window surfaces can render straight to the front buffer, which is
+    // requested when creating the surface rather than through the config.
+    if pf_reqs.double_buffer == Some(false) && surface_type != ffi::egl::WINDOW_BIT {
+        return Err(CreationError::NoAvailablePixelFormat);
+    }
+
+    if let Some(multisampling) = pf_reqs.multisampling {
+        out.push(ffi::egl::SAMPLES as raw::c_int);
+        out.push(multisampling as raw::c_int);
+    }
+
+    if let Some(native_renderable) = pf_reqs.native_renderable {
+        out.push(ffi::egl::NATIVE_RENDERABLE as raw::c_int);
+        out.push(native_renderable as raw::c_int);
+    }
+
+    // Configs without color-key transparency are the only ones matched when
+    // `EGL_TRANSPARENT_TYPE` is left out.
+    if let Some((red, green, blue)) = pf_reqs.color_key {
+        out.push(ffi::egl::TRANSPARENT_TYPE as raw::c_int);
+        out.push(ffi::egl::TRANSPARENT_RGB as raw::c_int);
+        out.push(ffi::egl::TRANSPARENT_RED_VALUE as raw::c_int);
+        out.push(red as raw::c_int);
+        out.push(ffi::egl::TRANSPARENT_GREEN_VALUE as raw::c_int);
+        out.push(green as raw::c_int);
+        out.push(ffi::egl::TRANSPARENT_BLUE_VALUE as raw::c_int);
+        out.push(blue as raw::c_int);
+    }
+
+    if let Some(xid) = pf_reqs.x11_visual_xid {
+        out.push(ffi::egl::NATIVE_VISUAL_ID as raw::c_int);
+        out.push(xid as raw::c_int);
+    }
+
+    out.push(ffi::egl::NONE as raw::c_int);
+    Ok(out)
+}
+
+/// Whether to ask for an sRGB colorspace when creating the surface.
+///
+/// sRGB is a property of the surface rather than of the config, so without
+/// `EGL_GL_COLORSPACE` there is no way to ask for it. Only an explicit request
+/// fails then, the default one is dropped. OpenGL ES has no
+/// `GL_FRAMEBUFFER_SRGB` switch and always encodes writes to an sRGB surface,
+/// so there the default doesn't ask for it at all.
+fn surface_srgb(
+    egl_version: &(ffi::egl::types::EGLint, ffi::egl::types::EGLint),
+    extensions: &[String],
+    api: Api,
+    pf_reqs: &PixelFormatRequirements,
+    surface_type: SurfaceType,
+) -> Result<bool, CreationError> {
+    if !pf_reqs.srgb || surface_type == SurfaceType::Surfaceless {
+        return Ok(false);
+    }
+    if api != Api::OpenGl && !pf_reqs.srgb_required {
+        return Ok(false);
+    }
+    let has_colorspace =
+        egl_version >= &(1, 5) || extensions.iter().any(|s| s == "EGL_KHR_gl_colorspace");
+    if pf_reqs.srgb_required && !has_colorspace {
+        return Err(CreationError::NotSupported(
+            "sRGB surfaces require EGL 1.5 or EGL_KHR_gl_colorspace".to_string(),
+        ));
+    }
+    Ok(has_colorspace)
+}
+
+/// Whether `context` is current with `surface` as its draw surface, given what
+/// `eglGetCurrentContext` and `eglGetCurrentSurface` returned.
+///
+/// `eglSwapInterval` applies to the current draw surface, so then there is no
+/// need for the two `eglMakeCurrent` calls of a [`MakeCurrentGuard`], which
+/// add up when the vsync mode changes every frame.
+fn is_bound(
+    context: ffi::egl::types::EGLContext,
+    surface: ffi::egl::types::EGLSurface,
+    current_context: ffi::egl::types::EGLContext,
+    current_draw_surface: ffi::egl::types::EGLSurface,
+) -> bool {
+    // A failed `recreate()` leaves no context to be current.
+    context != ffi::egl::NO_CONTEXT && current_context == context && current_draw_surface == surface
+}
+
+/// The `eglSurfaceAttrib` attribute and value making swaps keep or discard
+/// the back buffer.
+fn swap_behavior_attrib(preserved: bool) -> (ffi::egl::types::EGLint, ffi::egl::types::EGLint) {
+    let value = if preserved { ffi::egl::BUFFER_PRESERVED } else { ffi::egl::BUFFER_DESTROYED };
+    (ffi::egl::SWAP_BEHAVIOR as ffi::egl::types::EGLint, value as ffi::egl::types::EGLint)
+}
+
+/// Terminates `attrs` for `eglCreate*Surface`, after asking for an sRGB or
+/// stereo surface if `pixel_format` has one.
+fn surface_attribs(pixel_format: &PixelFormat, mut attrs: Vec<raw::c_int>) -> Vec<raw::c_int> {
+    if pixel_format.srgb {
+        attrs.push(ffi::egl::GL_COLORSPACE_KHR as raw::c_int);
+        attrs.push(ffi::egl::GL_COLORSPACE_SRGB_KHR as raw::c_int);
+    }
+    if pixel_format.stereoscopy {
+        attrs.push(ffi::egl::MULTIVIEW_VIEW_COUNT_EXT as raw::c_int);
+        attrs.push(2);
+    }
+    if !pixel_format.double_buffer {
+        attrs.push(ffi::egl::RENDER_BUFFER as raw::c_int);
+        attrs.push(ffi::egl::SINGLE_BUFFER as raw::c_int);
+    }
+    attrs.push(ffi::egl::NONE as raw::c_int);
+    attrs
+}
+
+/// Counts the configs of `native_display` that match `pf_reqs`, without
+/// inspecting any of them.
+///
+/// Unlike [`Context::new()`], this doesn't filter out configs that can't use
+/// the requested vsync mode.
+pub fn count_matching_configs(
+    pf_reqs: &PixelFormatRequirements,
+    opengl: &GlAttributes<&Context>,
+    native_display: NativeDisplay,
+    surface_type: SurfaceType,
+) -> Result<usize, CreationError> {
+    let egl = EGL.as_ref().unwrap();
+    let (display_ref, descriptor) =
+        match matching_descriptor(pf_reqs, opengl, native_display, surface_type)? {
+            Some(found) => found,
+            None => return Ok(0),
+        };
+    let display = display_ref.0;
+
+    let mut num_configs = 0;
+    if unsafe {
+        egl.ChooseConfig(display, descriptor.as_ptr(), std::ptr::null_mut(), 0, &mut num_configs)
+    } == 0
+    {
+        return Err(egl_error("eglChooseConfig"));
+    }
+
+    Ok(num_configs as usize)
+}
+
+/// Describes every config of `native_display` that matches `pf_reqs`, in the
+/// order `eglChooseConfig` sorted them.
+///
+/// Like [`count_matching_configs()`], this doesn't filter out configs that
+/// can't use the requested vsync mode, so it shows what the driver offers
+/// before any of glutin's own filtering.
+pub fn enumerate_configs(
+    pf_reqs: &PixelFormatRequirements,
+    opengl: &GlAttributes<&Context>,
+    native_display: NativeDisplay,
+    surface_type: SurfaceType,
+) -> Result<Vec<PixelFormat>, CreationError> {
+    let egl = EGL.as_ref().unwrap();
+    let (display_ref, configs) =
+        match matching_configs(pf_reqs, opengl, native_display, surface_type)? {
+            Some(found) => found,
+            None => return Ok(Vec::new()),
+        };
+    let display = display_ref.0;
+
+    configs.into_iter().map(|config| unsafe { config_pixel_format(egl, display, config) }).collect()
+}
+
+/// Returns the lowest `EGL_MIN_SWAP_INTERVAL` and highest
+/// `EGL_MAX_SWAP_INTERVAL` among the configs of `native_display` that match
+/// `pf_reqs`.
+///
+/// The requested vsync mode is ignored here, as the point is to find out
+/// which modes are worth requesting.
+pub fn query_swap_interval_range(
+    pf_reqs: &PixelFormatRequirements,
+    opengl: &GlAttributes<&Context>,
+    native_display: NativeDisplay,
+    surface_type: SurfaceType,
+) -> Result<(i32, i32), CreationError> {
+    let egl = EGL.as_ref().unwrap();
+    let (display_ref, configs) =
+        match matching_configs(pf_reqs, opengl, native_display, surface_type)? {
+            Some(found) => found,
+            None => return Err(CreationError::NoAvailablePixelFormat),
+        };
+    let display = display_ref.0;
+
+    let mut range: Option<(i32, i32)> = None;
+    for config in configs {
+        let attribs = unsafe {
+            query_config_attribs(
+                egl,
+                display,
+                config,
+                &[ffi::egl::MIN_SWAP_INTERVAL, ffi::egl::MAX_SWAP_INTERVAL],
+            )?
+        };
+        range = Some(match range {
+            Some((min, max)) => (min.min(attribs[0]), max.max(attribs[1])),
+            None => (attribs[0], attribs[1]),
+        });
+    }
+    range.ok_or(CreationError::NoAvailablePixelFormat)
+}
+
+/// Returns every config of `native_display` that matches `pf_reqs`, along
+/// with the display they belong to.
+fn matching_configs(
+    pf_reqs: &PixelFormatRequirements,
+    opengl: &GlAttributes<&Context>,
+    native_display: NativeDisplay,
+    surface_type: SurfaceType,
+) -> Result<Option<(DisplayRef, Vec<ffi::egl::types::EGLConfig>)>, CreationError> {
+    let egl = EGL.as_ref().unwrap();
+    let (display_ref, descriptor) =
+        match matching_descriptor(pf_reqs, opengl, native_display, surface_type)? {
+            Some(found) => found,
+            None => return Ok(None),
+        };
+    let display = display_ref.0;
+
+    unsafe {
+        let mut num_configs = 0;
+        if egl.ChooseConfig(display, descriptor.as_ptr(), std::ptr::null_mut(), 0, &mut num_configs)
+            == 0
+        {
+            return Err(egl_error("eglChooseConfig"));
+        }
+        let mut configs = vec![std::ptr::null(); num_configs as usize];
+        if egl.ChooseConfig(
+            display,
+            descriptor.as_ptr(),
+            configs.as_mut_ptr(),
+            num_configs,
+            &mut num_configs,
+        ) == 0
+        {
+            return Err(egl_error("eglChooseConfig"));
+        }
+        configs.truncate(num_configs as usize);
+
+        Ok(Some((display_ref, configs)))
+    }
+}
+
+/// Initializes `native_display` and builds the config descriptor for
+/// `pf_reqs`, or returns [`None`] if the requirements can't be expressed on
+/// this display at all.
+fn matching_descriptor(
+    pf_reqs: &PixelFormatRequirements,
+    opengl: &GlAttributes<&Context>,
+    native_display: NativeDisplay,
+    surface_type: SurfaceType,
+) -> Result<Option<(DisplayRef, Vec<raw::c_int>)>, CreationError> {
+    let (display, egl_version, extensions) =
+        init_display(&native_display, opengl.terminate_display)?;
+    let (version, api) = unsafe { bind_and_get_api(opengl, egl_version)? };
+
+    match config_descriptor(&egl_version, &extensions, api, version, pf_reqs, surface_type) {
+        Ok(descriptor) => Ok(Some((display, descriptor))),
+        Err(CreationError::NoAvailablePixelFormat) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Whether an [`Output`] is a layer or a port.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputKind {
+    /// An `EGLOutputLayerEXT`, e.g. a DRM plane or CRTC that images are scanned out of.
+    Layer,
+    /// An `EGLOutputPortEXT`, e.g. a DRM connector that a monitor is plugged into.
+    Port,
+}
+
+/// An output layer or port of an EGL device, as exposed by `EGL_EXT_output_base`.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+#[derive(Debug, Clone)]
+pub struct Output {
+    kind: OutputKind,
+    handle: *const raw::c_void,
+    drm_id: Option<u32>,
+    name: String,
+    device: *const raw::c_void,
+    /// Keeps the device's display initialized while the handle is around.
+    _display_ref: DisplayRef,
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+impl Output {
+    /// Whether this is a layer or a port.
+    #[inline]
+    pub fn kind(&self) -> OutputKind {
+        self.kind
+    }
+
+    /// The raw `EGLOutputLayerEXT` or `EGLOutputPortEXT` handle.
+    #[inline]
+    pub fn raw_handle(&self) -> *const raw::c_void {
+        self.handle
+    }
+
+    /// The DRM object id backing this output, if `EGL_EXT_output_drm` is
+    /// supported.
+    #[inline]
+    pub fn drm_id(&self) -> Option<u32> {
+        self.drm_id
+    }
+
+    /// A name identifying the output, e.g. `"plane-31"`, `"connector-42"`,
+    /// or `"layer-0"` when the DRM id is unknown.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The `EGLDeviceEXT` this output belongs to.
+    #[inline]
+    pub fn device(&self) -> *const raw::c_void {
+        self.device
+    }
+}
+
+/// Lists the output layers and ports of an `EGLDeviceEXT`.
+///
+/// The device's display is initialized on the way, and stays initialized
+/// while any of the returned [`Output`]s is alive so that their handles
+/// remain valid.
+///
+/// # Safety
+///
+/// `device` must be a valid `EGLDeviceEXT`, e.g. the
+/// [`handle`][EglDevice::handle] of a device returned by
+/// [`query_egl_devices()`].
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+pub unsafe fn enumerate_outputs(device: *const raw::c_void) -> Result<Vec<Output>, CreationError> {
+    let egl = EGL
+        .as_ref()
+        .ok_or_else(|| CreationError::NotSupported("EGL is not available".to_string()))?;
+    let (display_ref, _, extensions) =
+        init_display(&NativeDisplay::Device(device as *const _), false)?;
+    let display = display_ref.0;
+
+    if !extensions.iter().any(|s| s == "EGL_EXT_output_base")
+        || !egl.GetOutputLayersEXT.is_loaded()
+        || !egl.GetOutputPortsEXT.is_loaded()
+    {
+        return Err(CreationError::NotSupported(
+            "EGL_EXT_output_base is not supported".to_string(),
+        ));
+    }
+    let has_drm = extensions.iter().any(|s| s == "EGL_EXT_output_drm");
+
+    let mut num_layers = 0;
+    let mut num_ports = 0;
+    unsafe {
+        if egl.GetOutputLayersEXT(
+            display,
+            std::ptr::null(),
+            std::ptr::null_mut(),
+            0,
+            &mut num_layers,
+        ) == 0
+            || egl.GetOutputPortsEXT(
+                display,
+                std::ptr::null(),
+                std::ptr::null_mut(),
+                0,
+                &mut num_ports,
+            ) == 0
+        {
+            return Err(CreationError::OsError("Could not count the EGL outputs".to_string()));
+        }
+    }
+
+    let mut layers = vec![std::ptr::null(); num_layers as usize];
+    let mut ports = vec![std::ptr::null(); num_ports as usize];
+    unsafe {
+        if egl.GetOutputLayersEXT(
+            display,
+            std::ptr::null(),
+            layers.as_mut_ptr(),
+            num_layers,
+            &mut num_layers,
+        ) == 0
+            || egl.GetOutputPortsEXT(
+                display,
+                std::ptr::null(),
+                ports.as_mut_ptr(),
+                num_ports,
+                &mut num_ports,
+            ) == 0
+        {
+            return Err(CreationError::OsError("Could not list the EGL outputs".to_string()));
+        }
+    }
+    layers.truncate(num_layers as usize);
+    ports.truncate(num_ports as usize);
+
+    // A layer is either a plane or a CRTC, depending on how the driver maps them.
+    let layer_drm_id = |layer| {
+        [(ffi::egl::DRM_PLANE_EXT, "plane"), (ffi::egl::DRM_CRTC_EXT, "crtc")].iter().find_map(
+            |&(attrib, prefix)| {
+                let mut value = 0;
+                let ok = unsafe {
+                    egl.QueryOutputLayerAttribEXT(display, layer, attrib as raw::c_int, &mut value)
+                };
+                if ok != 0 {
+                    Some((value as u32, prefix))
+                } else {
+                    None
+                }
+            },
+        )
+    };
+    let port_drm_id = |port| {
+        let mut value = 0;
+        let ok = unsafe {
+            egl.QueryOutputPortAttribEXT(
+                display,
+                port,
+                ffi::egl::DRM_CONNECTOR_EXT as raw::c_int,
+                &mut value,
+            )
+        };
+        if ok != 0 {
+            Some(value as u32)
+        } else {
+            None
+        }
+    };
+
+    let layers = layers.into_iter().enumerate().map(|(i, handle)| {
+        let drm = if has_drm { layer_drm_id(handle) } else { None };
+        let name = match drm {
+            Some((id, prefix)) => format!("{}-{}", prefix, id),
+            None => format!("layer-{}", i),
+        };
+        Output {
+            kind: OutputKind::Layer,
+            handle,
+            drm_id: drm.map(|(id, _)| id),
+            name,
+            device,
+            _display_ref: display_ref.clone(),
+        }
+    });
+    let ports = ports.into_iter().enumerate().map(|(i, handle)| {
+        let drm_id = if has_drm { port_drm_id(handle) } else { None };
+        let name = match drm_id {
+            Some(id) => format!("connector-{}", id),
+            None => format!("port-{}", i),
+        };
+        Output {
+            kind: OutputKind::Port,
+            handle,
+            drm_id,
+            name,
+            device,
+            _display_ref: display_ref.clone(),
+        }
+    });
+
+    Ok(layers.chain(ports).collect())
+}
+
+/// An `EGLSyncKHR` fence, destroyed on drop.
+#[derive(Debug)]
+pub struct EglSync {
+    display: ffi::egl::types::EGLDisplay,
+    sync: ffi::egl::types::EGLSyncKHR,
+    _display_ref: DisplayRef,
+}
+
+impl EglSync {
+    /// Waits up to `timeout_ns` nanoseconds for the fence to be signaled,
+    /// flushing the commands before it first. Returns whether it was.
+    pub fn wait(&self, timeout_ns: u64) -> Result<bool, ContextError> {
+        let egl = EGL.as_ref().unwrap();
+        unsafe {
+            let ret = egl.ClientWaitSyncKHR(
+                self.display,
+                self.sync,
+                ffi::egl::SYNC_FLUSH_COMMANDS_BIT as ffi::egl::types::EGLint,
+                timeout_ns,
+            );
+            match ret as u32 {
+                ffi::egl::CONDITION_SATISFIED => Ok(true),
+                ffi::egl::TIMEOUT_EXPIRED => Ok(false),
+                _ => match egl.GetError() as u32 {
+                    ffi::egl::CONTEXT_LOST => Err(ContextError::ContextLost),
+                    err => Err(ContextError::OsError(format!(
+                        "eglClientWaitSyncKHR failed: 0x{:x}",
+                        err
+                    ))),
+                },
+            }
+        }
+    }
+}
+
+impl Drop for EglSync {
+    fn drop(&mut self) {
+        let egl = EGL.as_ref().unwrap();
+        unsafe {
+            egl.DestroySyncKHR(self.display, self.sync);
+        }
+    }
+}
+
+/// An `EGLStreamKHR` whose frames are scanned out by an output layer.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+#[derive(Debug)]
+pub struct EglStream {
+    device: *const raw::c_void,
+    display: ffi::egl::types::EGLDisplay,
+    stream: ffi::egl::types::EGLStreamKHR,
+    _display_ref: DisplayRef,
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+impl EglStream {
+    /// The `EGLDeviceEXT` this stream was created on.
+    #[inline]
+    pub fn device(&self) -> *const raw::c_void {
+        self.device
+    }
+
+    /// The raw `EGLStreamKHR` handle.
+    #[inline]
+    pub fn raw_handle(&self) -> *const raw::c_void {
+        self.stream
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+impl Drop for EglStream {
+    fn drop(&mut self) {
+        let egl = EGL.as_ref().unwrap();
+        unsafe {
+            egl.DestroyStreamKHR(self.display, self.stream);
+        }
+    }
+}
+
+/// Creates a stream on the device of `layer`, one of the layers returned by
+/// [`enumerate_outputs()`], whose frames are displayed by that layer.
+///
+/// This needs `EGL_KHR_stream` and `EGL_EXT_stream_consumer_egloutput`.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+pub fn create_output_stream(layer: &Output) -> Result<EglStream, CreationError> {
+    if layer.kind != OutputKind::Layer {
+        return Err(CreationError::PlatformSpecific(
+            "Streams can only be consumed by output layers".to_string(),
+        ));
+    }
+
+    let egl = EGL
+        .as_ref()
+        .ok_or_else(|| CreationError::NotSupported("EGL is not available".to_string()))?;
+    let device = layer.device;
+    let (display_ref, _, extensions) =
+        init_display(&NativeDisplay::Device(device as *const _), false)?;
+    let display = display_ref.0;
+
+    for ext in ["EGL_KHR_stream", "EGL_EXT_stream_consumer_egloutput"] {
+        if !extensions.iter().any(|s| s == ext) {
+            return Err(CreationError::NotSupported(format!("{} is not supported", ext)));
+        }
+    }
+    if !egl.CreateStreamKHR.is_loaded()
+        || !egl.DestroyStreamKHR.is_loaded()
+        || !egl.StreamConsumerOutputEXT.is_loaded()
+    {
+        return Err(CreationError::NotSupported(
+            "The EGL stream functions could not be loaded".to_string(),
+        ));
+    }
+
+    let attrs = [ffi::egl::NONE as raw::c_int];
+    let stream = unsafe { egl.CreateStreamKHR(display, attrs.as_ptr()) };
+    if stream == ffi::egl::NO_STREAM_KHR {
+        return Err(egl_error("eglCreateStreamKHR"));
+    }
+    let stream = EglStream { device, display, stream, _display_ref: display_ref };
 
-            let egl = EGL.as_ref().unwrap();
-            unsafe {
-                if egl.SwapInterval(self.display, self.opengl.vsync.get_swap_interval())
-                    == ffi::egl::FALSE
-                {
-                    panic!("finish_impl: eglSwapInterval failed: 0x{:x}", egl.GetError());
-                }
-            }
-            // }
+    if unsafe { egl.StreamConsumerOutputEXT(display, stream.stream, layer.handle) } == 0 {
+        return Err(egl_error("eglStreamConsumerOutputEXT"));
+    }
+
+    Ok(stream)
+}
+
+/// An `EGLDeviceEXT`, as listed by [`query_egl_devices()`].
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+#[derive(Debug, Clone)]
+pub struct EglDevice {
+    /// The raw `EGLDeviceEXT` handle, e.g. for [`enumerate_outputs()`].
+    pub handle: *const raw::c_void,
+    /// The device extensions, e.g. `EGL_EXT_device_drm`.
+    pub extensions: Vec<String>,
+    /// The DRM device node, e.g. `/dev/dri/card0`, if `EGL_EXT_device_drm`
+    /// is supported.
+    pub drm_device_file: Option<String>,
+}
+
+/// Lists the `EGLDeviceEXT`s of the system, e.g. to pick the GPU a headless
+/// context renders on.
+///
+/// This needs `EGL_EXT_device_enumeration`, or `EGL_EXT_device_base` which
+/// includes it.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+pub fn query_egl_devices() -> Result<Vec<EglDevice>, CreationError> {
+    let egl = EGL
+        .as_ref()
+        .ok_or_else(|| CreationError::NotSupported("EGL is not available".to_string()))?;
+    let extensions = client_extensions();
+    if !extensions.iter().any(|s| s == "EGL_EXT_device_enumeration" || s == "EGL_EXT_device_base")
+        || !egl.QueryDevicesEXT.is_loaded()
+        || !egl.QueryDeviceStringEXT.is_loaded()
+    {
+        return Err(CreationError::NotSupported(
+            "EGL_EXT_device_enumeration is not supported".to_string(),
+        ));
+    }
+
+    let mut num_devices = 0;
+    if unsafe { egl.QueryDevicesEXT(0, std::ptr::null_mut(), &mut num_devices) } == 0 {
+        return Err(egl_error("eglQueryDevicesEXT"));
+    }
+    let mut devices = vec![std::ptr::null(); num_devices as usize];
+    if unsafe { egl.QueryDevicesEXT(num_devices, devices.as_mut_ptr(), &mut num_devices) } == 0 {
+        return Err(egl_error("eglQueryDevicesEXT"));
+    }
+    devices.truncate(num_devices as usize);
+
+    let query_string = |device, name| unsafe {
+        let p = egl.QueryDeviceStringEXT(device, name as ffi::egl::types::EGLint);
+        if p.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(p).to_string_lossy().into_owned())
         }
+    };
 
-        Ok(Context {
-            display: self.display,
-            context,
-            surface: surface.map(parking_lot::Mutex::new),
-            api: self.api,
-            pixel_format: self.pixel_format,
-            swap_interval_range: self.swap_interval_range,
+    Ok(devices
+        .into_iter()
+        .map(|handle| {
+            let extensions: Vec<String> = query_string(handle, ffi::egl::EXTENSIONS)
+                .map(|list| list.split(' ').map(|e| e.to_string()).collect())
+                .unwrap_or_default();
+            let drm_device_file = if extensions.iter().any(|s| s == "EGL_EXT_device_drm") {
+                query_string(handle, ffi::egl::DRM_DEVICE_FILE_EXT)
+            } else {
+                None
+            };
+            EglDevice { handle, extensions, drm_device_file }
         })
-    }
+        .collect())
 }
 
 unsafe fn choose_fbconfig<F>(
     display: ffi::egl::types::EGLDisplay,
     egl_version: &(ffi::egl::types::EGLint, ffi::egl::types::EGLint),
+    extensions: &[String],
     api: Api,
     version: Option<(u8, u8)>,
     pf_reqs: &PixelFormatRequirements,
@@ -976,136 +3280,19 @@ where
     F: FnMut(
         Vec<ffi::egl::types::EGLConfig>,
         ffi::egl::types::EGLDisplay,
-    ) -> Result<ffi::egl::types::EGLConfig, ()>,
+    ) -> Result<ffi::egl::types::EGLConfig, CreationError>,
 {
     let egl = EGL.as_ref().unwrap();
 
-    let descriptor = {
-        let mut out: Vec<raw::c_int> = Vec::with_capacity(37);
-
-        if egl_version >= &(1, 2) {
-            out.push(ffi::egl::COLOR_BUFFER_TYPE as raw::c_int);
-            out.push(ffi::egl::RGB_BUFFER as raw::c_int);
-        }
-
-        out.push(ffi::egl::SURFACE_TYPE as raw::c_int);
-        let surface_type = match surface_type {
-            SurfaceType::Window => ffi::egl::WINDOW_BIT,
-            SurfaceType::PBuffer => ffi::egl::PBUFFER_BIT,
-            SurfaceType::Surfaceless => 0,
-        };
-        out.push(surface_type as raw::c_int);
-
-        match (api, version) {
-            (Api::OpenGlEs, Some((3, _))) => {
-                if egl_version < &(1, 3) {
-                    return Err(CreationError::NoAvailablePixelFormat);
-                }
-                out.push(ffi::egl::RENDERABLE_TYPE as raw::c_int);
-                out.push(ffi::egl::OPENGL_ES3_BIT as raw::c_int);
-                out.push(ffi::egl::CONFORMANT as raw::c_int);
-                out.push(ffi::egl::OPENGL_ES3_BIT as raw::c_int);
-            }
-            (Api::OpenGlEs, Some((2, _))) => {
-                if egl_version < &(1, 3) {
-                    return Err(CreationError::NoAvailablePixelFormat);
-                }
-                out.push(ffi::egl::RENDERABLE_TYPE as raw::c_int);
-                out.push(ffi::egl::OPENGL_ES2_BIT as raw::c_int);
-                out.push(ffi::egl::CONFORMANT as raw::c_int);
-                out.push(ffi::egl::OPENGL_ES2_BIT as raw::c_int);
-            }
-            (Api::OpenGlEs, _) => {
-                if egl_version >= &(1, 3) {
-                    out.push(ffi::egl::RENDERABLE_TYPE as raw::c_int);
-                    out.push(ffi::egl::OPENGL_ES_BIT as raw::c_int);
-                    out.push(ffi::egl::CONFORMANT as raw::c_int);
-                    out.push(ffi::egl::OPENGL_ES_BIT as raw::c_int);
-                }
-            }
-            (Api::OpenGl, _) => {
-                if egl_version < &(1, 3) {
-                    return Err(CreationError::NoAvailablePixelFormat);
-                }
-                out.push(ffi::egl::RENDERABLE_TYPE as raw::c_int);
-                out.push(ffi::egl::OPENGL_BIT as raw::c_int);
-                out.push(ffi::egl::CONFORMANT as raw::c_int);
-                out.push(ffi::egl::OPENGL_BIT as raw::c_int);
-            }
-            (_, _) => unimplemented!(),
-        };
-
-        if let Some(hardware_accelerated) = pf_reqs.hardware_accelerated {
-            out.push(ffi::egl::CONFIG_CAVEAT as raw::c_int);
-            out.push(if hardware_accelerated {
-                ffi::egl::NONE as raw::c_int
-            } else {
-                ffi::egl::SLOW_CONFIG as raw::c_int
-            });
-        }
-
-        if let Some(color) = pf_reqs.color_bits {
-            out.push(ffi::egl::RED_SIZE as raw::c_int);
-            out.push((color / 3) as raw::c_int);
-            out.push(ffi::egl::GREEN_SIZE as raw::c_int);
-            out.push((color / 3 + if color % 3 != 0 { 1 } else { 0 }) as raw::c_int);
-            out.push(ffi::egl::BLUE_SIZE as raw::c_int);
-            out.push((color / 3 + if color % 3 == 2 { 1 } else { 0 }) as raw::c_int);
-        }
-
-        if let Some(alpha) = pf_reqs.alpha_bits {
-            out.push(ffi::egl::ALPHA_SIZE as raw::c_int);
-            out.push(alpha as raw::c_int);
-        }
-
-        if let Some(depth) = pf_reqs.depth_bits {
-            out.push(ffi::egl::DEPTH_SIZE as raw::c_int);
-            out.push(depth as raw::c_int);
-        }
-
-        if let Some(stencil) = pf_reqs.stencil_bits {
-            out.push(ffi::egl::STENCIL_SIZE as raw::c_int);
-            out.push(stencil as raw::c_int);
-        }
-
-        if let Some(true) = pf_reqs.double_buffer {
-            return Err(CreationError::NoAvailablePixelFormat);
-        }
-
-        if let Some(multisampling) = pf_reqs.multisampling {
-            out.push(ffi::egl::SAMPLES as raw::c_int);
-            out.push(multisampling as raw::c_int);
-        }
-
-        if pf_reqs.stereoscopy {
-            return Err(CreationError::NoAvailablePixelFormat);
-        }
-
-        if let Some(xid) = pf_reqs.x11_visual_xid {
-            out.push(ffi::egl::NATIVE_VISUAL_ID as raw::c_int);
-            out.push(xid as raw::c_int);
-        }
-
-        // FIXME: srgb is not taken into account
-
-        match pf_reqs.release_behavior {
-            ReleaseBehavior::Flush => (),
-            ReleaseBehavior::None => {
-                // TODO: with EGL you need to manually set the behavior
-                unimplemented!()
-            }
-        }
-
-        out.push(ffi::egl::NONE as raw::c_int);
-        out
-    };
+    let descriptor =
+        config_descriptor(egl_version, extensions, api, version, pf_reqs, surface_type)?;
 
     // calling `eglChooseConfig`
     let mut num_configs = std::mem::zeroed();
     if egl.ChooseConfig(display, descriptor.as_ptr(), std::ptr::null_mut(), 0, &mut num_configs)
         == 0
     {
-        return Err(CreationError::OsError("eglChooseConfig failed".to_string()));
+        return Err(egl_error("eglChooseConfig"));
     }
 
     if num_configs == 0 {
@@ -1122,88 +3309,256 @@ where
         &mut num_configs,
     ) == 0
     {
-        return Err(CreationError::OsError("eglChooseConfig failed".to_string()));
+        return Err(egl_error("eglChooseConfig"));
     }
 
-    // We're interested in those configs which allow our desired VSync.
-    let desired_swap_interval = opengl.vsync.get_swap_interval();
+    // We're interested in those configs which allow one of our desired VSync
+    // modes, and which have enough samples or the exact alpha size if
+    // required. When going for the lowest latency, any swap interval will do
+    // for now.
+    let desired_swap_intervals =
+        desired_vsync_modes(opengl).iter().map(|mode| mode.get_swap_interval()).collect::<Vec<_>>();
 
     let mut config_ids_with_range = config_ids
         .into_iter()
         .filter_map(|config| {
-            let mut min_swap_interval = 0;
-            let _res = egl.GetConfigAttrib(
+            let attribs = query_config_attribs(
+                egl,
                 display,
                 config,
-                ffi::egl::MIN_SWAP_INTERVAL as ffi::egl::types::EGLint,
-                &mut min_swap_interval,
-            );
+                &[
+                    ffi::egl::MIN_SWAP_INTERVAL,
+                    ffi::egl::MAX_SWAP_INTERVAL,
+                    ffi::egl::SAMPLES,
+                    ffi::egl::ALPHA_SIZE,
+                    ffi::egl::CONFIG_CAVEAT,
+                    ffi::egl::BIND_TO_TEXTURE_RGB,
+                    ffi::egl::BIND_TO_TEXTURE_RGBA,
+                    ffi::egl::DEPTH_SIZE,
+                    ffi::egl::STENCIL_SIZE,
+                ],
+            )
+            .ok()?;
+            let (min_swap_interval, max_swap_interval) = (attribs[0], attribs[1]);
 
-            if desired_swap_interval < min_swap_interval {
+            let range = SwapIntervalRange(min_swap_interval, max_swap_interval);
+            if !opengl.lowest_latency && !desired_swap_intervals.iter().any(|&i| range.contains(i))
+            {
                 return None;
             }
 
-            let mut max_swap_interval = 0;
-            let _res = egl.GetConfigAttrib(
-                display,
-                config,
-                ffi::egl::MAX_SWAP_INTERVAL as ffi::egl::types::EGLint,
-                &mut max_swap_interval,
-            );
+            if !pf_reqs.multisampling_satisfied_by(Some(attribs[2] as u16))
+                || !pf_reqs.alpha_satisfied_by(attribs[3] as u8)
+            {
+                return None;
+            }
 
-            if desired_swap_interval > max_swap_interval {
+            // The config can't ask for either binding alone, so check here.
+            if pf_reqs.bindable_to_texture && attribs[5] == 0 && attribs[6] == 0 {
+                return None;
+            }
+
+            if matches!(
+                (pf_reqs.caveat_tolerance, attribs[4] as raw::c_uint),
+                (Some(CaveatTolerance::AllowSlow), ffi::egl::NON_CONFORMANT_CONFIG)
+                    | (Some(CaveatTolerance::AllowNonConformant), ffi::egl::SLOW_CONFIG)
+            ) {
                 return None;
             }
 
-            Some((config, SwapIntervalRange(min_swap_interval, max_swap_interval)))
+            Some(((config, is_packed_depth_stencil(attribs[7], attribs[8])), range))
         })
         .collect::<HashMap<_, _>>();
-    let config_ids = config_ids_with_range.iter().map(|(i, _)| *i).collect::<Vec<_>>();
+
+    // Packed depth-stencil buffers are only a preference, so drop the other
+    // configs only if some are packed.
+    if pf_reqs.packed_depth_stencil && config_ids_with_range.keys().any(|&(_, packed)| packed) {
+        config_ids_with_range.retain(|&(_, packed), _| packed);
+    }
+    let mut config_ids_with_range = config_ids_with_range
+        .into_iter()
+        .map(|((config, _), range)| (config, range))
+        .collect::<HashMap<_, _>>();
+
+    // Float depth is only a preference, so drop the other configs only if
+    // that leaves something.
+    if pf_reqs.float_depth_attachments
+        && config_ids_with_range.keys().any(|&config| supports_float_depth(egl, display, config))
+    {
+        config_ids_with_range.retain(|&config, _| supports_float_depth(egl, display, config));
+    }
+
+    // Keep the configs that get the closest to not waiting for vblank at all.
+    // Otherwise, keep the ones that allow the earliest of the fallback modes.
+    if opengl.lowest_latency {
+        if let Some(lowest) = config_ids_with_range.values().map(|r| r.lowest_interval()).min() {
+            config_ids_with_range.retain(|_, r| r.lowest_interval() == lowest);
+        }
+    } else if let Some(&interval) = desired_swap_intervals
+        .iter()
+        .find(|&&i| config_ids_with_range.values().any(|r| r.contains(i)))
+    {
+        config_ids_with_range.retain(|_, r| r.contains(interval));
+    }
+    let mut config_ids = config_ids_with_range.keys().copied().collect::<Vec<_>>();
 
     if config_ids.is_empty() {
         return Err(CreationError::NoAvailablePixelFormat);
     }
 
-    let config_id =
-        config_selector(config_ids, display).map_err(|_| CreationError::NoAvailablePixelFormat)?;
+    // Platform selectors settle for the first config that suits them, so
+    // putting the user's pick first makes it win unless it doesn't suit.
+    if let Some(ref selector) = pf_reqs.config_selector {
+        let formats = config_ids
+            .iter()
+            .map(|&config| config_pixel_format(egl, display, config))
+            .collect::<Result<Vec<_>, _>>()?;
+        let chosen = config_ids.remove(selector.select(&formats)?);
+        config_ids.insert(0, chosen);
+    }
+
+    let config_id = config_selector(config_ids, display)?;
 
-    // analyzing each config
-    macro_rules! attrib {
-        ($egl:expr, $display:expr, $config:expr, $attr:expr) => {{
-            let mut value = std::mem::zeroed();
-            let res = $egl.GetConfigAttrib(
-                $display,
-                $config,
-                $attr as ffi::egl::types::EGLint,
-                &mut value,
-            );
+    let desc = config_pixel_format(egl, display, config_id)?;
+
+    let swap_interval_range = config_ids_with_range.remove(&config_id).unwrap();
+    Ok((config_id, desc, swap_interval_range))
+}
+
+/// Queries every attribute of `attribs` on `config`, returning the values in
+/// the same order.
+///
+/// EGL has no way to fetch several attributes at once, so this still costs one
+/// `eglGetConfigAttrib` call per attribute, but keeps that cost in one place.
+unsafe fn query_config_attribs(
+    egl: &ffi::egl::Egl,
+    display: ffi::egl::types::EGLDisplay,
+    config: ffi::egl::types::EGLConfig,
+    attribs: &[ffi::egl::types::EGLenum],
+) -> Result<Vec<ffi::egl::types::EGLint>, CreationError> {
+    attribs
+        .iter()
+        .map(|&attrib| {
+            let mut value = 0;
+            let res =
+                egl.GetConfigAttrib(display, config, attrib as ffi::egl::types::EGLint, &mut value);
             if res == 0 {
-                return Err(CreationError::OsError("eglGetConfigAttrib failed".to_string()));
+                return Err(egl_error("eglGetConfigAttrib"));
             }
-            value
-        }};
+            Ok(value)
+        })
+        .collect()
+}
+
+/// Whether a config with these `EGL_DEPTH_SIZE` and `EGL_STENCIL_SIZE` most
+/// likely has a packed `DEPTH24_STENCIL8` buffer, as EGL doesn't tell.
+fn is_packed_depth_stencil(depth_size: raw::c_int, stencil_size: raw::c_int) -> bool {
+    depth_size == 24 && stencil_size == 8
+}
+
+/// Whether `config` is conformant with an API that has `GL_DEPTH_COMPONENT32F`
+/// renderbuffers in core, i.e. OpenGL ES 3.0 or desktop OpenGL. This says
+/// nothing about the config's own depth buffer.
+///
+/// `EGL_CONFORMANT` doesn't exist before EGL 1.3, so this is `false` there.
+unsafe fn supports_float_depth(
+    egl: &ffi::egl::Egl,
+    display: ffi::egl::types::EGLDisplay,
+    config: ffi::egl::types::EGLConfig,
+) -> bool {
+    match query_config_attribs(egl, display, config, &[ffi::egl::CONFORMANT]) {
+        Ok(attribs) => attribs[0] & (ffi::egl::OPENGL_ES3_BIT | ffi::egl::OPENGL_BIT) as i32 != 0,
+        Err(_) => false,
     }
+}
+
+/// Builds the [`PixelFormat`] describing `config`.
+unsafe fn config_pixel_format(
+    egl: &ffi::egl::Egl,
+    display: ffi::egl::types::EGLDisplay,
+    config: ffi::egl::types::EGLConfig,
+) -> Result<PixelFormat, CreationError> {
+    let attribs = query_config_attribs(
+        egl,
+        display,
+        config,
+        &[
+            ffi::egl::CONFIG_CAVEAT,
+            ffi::egl::RED_SIZE,
+            ffi::egl::GREEN_SIZE,
+            ffi::egl::BLUE_SIZE,
+            ffi::egl::ALPHA_SIZE,
+            ffi::egl::DEPTH_SIZE,
+            ffi::egl::STENCIL_SIZE,
+            ffi::egl::SAMPLES,
+            ffi::egl::NATIVE_RENDERABLE,
+            ffi::egl::BIND_TO_TEXTURE_RGB,
+            ffi::egl::BIND_TO_TEXTURE_RGBA,
+        ],
+    )?;
+
+    // `EGL_COLOR_BUFFER_TYPE` doesn't exist before EGL 1.2, where every
+    // config is RGB.
+    let luminance_size = match query_config_attribs(
+        egl,
+        display,
+        config,
+        &[ffi::egl::COLOR_BUFFER_TYPE, ffi::egl::LUMINANCE_SIZE],
+    ) {
+        Ok(attribs) if attribs[0] == ffi::egl::LUMINANCE_BUFFER as i32 => Some(attribs[1]),
+        _ => None,
+    };
+
+    let transparent_color = match query_config_attribs(
+        egl,
+        display,
+        config,
+        &[
+            ffi::egl::TRANSPARENT_TYPE,
+            ffi::egl::TRANSPARENT_RED_VALUE,
+            ffi::egl::TRANSPARENT_GREEN_VALUE,
+            ffi::egl::TRANSPARENT_BLUE_VALUE,
+        ],
+    ) {
+        Ok(attribs) if attribs[0] == ffi::egl::TRANSPARENT_RGB as i32 => {
+            Some((attribs[1] as u32, attribs[2] as u32, attribs[3] as u32))
+        }
+        _ => None,
+    };
 
-    let desc = PixelFormat {
-        hardware_accelerated: attrib!(egl, display, config_id, ffi::egl::CONFIG_CAVEAT)
-            != ffi::egl::SLOW_CONFIG as i32,
-        color_bits: attrib!(egl, display, config_id, ffi::egl::RED_SIZE) as u8
-            + attrib!(egl, display, config_id, ffi::egl::BLUE_SIZE) as u8
-            + attrib!(egl, display, config_id, ffi::egl::GREEN_SIZE) as u8,
-        alpha_bits: attrib!(egl, display, config_id, ffi::egl::ALPHA_SIZE) as u8,
-        depth_bits: attrib!(egl, display, config_id, ffi::egl::DEPTH_SIZE) as u8,
-        stencil_bits: attrib!(egl, display, config_id, ffi::egl::STENCIL_SIZE) as u8,
+    Ok(PixelFormat {
+        hardware_accelerated: attribs[0] != ffi::egl::SLOW_CONFIG as i32,
+        color_bits: match luminance_size {
+            Some(size) => size as u8,
+            None => attribs[1] as u8 + attribs[2] as u8 + attribs[3] as u8,
+        },
+        alpha_bits: attribs[4] as u8,
+        depth_bits: attribs[5] as u8,
+        float_depth_attachments: supports_float_depth(egl, display, config),
+        stencil_bits: attribs[6] as u8,
+        packed_depth_stencil: is_packed_depth_stencil(attribs[5], attribs[6]),
         stereoscopy: false,
         double_buffer: true,
-        multisampling: match attrib!(egl, display, config_id, ffi::egl::SAMPLES) {
+        multisampling: match attribs[7] {
             0 | 1 => None,
             a => Some(a as u16),
         },
-        srgb: false, // TODO: use EGL_KHR_gl_colorspace to know that
-    };
-
-    let swap_interval_range = config_ids_with_range.remove(&config_id).unwrap();
-    Ok((config_id, desc, swap_interval_range))
+        // Depends on the surface, see `ContextPrototype::finish_impl()`.
+        srgb: false,
+        native_renderable: attribs[8] != 0,
+        bindable_to_texture: attribs[9] != 0 || attribs[10] != 0,
+        color_buffer_type: if luminance_size.is_some() {
+            ColorBufferType::Luminance
+        } else {
+            ColorBufferType::Rgb
+        },
+        transparent_type: if transparent_color.is_some() {
+            TransparentType::Rgb
+        } else {
+            TransparentType::None
+        },
+        transparent_color: transparent_color.unwrap_or((0, 0, 0)),
+    })
 }
 
 unsafe fn create_context(
@@ -1215,14 +3570,99 @@ unsafe fn create_context(
     config_id: ffi::egl::types::EGLConfig,
     gl_debug: bool,
     gl_robustness: Robustness,
+    gl_memory_purge: bool,
+    release_behavior: ReleaseBehavior,
+    priority: Option<Priority>,
+    raw_attributes: &[(i32, i32)],
     share: ffi::EGLContext,
-) -> Result<ffi::egl::types::EGLContext, CreationError> {
+) -> Result<(ffi::egl::types::EGLContext, Robustness, bool), CreationError> {
     let egl = EGL.as_ref().unwrap();
 
+    let (context_attributes, robustness, debug) = context_attribs(
+        egl_version,
+        extensions,
+        api,
+        version,
+        gl_debug,
+        gl_robustness,
+        gl_memory_purge,
+        release_behavior,
+        priority,
+        raw_attributes,
+    )?;
+
+    let context = egl.CreateContext(display, config_id, share, context_attributes.as_ptr());
+
+    if context.is_null() {
+        match egl.GetError() as u32 {
+            ffi::egl::BAD_MATCH | ffi::egl::BAD_ATTRIBUTE => {
+                return Err(CreationError::OpenGlVersionNotSupported);
+            }
+            code => return Err(CreationError::EglError { call: "eglCreateContext", code }),
+        }
+    }
+
+    Ok((context, robustness, debug))
+}
+
+/// Picks the context to pass to `eglCreateContext` out of the `(display,
+/// context)` pairs of the glutin and external contexts to share with.
+fn share_context(
+    display: ffi::egl::types::EGLDisplay,
+    sharing: Option<(ffi::egl::types::EGLDisplay, ffi::EGLContext)>,
+    external_share: Option<(ffi::egl::types::EGLDisplay, ffi::EGLContext)>,
+) -> Result<ffi::EGLContext, CreationError> {
+    match (sharing, external_share) {
+        (Some(_), Some(_)) => Err(CreationError::PlatformSpecific(
+            "Cannot share with both a glutin context and an external one".to_string(),
+        )),
+        // `eglCreateContext` only reports `EGL_BAD_MATCH` for this, which
+        // says nothing about the cause.
+        (Some((share_display, _)), None) if share_display != display => {
+            Err(CreationError::SharingNotPossible(
+                "The context to share with belongs to a different EGL display, e.g. \
+                 another GPU, and contexts can only share within a display"
+                    .to_string(),
+            ))
+        }
+        (Some((_, context)), None) => Ok(context),
+        (None, Some((share_display, _))) if share_display != display => {
+            Err(CreationError::SharingNotPossible(
+                "The external context belongs to a different EGL display".to_string(),
+            ))
+        }
+        (None, Some((_, context))) => Ok(context),
+        (None, None) => Ok(std::ptr::null()),
+    }
+}
+
+/// Builds the `NONE`-terminated attribute list for `eglCreateContext`, along
+/// with the robustness and debug state the context will end up with.
+fn context_attribs(
+    egl_version: &(ffi::egl::types::EGLint, ffi::egl::types::EGLint),
+    extensions: &[String],
+    api: Api,
+    version: (u8, u8),
+    gl_debug: bool,
+    gl_robustness: Robustness,
+    gl_memory_purge: bool,
+    release_behavior: ReleaseBehavior,
+    priority: Option<Priority>,
+    raw_attributes: &[(i32, i32)],
+) -> Result<(Vec<raw::c_int>, Robustness, bool), CreationError> {
     let mut context_attributes = Vec::with_capacity(10);
     let mut flags = 0;
+    let mut robustness = Robustness::NotRobust;
+    let mut debug = false;
+
+    // `EGL_KHR_create_context` brings the major and minor version attributes
+    // to EGL 1.4, so OpenGL ES 3.x contexts can be requested there too. Only
+    // without it does OpenGL ES fall back to `EGL_CONTEXT_CLIENT_VERSION`,
+    // which can't express a minor version.
+    let create_context_supported =
+        egl_version >= &(1, 5) || extensions.iter().any(|s| s == "EGL_KHR_create_context");
 
-    if egl_version >= &(1, 5) || extensions.iter().any(|s| s == "EGL_KHR_create_context") {
+    if create_context_supported {
         context_attributes.push(ffi::egl::CONTEXT_MAJOR_VERSION as i32);
         context_attributes.push(version.0 as i32);
         context_attributes.push(ffi::egl::CONTEXT_MINOR_VERSION as i32);
@@ -1239,6 +3679,7 @@ unsafe fn create_context(
                 if extensions.iter().any(|s| s == "EGL_KHR_create_context_no_error") {
                     context_attributes.push(ffi::egl::CONTEXT_OPENGL_NO_ERROR_KHR as raw::c_int);
                     context_attributes.push(1);
+                    robustness = Robustness::NoError;
                 }
             }
 
@@ -1248,6 +3689,7 @@ unsafe fn create_context(
                         .push(ffi::egl::CONTEXT_OPENGL_RESET_NOTIFICATION_STRATEGY as raw::c_int);
                     context_attributes.push(ffi::egl::NO_RESET_NOTIFICATION as raw::c_int);
                     flags |= ffi::egl::CONTEXT_OPENGL_ROBUST_ACCESS as raw::c_int;
+                    robustness = Robustness::RobustNoResetNotification;
                 } else {
                     return Err(CreationError::RobustnessNotSupported);
                 }
@@ -1259,6 +3701,7 @@ unsafe fn create_context(
                         .push(ffi::egl::CONTEXT_OPENGL_RESET_NOTIFICATION_STRATEGY as raw::c_int);
                     context_attributes.push(ffi::egl::NO_RESET_NOTIFICATION as raw::c_int);
                     flags |= ffi::egl::CONTEXT_OPENGL_ROBUST_ACCESS as raw::c_int;
+                    robustness = Robustness::RobustNoResetNotification;
                 }
             }
 
@@ -1268,6 +3711,7 @@ unsafe fn create_context(
                         .push(ffi::egl::CONTEXT_OPENGL_RESET_NOTIFICATION_STRATEGY as raw::c_int);
                     context_attributes.push(ffi::egl::LOSE_CONTEXT_ON_RESET as raw::c_int);
                     flags |= ffi::egl::CONTEXT_OPENGL_ROBUST_ACCESS as raw::c_int;
+                    robustness = Robustness::RobustLoseContextOnReset;
                 } else {
                     return Err(CreationError::RobustnessNotSupported);
                 }
@@ -1279,13 +3723,26 @@ unsafe fn create_context(
                         .push(ffi::egl::CONTEXT_OPENGL_RESET_NOTIFICATION_STRATEGY as raw::c_int);
                     context_attributes.push(ffi::egl::LOSE_CONTEXT_ON_RESET as raw::c_int);
                     flags |= ffi::egl::CONTEXT_OPENGL_ROBUST_ACCESS as raw::c_int;
+                    robustness = Robustness::RobustLoseContextOnReset;
                 }
             }
         }
 
+        // Purges are reported like resets, so they need a context that gets
+        // lost on reset in the first place.
+        if gl_memory_purge
+            && robustness == Robustness::RobustLoseContextOnReset
+            && extensions.iter().any(|s| s == "EGL_NV_robustness_video_memory_purge")
+        {
+            context_attributes
+                .push(ffi::egl::GENERATE_RESET_ON_VIDEO_MEMORY_PURGE_NV as raw::c_int);
+            context_attributes.push(ffi::egl::TRUE as raw::c_int);
+        }
+
         if gl_debug && egl_version >= &(1, 5) {
             context_attributes.push(ffi::egl::CONTEXT_OPENGL_DEBUG as i32);
             context_attributes.push(ffi::egl::TRUE as i32);
+            debug = true;
 
             // TODO: using this flag sometimes generates an error
             //       there was a change in the specs that added this flag, so it
@@ -1314,20 +3771,312 @@ unsafe fn create_context(
 
         context_attributes.push(ffi::egl::CONTEXT_CLIENT_VERSION as i32);
         context_attributes.push(version.0 as i32);
+    } else if version > (1, 0) {
+        // Without any version attribute, the driver picks whatever version it
+        // likes, which is unlikely to be the one asked for.
+        log::warn!(
+            "Cannot request {:?} {}.{}: EGL {}.{} has no EGL_KHR_create_context",
+            api,
+            version.0,
+            version.1,
+            egl_version.0,
+            egl_version.1,
+        );
+        return Err(CreationError::OpenGlVersionNotSupported);
+    }
+
+    // Flushing is the default, so only `None` needs an attribute.
+    if release_behavior == ReleaseBehavior::None {
+        if !extensions.iter().any(|s| s == "EGL_KHR_context_flush_control") {
+            return Err(CreationError::NotSupported(
+                "EGL_KHR_context_flush_control not supported".to_string(),
+            ));
+        }
+        context_attributes.push(ffi::egl::CONTEXT_RELEASE_BEHAVIOR_KHR as i32);
+        context_attributes.push(ffi::egl::CONTEXT_RELEASE_BEHAVIOR_NONE_KHR as i32);
+    }
+
+    // Only a hint, the driver may pick a lower priority.
+    if let Some(priority) = priority {
+        if extensions.iter().any(|s| s == "EGL_IMG_context_priority") {
+            context_attributes.push(ffi::egl::CONTEXT_PRIORITY_LEVEL_IMG as i32);
+            context_attributes.push(match priority {
+                Priority::Low => ffi::egl::CONTEXT_PRIORITY_LOW_IMG,
+                Priority::Medium => ffi::egl::CONTEXT_PRIORITY_MEDIUM_IMG,
+                Priority::High => ffi::egl::CONTEXT_PRIORITY_HIGH_IMG,
+            } as i32);
+        }
+    }
+
+    for &(attribute, value) in raw_attributes {
+        context_attributes.push(attribute);
+        context_attributes.push(value);
     }
 
     context_attributes.push(ffi::egl::NONE as i32);
 
-    let context = egl.CreateContext(display, config_id, share, context_attributes.as_ptr());
+    Ok((context_attributes, robustness, debug))
+}
 
-    if context.is_null() {
-        match egl.GetError() as u32 {
-            ffi::egl::BAD_MATCH | ffi::egl::BAD_ATTRIBUTE => {
-                return Err(CreationError::OpenGlVersionNotSupported);
-            }
-            e => panic!("create_context: eglCreateContext failed: 0x{:x}", e),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgba8_pixel_format() -> PixelFormat {
+        PixelFormat {
+            hardware_accelerated: true,
+            color_bits: 24,
+            alpha_bits: 8,
+            depth_bits: 24,
+            float_depth_attachments: false,
+            stencil_bits: 8,
+            packed_depth_stencil: false,
+            stereoscopy: false,
+            double_buffer: true,
+            multisampling: None,
+            srgb: false,
+            native_renderable: false,
+            bindable_to_texture: false,
+            color_buffer_type: ColorBufferType::Rgb,
+            transparent_type: TransparentType::None,
+            transparent_color: (0, 0, 0),
+        }
+    }
+
+    fn descriptor(
+        double_buffer: Option<bool>,
+        surface_type: SurfaceType,
+    ) -> Result<Vec<raw::c_int>, CreationError> {
+        let pf_reqs = PixelFormatRequirements { double_buffer, ..Default::default() };
+        config_descriptor(&(1, 5), &[], Api::OpenGlEs, Some((3, 0)), &pf_reqs, surface_type)
+    }
+
+    fn context_attribs_for(
+        egl_version: (i32, i32),
+        extensions: &[&str],
+        version: (u8, u8),
+        release_behavior: ReleaseBehavior,
+    ) -> Result<Vec<raw::c_int>, CreationError> {
+        let extensions: Vec<String> = extensions.iter().map(|s| s.to_string()).collect();
+        context_attribs(
+            &egl_version,
+            &extensions,
+            Api::OpenGlEs,
+            version,
+            false,
+            Robustness::NotRobust,
+            false,
+            release_behavior,
+            None,
+            &[],
+        )
+        .map(|(attrs, _, _)| attrs)
+    }
+
+    /// Whether the `attrs` key-value list sets `attrib` to `value`.
+    fn has_attrib(attrs: &[raw::c_int], attrib: u32, value: u32) -> bool {
+        attrs.chunks(2).any(|pair| pair == [attrib as raw::c_int, value as raw::c_int])
+    }
+
+    #[test]
+    fn double_buffered_descriptor() {
+        assert!(descriptor(Some(true), SurfaceType::Window).is_ok());
+        assert!(descriptor(Some(true), SurfaceType::PBuffer).is_ok());
+    }
+
+    #[test]
+    fn single_buffered_descriptor() {
+        assert!(descriptor(Some(false), SurfaceType::Window).is_ok());
+        assert!(matches!(
+            descriptor(Some(false), SurfaceType::PBuffer),
+            Err(CreationError::NoAvailablePixelFormat)
+        ));
+    }
+
+    #[test]
+    fn single_buffered_surface_attribs() {
+        let pixel_format = PixelFormat { double_buffer: false, ..rgba8_pixel_format() };
+        let attrs = surface_attribs(&pixel_format, Vec::new());
+        assert!(has_attrib(&attrs, ffi::egl::RENDER_BUFFER, ffi::egl::SINGLE_BUFFER));
+        assert_eq!(attrs.last(), Some(&(ffi::egl::NONE as raw::c_int)));
+
+        let attrs = surface_attribs(&rgba8_pixel_format(), Vec::new());
+        assert!(!attrs.contains(&(ffi::egl::RENDER_BUFFER as raw::c_int)));
+    }
+
+    #[test]
+    fn srgb_surface_attribs() {
+        let pixel_format = PixelFormat { srgb: true, ..rgba8_pixel_format() };
+        let attrs = surface_attribs(&pixel_format, Vec::new());
+        assert!(has_attrib(&attrs, ffi::egl::GL_COLORSPACE_KHR, ffi::egl::GL_COLORSPACE_SRGB_KHR));
+
+        let attrs = surface_attribs(&rgba8_pixel_format(), Vec::new());
+        assert!(!attrs.contains(&(ffi::egl::GL_COLORSPACE_KHR as raw::c_int)));
+    }
+
+    #[test]
+    fn default_srgb_on_gles() {
+        let pf_reqs = PixelFormatRequirements::default();
+        let srgb = surface_srgb(&(1, 5), &[], Api::OpenGlEs, &pf_reqs, SurfaceType::Window);
+        assert!(!srgb.unwrap());
+
+        let srgb = surface_srgb(&(1, 5), &[], Api::OpenGl, &pf_reqs, SurfaceType::Window);
+        assert!(srgb.unwrap());
+    }
+
+    #[test]
+    fn explicit_srgb_on_gles() {
+        let pf_reqs = PixelFormatRequirements { srgb_required: true, ..Default::default() };
+        let srgb = surface_srgb(&(1, 5), &[], Api::OpenGlEs, &pf_reqs, SurfaceType::Window);
+        assert!(srgb.unwrap());
+
+        let srgb = surface_srgb(&(1, 4), &[], Api::OpenGlEs, &pf_reqs, SurfaceType::Window);
+        assert!(matches!(srgb, Err(CreationError::NotSupported(_))));
+    }
+
+    #[test]
+    fn stereo_descriptor() {
+        let pf_reqs = PixelFormatRequirements { stereoscopy: true, ..Default::default() };
+        let stereo = config_descriptor(
+            &(1, 5),
+            &[],
+            Api::OpenGlEs,
+            Some((3, 0)),
+            &pf_reqs,
+            SurfaceType::Window,
+        );
+        assert_eq!(stereo.unwrap(), descriptor(None, SurfaceType::Window).unwrap());
+    }
+
+    #[test]
+    fn stereo_surface_attribs() {
+        let pixel_format = PixelFormat { stereoscopy: true, ..rgba8_pixel_format() };
+        let attrs = surface_attribs(&pixel_format, Vec::new());
+        assert!(has_attrib(&attrs, ffi::egl::MULTIVIEW_VIEW_COUNT_EXT, 2));
+
+        let attrs = surface_attribs(&rgba8_pixel_format(), Vec::new());
+        assert!(!attrs.contains(&(ffi::egl::MULTIVIEW_VIEW_COUNT_EXT as raw::c_int)));
+    }
+
+    #[test]
+    fn swap_behavior_toggled_per_frame() {
+        for frame in 0..4 {
+            let preserved = frame % 2 == 0;
+            let (attribute, value) = swap_behavior_attrib(preserved);
+            assert_eq!(attribute, ffi::egl::SWAP_BEHAVIOR as ffi::egl::types::EGLint);
+            let expected =
+                if preserved { ffi::egl::BUFFER_PRESERVED } else { ffi::egl::BUFFER_DESTROYED };
+            assert_eq!(value, expected as ffi::egl::types::EGLint);
+        }
+    }
+
+    #[test]
+    fn vsync_skips_guard_when_bound() {
+        let context = 1 as ffi::egl::types::EGLContext;
+        let surface = 2 as ffi::egl::types::EGLSurface;
+        assert!(is_bound(context, surface, context, surface));
+    }
+
+    #[test]
+    fn vsync_takes_guard_when_not_bound() {
+        let context = 1 as ffi::egl::types::EGLContext;
+        let surface = 2 as ffi::egl::types::EGLSurface;
+        let other_context = 3 as ffi::egl::types::EGLContext;
+        let other_surface = 4 as ffi::egl::types::EGLSurface;
+        assert!(!is_bound(context, surface, ffi::egl::NO_CONTEXT, ffi::egl::NO_SURFACE));
+        assert!(!is_bound(context, surface, other_context, surface));
+        assert!(!is_bound(context, surface, context, other_surface));
+        assert!(!is_bound(ffi::egl::NO_CONTEXT, surface, ffi::egl::NO_CONTEXT, surface));
+    }
+
+    /// Skipped if there is no EGL display to create the context on.
+    #[test]
+    fn surfaceless_or_not_supported() {
+        if EGL.is_none() {
+            return;
+        }
+        // Software renderers are fine, nothing gets drawn.
+        let pf_reqs = PixelFormatRequirements { hardware_accelerated: None, ..Default::default() };
+        let opengl = GlAttributes::default();
+        let prototype = match Context::new(
+            &pf_reqs,
+            &opengl,
+            NativeDisplay::Other(None),
+            SurfaceType::Surfaceless,
+            |configs, _| Ok(configs[0]),
+        ) {
+            Ok(prototype) => prototype,
+            Err(_) => return,
+        };
+        match prototype.finish_surfaceless() {
+            Ok(_) | Err(CreationError::NotSupported(_)) => (),
+            Err(err) => panic!("{}", err),
         }
     }
 
-    Ok(context)
+    #[test]
+    fn share_context_on_same_display() {
+        let display = 1 as ffi::egl::types::EGLDisplay;
+        let context = 2 as ffi::EGLContext;
+        assert_eq!(share_context(display, Some((display, context)), None).unwrap(), context);
+        assert_eq!(share_context(display, None, Some((display, context))).unwrap(), context);
+        assert!(share_context(display, None, None).unwrap().is_null());
+    }
+
+    #[test]
+    fn share_context_on_other_display() {
+        let display = 1 as ffi::egl::types::EGLDisplay;
+        let other = (3 as ffi::egl::types::EGLDisplay, 2 as ffi::EGLContext);
+        assert!(matches!(
+            share_context(display, Some(other), None),
+            Err(CreationError::SharingNotPossible(_))
+        ));
+        assert!(matches!(
+            share_context(display, None, Some(other)),
+            Err(CreationError::SharingNotPossible(_))
+        ));
+    }
+
+    #[test]
+    fn release_behavior_none_with_flush_control() {
+        let extensions = ["EGL_KHR_context_flush_control"];
+        let attrs =
+            context_attribs_for((1, 5), &extensions, (3, 0), ReleaseBehavior::None).unwrap();
+        assert!(has_attrib(
+            &attrs,
+            ffi::egl::CONTEXT_RELEASE_BEHAVIOR_KHR,
+            ffi::egl::CONTEXT_RELEASE_BEHAVIOR_NONE_KHR
+        ));
+
+        let attrs =
+            context_attribs_for((1, 5), &extensions, (3, 0), ReleaseBehavior::Flush).unwrap();
+        assert!(!attrs.contains(&(ffi::egl::CONTEXT_RELEASE_BEHAVIOR_KHR as raw::c_int)));
+    }
+
+    #[test]
+    fn release_behavior_none_without_flush_control() {
+        assert!(matches!(
+            context_attribs_for((1, 5), &[], (3, 0), ReleaseBehavior::None),
+            Err(CreationError::NotSupported(_))
+        ));
+        assert!(context_attribs_for((1, 5), &[], (3, 0), ReleaseBehavior::Flush).is_ok());
+    }
+
+    #[test]
+    fn gles3_on_egl_1_4_with_create_context() {
+        let extensions = ["EGL_KHR_create_context"];
+        let attrs =
+            context_attribs_for((1, 4), &extensions, (3, 0), ReleaseBehavior::Flush).unwrap();
+        assert!(has_attrib(&attrs, ffi::egl::CONTEXT_MAJOR_VERSION, 3));
+        assert!(has_attrib(&attrs, ffi::egl::CONTEXT_MINOR_VERSION, 0));
+    }
+
+    #[test]
+    fn gles3_on_egl_1_4_without_create_context() {
+        let attrs = context_attribs_for((1, 4), &[], (3, 0), ReleaseBehavior::Flush).unwrap();
+        // `EGL_CONTEXT_CLIENT_VERSION` is the same enum as
+        // `EGL_CONTEXT_MAJOR_VERSION`, only the minor version tells them apart.
+        assert!(has_attrib(&attrs, ffi::egl::CONTEXT_CLIENT_VERSION, 3));
+        assert!(!attrs.contains(&(ffi::egl::CONTEXT_MINOR_VERSION as raw::c_int)));
+    }
 }
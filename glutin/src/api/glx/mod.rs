@@ -22,8 +22,9 @@ use crate::api::dlloader::{SymTrait, SymWrapper};
 use crate::platform::unix::x11::XConnection;
 use crate::platform_impl::x11_utils::SurfaceType;
 use crate::{
-    Api, ContextError, CreationError, GlAttributes, GlProfile, GlRequest, PixelFormat,
-    PixelFormatRequirements, ReleaseBehavior, Robustness,
+    Api, Backend, ColorBufferType, ContextError, CreationError, CreationReport, GlAttributes,
+    GlProfile, GlRequest, PixelFormat, PixelFormatRequirements, ReleaseBehavior, Robustness,
+    SwapMethod, TransparentType, VSyncError, VSyncMode,
 };
 
 #[derive(Clone)]
@@ -73,9 +74,20 @@ pub struct Context {
     xconn: Arc<XConnection>,
     drawable: ffi::Window,
     context: ffi::GLXContext,
+    extensions: String,
+    attribs: ContextAttribs,
     pixel_format: PixelFormat,
 }
 
+/// The attributes that were actually passed to GLX when creating a context.
+#[derive(Debug, Clone, Copy)]
+struct ContextAttribs {
+    /// [`None`] if the context was created without `GLX_ARB_create_context`.
+    version: Option<(u8, u8)>,
+    robustness: Robustness,
+    debug: bool,
+}
+
 impl Context {
     // transparent is [`None`] if window is raw.
     #[allow(clippy::new_ret_no_self)]
@@ -109,14 +121,7 @@ impl Context {
             choose_fbconfig(&extensions, &xconn, screen_id, pf_reqs, surface_type, transparent)?
         };
 
-        Ok(ContextPrototype {
-            extensions,
-            xconn,
-            opengl,
-            fb_config,
-            visual_infos: unsafe { std::mem::transmute(visual_infos) },
-            pixel_format,
-        })
+        Ok(ContextPrototype { extensions, xconn, opengl, fb_config, visual_infos, pixel_format })
     }
 
     unsafe fn check_make_current(&self, ret: Option<i32>) -> Result<(), ContextError> {
@@ -158,11 +163,33 @@ impl Context {
     }
 
     pub fn supports_vsync_mode(&self, mode: VSyncMode) -> bool {
-        todo!()
+        match mode.get_swap_interval() {
+            -1 => check_ext(&self.extensions, "GLX_EXT_swap_control_tear"),
+            0 => true,
+            // Must match the extensions `set_swap_interval` falls back through.
+            _ => ["GLX_EXT_swap_control", "GLX_MESA_swap_control", "GLX_SGI_swap_control"]
+                .iter()
+                .any(|ext| check_ext(&self.extensions, ext)),
+        }
     }
 
     pub fn set_vsync_mode(&self, mode: VSyncMode) -> Result<(), VSyncError> {
-        todo!()
+        if !self.supports_vsync_mode(mode) {
+            return Err(VSyncError::UnsupportedVSyncMode(mode));
+        }
+
+        let _guard = MakeCurrentGuard::new(&self.xconn, self.drawable, self.context)
+            .map_err(|e| VSyncError::ContextError(ContextError::OsError(e)))?;
+
+        let extra_functions = load_extra_functions();
+        set_swap_interval(
+            &extra_functions,
+            &self.extensions,
+            &self.xconn,
+            self.drawable,
+            mode.get_swap_interval(),
+        )
+        .map_err(|e| VSyncError::ContextError(ContextError::OsError(e)))
     }
 
     #[inline]
@@ -192,7 +219,16 @@ impl Context {
     }
 
     #[inline]
-    pub fn buffer_age(&self) -> u32 {
+    pub fn buffer_age_supported(&self) -> bool {
+        check_ext(&self.extensions, "GLX_EXT_buffer_age")
+    }
+
+    #[inline]
+    pub fn buffer_age(&self) -> Option<u32> {
+        if !self.buffer_age_supported() {
+            return None;
+        }
+
         let glx = GLX.as_ref().unwrap();
 
         let mut buffer_age = 0;
@@ -206,13 +242,68 @@ impl Context {
             );
         }
 
-        buffer_age
+        Some(buffer_age)
     }
 
     #[inline]
     pub fn get_pixel_format(&self) -> PixelFormat {
         self.pixel_format.clone()
     }
+
+    #[inline]
+    pub fn creation_report(&self) -> CreationReport {
+        let glx = GLX.as_ref().unwrap();
+        let (mut major, mut minor) = (0, 0);
+        let backend_version =
+            match unsafe { glx.QueryVersion(self.xconn.display as *mut _, &mut major, &mut minor) }
+            {
+                0 => None,
+                _ => Some((major as u8, minor as u8)),
+            };
+
+        CreationReport {
+            backend: Backend::Glx,
+            backend_version,
+            api: Api::OpenGl,
+            version: self.attribs.version,
+            robustness: Some(self.attribs.robustness),
+            debug: Some(self.attribs.debug),
+            extensions: self
+                .extensions
+                .split(' ')
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect(),
+            pixel_format: self.pixel_format.clone(),
+        }
+    }
+
+    #[inline]
+    pub fn surface_size(&self) -> Option<dpi::PhysicalSize<u32>> {
+        let glx = GLX.as_ref().unwrap();
+
+        let (mut width, mut height) = (0, 0);
+        unsafe {
+            glx.QueryDrawable(
+                self.xconn.display as *mut _,
+                self.drawable,
+                ffi::glx::WIDTH as i32,
+                &mut width,
+            );
+            glx.QueryDrawable(
+                self.xconn.display as *mut _,
+                self.drawable,
+                ffi::glx::HEIGHT as i32,
+                &mut height,
+            );
+        }
+
+        if self.xconn.check_errors().is_err() {
+            None
+        } else {
+            Some(dpi::PhysicalSize::new(width, height))
+        }
+    }
 }
 
 unsafe impl Send for Context {}
@@ -229,7 +320,8 @@ impl Drop for Context {
 
             let gl_finish_fn = self.get_proc_address("glFinish");
             assert!(!gl_finish_fn.is_null());
-            let gl_finish_fn = std::mem::transmute::<_, extern "system" fn()>(gl_finish_fn);
+            let gl_finish_fn =
+                std::mem::transmute::<*const core::ffi::c_void, extern "system" fn()>(gl_finish_fn);
             gl_finish_fn();
 
             if guard.old_context() == Some(self.context) {
@@ -259,20 +351,20 @@ impl<'a> ContextPrototype<'a> {
     }
 
     // creating GL context
-    fn create_context(&self) -> Result<(ffi::glx_extra::Glx, ffi::GLXContext), CreationError> {
-        let glx = GLX.as_ref().unwrap();
+    fn create_context(
+        &self,
+    ) -> Result<(ffi::glx_extra::Glx, ffi::GLXContext, ContextAttribs), CreationError> {
+        self.opengl.check_no_external_share()?;
+        self.opengl.check_no_config_context()?;
         let share = match self.opengl.sharing {
             Some(ctx) => ctx.context,
             None => std::ptr::null(),
         };
 
         // loading the extra GLX functions
-        let extra_functions = ffi::glx_extra::Glx::load_with(|proc_name| {
-            let c_str = CString::new(proc_name).unwrap();
-            unsafe { glx.GetProcAddress(c_str.as_ptr() as *const u8) as *const _ }
-        });
+        let extra_functions = load_extra_functions();
 
-        let context = match self.opengl.version {
+        let (context, attribs) = match self.opengl.version {
             GlRequest::Latest => {
                 let opengl_versions = [
                     (4, 6),
@@ -340,7 +432,8 @@ impl<'a> ContextPrototype<'a> {
                 &self.visual_infos,
             )?,
             GlRequest::Specific(_, _) => panic!("Only OpenGL is supported"),
-            GlRequest::GlThenGles { opengl_version: (major, minor), .. } => create_context(
+            GlRequest::GlThenGles { opengl_version: (major, minor), .. }
+            | GlRequest::GlesThenGl { opengl_version: (major, minor), .. } => create_context(
                 &extra_functions,
                 &self.extensions,
                 &self.xconn.xlib,
@@ -355,13 +448,13 @@ impl<'a> ContextPrototype<'a> {
             )?,
         };
 
-        Ok((extra_functions, context))
+        Ok((extra_functions, context, attribs))
     }
 
     pub fn finish_pbuffer(self, size: dpi::PhysicalSize<u32>) -> Result<Context, CreationError> {
         let glx = GLX.as_ref().unwrap();
         let size: (u32, u32) = size.into();
-        let (_extra_functions, context) = self.create_context()?;
+        let (_extra_functions, context, attribs) = self.create_context()?;
 
         let attributes: Vec<raw::c_int> = vec![
             ffi::glx::PBUFFER_WIDTH as raw::c_int,
@@ -379,13 +472,14 @@ impl<'a> ContextPrototype<'a> {
             xconn: self.xconn,
             drawable: pbuffer,
             context,
+            extensions: self.extensions,
+            attribs,
             pixel_format: self.pixel_format,
         })
     }
 
     pub fn finish(self, window: ffi::Window) -> Result<Context, CreationError> {
-        let glx = GLX.as_ref().unwrap();
-        let (extra_functions, context) = self.create_context()?;
+        let (extra_functions, context, attribs) = self.create_context()?;
 
         // vsync
         let swap_mode = self.opengl.vsync.get_swap_interval();
@@ -393,57 +487,83 @@ impl<'a> ContextPrototype<'a> {
         let _guard =
             MakeCurrentGuard::new(&self.xconn, window, context).map_err(CreationError::OsError)?;
 
-        if check_ext(&self.extensions, "GLX_EXT_swap_control")
-            && extra_functions.SwapIntervalEXT.is_loaded()
-        {
-            // this should be the most common extension
-            unsafe {
-                extra_functions.SwapIntervalEXT(self.xconn.display as *mut _, window, swap_mode);
-            }
-
-            let mut swap = unsafe { std::mem::zeroed() };
-            unsafe {
-                glx.QueryDrawable(
-                    self.xconn.display as *mut _,
-                    window,
-                    ffi::glx_extra::SWAP_INTERVAL_EXT as i32,
-                    &mut swap,
-                );
-            }
-
-            if swap != swap_mode as u32 {
-                return Err(CreationError::OsError(format!(
-                    "Couldn't setup vsync: expected interval `{}` but got `{}`",
-                    swap_mode, swap
-                )));
-            }
-        } else if check_ext(&self.extensions, "GLX_MESA_swap_control")
-            && extra_functions.SwapIntervalMESA.is_loaded()
-        {
-            unsafe {
-                extra_functions.SwapIntervalMESA(swap_mode as u32);
-            }
-        } else if check_ext(&self.extensions, "GLX_SGI_swap_control")
-            && extra_functions.SwapIntervalSGI.is_loaded()
-        {
-            unsafe {
-                extra_functions.SwapIntervalSGI(swap_mode);
-            }
-        } else if self.opengl.vsync {
-            return Err(CreationError::OsError(
-                "Couldn't find any available vsync extension".to_string(),
-            ));
-        }
+        set_swap_interval(&extra_functions, &self.extensions, &self.xconn, window, swap_mode)
+            .map_err(CreationError::OsError)?;
 
         Ok(Context {
             xconn: self.xconn,
             drawable: window,
             context,
+            extensions: self.extensions,
+            attribs,
             pixel_format: self.pixel_format,
         })
     }
 }
 
+/// Loads the GLX functions that are not guaranteed to be supported.
+fn load_extra_functions() -> ffi::glx_extra::Glx {
+    let glx = GLX.as_ref().unwrap();
+    ffi::glx_extra::Glx::load_with(|proc_name| {
+        let c_str = CString::new(proc_name).unwrap();
+        unsafe { glx.GetProcAddress(c_str.as_ptr() as *const u8) as *const _ }
+    })
+}
+
+/// Applies `swap_mode` to `drawable`, which must be current.
+fn set_swap_interval(
+    extra_functions: &ffi::glx_extra::Glx,
+    extensions: &str,
+    xconn: &Arc<XConnection>,
+    drawable: ffi::Window,
+    swap_mode: raw::c_int,
+) -> Result<(), String> {
+    let glx = GLX.as_ref().unwrap();
+
+    if check_ext(extensions, "GLX_EXT_swap_control") && extra_functions.SwapIntervalEXT.is_loaded()
+    {
+        // this should be the most common extension
+        unsafe {
+            extra_functions.SwapIntervalEXT(xconn.display as *mut _, drawable, swap_mode);
+        }
+
+        let mut swap = unsafe { std::mem::zeroed() };
+        unsafe {
+            glx.QueryDrawable(
+                xconn.display as *mut _,
+                drawable,
+                ffi::glx_extra::SWAP_INTERVAL_EXT as i32,
+                &mut swap,
+            );
+        }
+
+        // With `GLX_EXT_swap_control_tear` a negative interval is reported as
+        // its absolute value.
+        if swap != swap_mode.unsigned_abs() {
+            return Err(format!(
+                "Couldn't setup vsync: expected interval `{}` but got `{}`",
+                swap_mode, swap
+            ));
+        }
+    } else if check_ext(extensions, "GLX_MESA_swap_control")
+        && extra_functions.SwapIntervalMESA.is_loaded()
+    {
+        unsafe {
+            extra_functions.SwapIntervalMESA(swap_mode as u32);
+        }
+    } else if check_ext(extensions, "GLX_SGI_swap_control")
+        && extra_functions.SwapIntervalSGI.is_loaded()
+    {
+        unsafe {
+            extra_functions.SwapIntervalSGI(swap_mode);
+        }
+    } else if swap_mode != 0 {
+        return Err("Couldn't find any available vsync extension".to_string());
+    }
+
+    Ok(())
+}
+
 extern "C" fn x_error_callback(_dpy: *mut ffi::Display, _err: *mut ffi::XErrorEvent) -> i32 {
     0
 }
@@ -460,11 +580,14 @@ fn create_context(
     display: *mut ffi::Display,
     fb_config: ffi::glx::types::GLXFBConfig,
     visual_infos: &ffi::XVisualInfo,
-) -> Result<ffi::GLXContext, CreationError> {
+) -> Result<(ffi::GLXContext, ContextAttribs), CreationError> {
     let glx = GLX.as_ref().unwrap();
+    let mut attribs = ContextAttribs { version: None, robustness: Robustness::NotRobust, debug };
     unsafe {
         let old_callback = (xlib.XSetErrorHandler)(Some(x_error_callback));
         let context = if check_ext(extensions, "GLX_ARB_create_context") {
+            attribs.version = Some(version);
+
             let mut attributes = Vec::with_capacity(9);
 
             attributes.push(ffi::glx_extra::CONTEXT_MAJOR_VERSION_ARB as raw::c_int);
@@ -499,6 +622,7 @@ fn create_context(
                             attributes
                                 .push(ffi::glx_extra::NO_RESET_NOTIFICATION_ARB as raw::c_int);
                             flags |= ffi::glx_extra::CONTEXT_ROBUST_ACCESS_BIT_ARB as raw::c_int;
+                            attribs.robustness = Robustness::RobustNoResetNotification;
                         }
                         Robustness::RobustLoseContextOnReset
                         | Robustness::TryRobustLoseContextOnReset => {
@@ -509,6 +633,7 @@ fn create_context(
                             attributes
                                 .push(ffi::glx_extra::LOSE_CONTEXT_ON_RESET_ARB as raw::c_int);
                             flags |= ffi::glx_extra::CONTEXT_ROBUST_ACCESS_BIT_ARB as raw::c_int;
+                            attribs.robustness = Robustness::RobustLoseContextOnReset;
                         }
                         Robustness::NotRobust => (),
                         Robustness::NoError => (),
@@ -543,6 +668,7 @@ fn create_context(
                 attributes.as_ptr(),
             )
         } else {
+            attribs.debug = false;
             let visual_infos: *const ffi::XVisualInfo = visual_infos;
             glx.CreateContext(display as *mut _, visual_infos as *mut _, share, 1)
         };
@@ -554,7 +680,7 @@ fn create_context(
             return Err(CreationError::OsError("GL context creation failed".to_string()));
         }
 
-        Ok(context)
+        Ok((context, attribs))
     }
 }
 
@@ -594,6 +720,9 @@ unsafe fn choose_fbconfig(
             SurfaceType::Window => ffi::glx::WINDOW_BIT,
             SurfaceType::PBuffer => ffi::glx::PBUFFER_BIT,
             SurfaceType::Surfaceless => ffi::glx::DONT_CARE, /* TODO: Properly support */
+            SurfaceType::Stream => {
+                return Err(CreationError::NotSupported("GLX has no EGL streams".to_string()))
+            }
         };
         out.push(surface_type as raw::c_int);
 
@@ -610,7 +739,21 @@ unsafe fn choose_fbconfig(
             out.push(ffi::glx::RGBA_BIT as raw::c_int);
         }
 
-        if let Some(color) = pf_reqs.color_bits {
+        if pf_reqs.color_buffer_type == ColorBufferType::Luminance
+            || pf_reqs.color_key.is_some()
+            || pf_reqs.bindable_to_texture
+        {
+            return Err(CreationError::NoAvailablePixelFormat);
+        }
+
+        if let Some((red, green, blue)) = pf_reqs.color_component_bits {
+            out.push(ffi::glx::RED_SIZE as raw::c_int);
+            out.push(red as raw::c_int);
+            out.push(ffi::glx::GREEN_SIZE as raw::c_int);
+            out.push(green as raw::c_int);
+            out.push(ffi::glx::BLUE_SIZE as raw::c_int);
+            out.push(blue as raw::c_int);
+        } else if let Some(color) = pf_reqs.color_bits {
             out.push(ffi::glx::RED_SIZE as raw::c_int);
             out.push((color / 3) as raw::c_int);
             out.push(ffi::glx::GREEN_SIZE as raw::c_int);
@@ -649,6 +792,19 @@ unsafe fn choose_fbconfig(
             }
         }
 
+        if let Some(swap_method) = pf_reqs.swap_method {
+            if check_ext(extensions, "GLX_OML_swap_method") {
+                out.push(ffi::glx_extra::SWAP_METHOD_OML as raw::c_int);
+                out.push(match swap_method {
+                    SwapMethod::Exchange => ffi::glx_extra::SWAP_EXCHANGE_OML,
+                    SwapMethod::Copy => ffi::glx_extra::SWAP_COPY_OML,
+                    SwapMethod::Undefined => ffi::glx_extra::SWAP_UNDEFINED_OML,
+                } as raw::c_int);
+            } else {
+                return Err(CreationError::NoAvailablePixelFormat);
+            }
+        }
+
         out.push(ffi::glx::STEREO as raw::c_int);
         out.push(if pf_reqs.stereoscopy { 1 } else { 0 });
 
@@ -719,14 +875,13 @@ unsafe fn choose_fbconfig(
         ) {
             Ok((config_id, visual_infos)) => {
                 let config = *configs.offset(config_id as isize);
-                let config = config;
 
                 (xconn.xlib.XFree)(configs as *mut _);
                 (config, visual_infos)
             }
-            Err(()) => {
+            Err(err) => {
                 (xconn.xlib.XFree)(configs as *mut _);
-                return Err(CreationError::NoAvailablePixelFormat);
+                return Err(err);
             }
         }
     };
@@ -746,7 +901,9 @@ unsafe fn choose_fbconfig(
             + get_attrib(ffi::glx::BLUE_SIZE as raw::c_int) as u8,
         alpha_bits: get_attrib(ffi::glx::ALPHA_SIZE as raw::c_int) as u8,
         depth_bits: get_attrib(ffi::glx::DEPTH_SIZE as raw::c_int) as u8,
+        float_depth_attachments: false,
         stencil_bits: get_attrib(ffi::glx::STENCIL_SIZE as raw::c_int) as u8,
+        packed_depth_stencil: false,
         stereoscopy: get_attrib(ffi::glx::STEREO as raw::c_int) != 0,
         double_buffer: get_attrib(ffi::glx::DOUBLEBUFFER as raw::c_int) != 0,
         multisampling: if get_attrib(ffi::glx::SAMPLE_BUFFERS as raw::c_int) != 0 {
@@ -756,8 +913,19 @@ unsafe fn choose_fbconfig(
         },
         srgb: get_attrib(ffi::glx_extra::FRAMEBUFFER_SRGB_CAPABLE_ARB as raw::c_int) != 0
             || get_attrib(ffi::glx_extra::FRAMEBUFFER_SRGB_CAPABLE_EXT as raw::c_int) != 0,
+        native_renderable: false,
+        bindable_to_texture: false,
+        color_buffer_type: ColorBufferType::Rgb,
+        transparent_type: TransparentType::None,
+        transparent_color: (0, 0, 0),
     };
 
+    if !pf_reqs.multisampling_satisfied_by(pf_desc.multisampling)
+        || !pf_reqs.alpha_satisfied_by(pf_desc.alpha_bits)
+    {
+        return Err(CreationError::NoAvailablePixelFormat);
+    }
+
     Ok((fb_config, pf_desc, visual_infos))
 }
 
@@ -62,8 +62,9 @@
 
 use crate::platform::ios::{WindowBuilderExtIOS, WindowExtIOS};
 use crate::{
-    Api, ContextError, CreationError, GlAttributes, GlRequest, PixelFormat,
-    PixelFormatRequirements, Rect,
+    Api, Backend, ColorBufferType, ContextError, CreationError, CreationReport, FrameTimestamps,
+    GlAttributes, GlRequest, MultisampleResolve, PixelFormat, PixelFormatRequirements, Priority,
+    Rect, ShareToken, SwapBehavior, TransparentType, VSyncError, VSyncMode,
 };
 
 use glutin_gles2_sys as ffi;
@@ -75,6 +76,7 @@ use winit::window::WindowBuilder;
 
 use std::ffi::CString;
 use std::os::raw;
+use std::time::Duration;
 
 #[derive(Debug, PartialEq)]
 enum ColorFormat {
@@ -147,6 +149,17 @@ fn multisampling_for_view(view: ffi::id) -> Option<u16> {
     }
 }
 
+/// Fences need EGL, so none can ever be created with EAGL.
+#[derive(Debug)]
+pub enum EglSync {}
+
+impl EglSync {
+    #[inline]
+    pub fn wait(&self, _timeout_ns: u64) -> Result<bool, ContextError> {
+        match *self {}
+    }
+}
+
 #[derive(Debug)]
 pub struct Context {
     eagl_context: ffi::id,
@@ -170,16 +183,64 @@ impl Context {
     pub fn new_windowed<T>(
         builder: WindowBuilder,
         el: &EventLoopWindowTarget<T>,
-        _: &PixelFormatRequirements,
+        pf_reqs: &PixelFormatRequirements,
         gl_attrs: &GlAttributes<&Context>,
     ) -> Result<(winit::window::Window, Self), CreationError> {
+        let win = Self::new_window(builder, el, pf_reqs, gl_attrs)?;
+        let context = Self::new_for_window(&win, pf_reqs, gl_attrs)?;
+        Ok((win, context))
+    }
+
+    #[inline]
+    pub fn new_window<T>(
+        builder: WindowBuilder,
+        el: &EventLoopWindowTarget<T>,
+        _pf_reqs: &PixelFormatRequirements,
+        gl_attrs: &GlAttributes<&Context>,
+    ) -> Result<winit::window::Window, CreationError> {
+        // Fail before building the window if the context can't be created.
+        Self::requested_version(gl_attrs)?;
+
         create_view_class();
         let view_class = Class::get("MainGLView").expect("Failed to get class `MainGLView`");
         let builder = builder.with_root_view_class(view_class as *const _ as *const _);
+        Ok(builder.build(el)?)
+    }
+
+    #[inline]
+    pub fn new_for_window(
+        win: &winit::window::Window,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attrs: &GlAttributes<&Context>,
+    ) -> Result<Self, CreationError> {
+        let version = Self::requested_version(gl_attrs)?;
+        let context = unsafe {
+            let eagl_context = Context::create_context(version)?;
+            let view = win.ui_view() as ffi::id;
+            let mut context = Context { eagl_context, view };
+            context.init_context(win);
+            context
+        };
+        let pixel_format = context.get_pixel_format();
+        if !pf_reqs.multisampling_satisfied_by(pixel_format.multisampling)
+            || !pf_reqs.alpha_satisfied_by(pixel_format.alpha_bits)
+            || pf_reqs.color_key.is_some()
+            || pf_reqs.bindable_to_texture
+        {
+            return Err(CreationError::NoAvailablePixelFormat);
+        }
+        Ok(context)
+    }
+
+    fn requested_version(
+        gl_attrs: &GlAttributes<&Context>,
+    ) -> Result<ffi::NSUInteger, CreationError> {
         if gl_attrs.sharing.is_some() {
             unimplemented!("Shared contexts are unimplemented on iOS.");
         }
-        let version = match gl_attrs.version {
+        gl_attrs.check_no_external_share()?;
+        gl_attrs.check_no_config_context()?;
+        Ok(match gl_attrs.version {
             GlRequest::Latest => ffi::kEAGLRenderingAPIOpenGLES3,
             GlRequest::Specific(api, (major, _minor)) => {
                 if api == Api::OpenGlEs {
@@ -191,19 +252,40 @@ impl Context {
                 )));
                 }
             }
-            GlRequest::GlThenGles { opengles_version: (major, _minor), .. } => {
+            GlRequest::GlThenGles { opengles_version: (major, _minor), .. }
+            | GlRequest::GlesThenGl { opengles_version: (major, _minor), .. } => {
                 validate_version(major)?
             }
-        };
-        let win = builder.build(el)?;
-        let context = unsafe {
-            let eagl_context = Context::create_context(version)?;
-            let view = win.ui_view() as ffi::id;
-            let mut context = Context { eagl_context, view };
-            context.init_context(&win);
-            context
-        };
-        Ok((win, context))
+        })
+    }
+
+    #[inline]
+    pub fn count_matching_formats<T>(
+        _el: &EventLoopWindowTarget<T>,
+        _pf_reqs: &PixelFormatRequirements,
+        _gl_attr: &GlAttributes<&Context>,
+    ) -> Result<usize, CreationError> {
+        Err(CreationError::NotSupported("counting pixel formats requires EGL".to_string()))
+    }
+
+    #[inline]
+    pub fn enumerate_pixel_formats<T>(
+        _el: &EventLoopWindowTarget<T>,
+        _pf_reqs: &PixelFormatRequirements,
+        _gl_attr: &GlAttributes<&Context>,
+    ) -> Result<Vec<PixelFormat>, CreationError> {
+        Err(CreationError::NotSupported("enumerating pixel formats requires EGL".to_string()))
+    }
+
+    #[inline]
+    pub fn query_swap_interval_range<T>(
+        _el: &EventLoopWindowTarget<T>,
+        _pf_reqs: &PixelFormatRequirements,
+        _gl_attr: &GlAttributes<&Context>,
+    ) -> Result<(i32, i32), CreationError> {
+        Err(CreationError::NotSupported(
+            "querying the swap interval range requires EGL".to_string(),
+        ))
     }
 
     #[inline]
@@ -306,8 +388,23 @@ impl Context {
     }
 
     #[inline]
-    pub fn buffer_age(&self) -> u32 {
-        0
+    pub fn buffer_age_supported(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn supports_surfaceless(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn buffer_age(&self) -> Option<u32> {
+        None
+    }
+
+    #[inline]
+    pub fn would_block_on_swap(&self) -> bool {
+        false
     }
 
     #[inline]
@@ -328,19 +425,60 @@ impl Context {
             color_bits: color_format.color_bits(),
             alpha_bits: color_format.alpha_bits(),
             depth_bits: depth_for_view(self.view),
+            float_depth_attachments: false,
             stencil_bits: stencil_for_view(self.view),
+            packed_depth_stencil: false,
             stereoscopy: false,
             double_buffer: true,
             multisampling: multisampling_for_view(self.view),
             srgb: color_format.srgb(),
+            native_renderable: false,
+            bindable_to_texture: false,
+            color_buffer_type: ColorBufferType::Rgb,
+            transparent_type: TransparentType::None,
+            transparent_color: (0, 0, 0),
+        }
+    }
+
+    #[inline]
+    pub fn creation_report(&self) -> CreationReport {
+        CreationReport {
+            backend: Backend::Eagl,
+            backend_version: None,
+            api: Api::OpenGlEs,
+            version: None,
+            robustness: None,
+            debug: None,
+            extensions: Vec::new(),
+            pixel_format: self.get_pixel_format(),
         }
     }
 
+    #[inline]
+    pub fn diagnostic_details(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    #[inline]
+    pub fn surface_size(&self) -> Option<dpi::PhysicalSize<u32>> {
+        None
+    }
+
+    #[inline]
+    pub fn resize_pbuffer(&self, _size: dpi::PhysicalSize<u32>) -> Result<(), ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
+
     #[inline]
     pub fn resize(&self, _width: u32, _height: u32) {
         // N/A
     }
 
+    #[inline]
+    pub fn update_surface_size(&self, _width: u32, _height: u32) -> Result<bool, ContextError> {
+        Ok(false)
+    }
+
     #[inline]
     pub unsafe fn make_current(&self) -> Result<(), ContextError> {
         let context_class = Class::get("EAGLContext").expect("Failed to get class `EAGLContext`");
@@ -355,6 +493,11 @@ impl Context {
         }
     }
 
+    #[inline]
+    pub unsafe fn make_current_asymmetric(&self, _read: &Context) -> Result<(), ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
+
     #[inline]
     pub unsafe fn make_not_current(&self) -> Result<(), ContextError> {
         if !self.is_current() {
@@ -380,6 +523,11 @@ impl Context {
         true
     }
 
+    #[inline]
+    pub fn bind_api(&self) -> Result<(), ContextError> {
+        Ok(())
+    }
+
     #[inline]
     pub fn get_proc_address(&self, proc_name: &str) -> *const core::ffi::c_void {
         let proc_name_c = CString::new(proc_name).expect("proc name contained interior nul byte");
@@ -392,15 +540,130 @@ impl Context {
         }
     }
 
+    #[inline]
+    pub fn finish_with_timeout(&self, _timeout: Duration) -> Result<bool, ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
+
+    #[inline]
+    pub fn create_fence(&self) -> Result<EglSync, ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
+
+    #[inline]
+    pub fn swap_buffers_after(&self, sync: &EglSync, _timeout_ns: u64) -> Result<(), ContextError> {
+        match *sync {}
+    }
+
+    #[inline]
+    pub fn set_swap_behavior_preserved(&self, _preserved: bool) -> Result<(), ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
+
+    #[inline]
+    pub fn set_presentation_time(&self, _nanos: i64) -> Result<(), ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
+
+    #[inline]
+    pub fn next_frame_id(&self) -> Result<u64, ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
+
+    #[inline]
+    pub fn frame_timestamps(&self, frame_id: u64) -> Result<FrameTimestamps, ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
+
+    #[inline]
+    pub fn recreate(&mut self) -> Result<(), CreationError> {
+        Err(CreationError::NotSupported("recreating EAGL contexts".to_string()))
+    }
+
+    #[inline]
+    pub fn recreate_surface(&mut self) -> Result<(), CreationError> {
+        Err(CreationError::NotSupported("recreating EAGL contexts".to_string()))
+    }
+
+    #[inline]
+    pub fn swap_behavior(&self) -> Result<SwapBehavior, ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
+
+    #[inline]
+    pub fn multisample_resolve(&self) -> Result<MultisampleResolve, ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
+
     #[inline]
     pub unsafe fn raw_handle(&self) -> *mut raw::c_void {
         self.eagl_context as *mut raw::c_void
     }
 
+    #[inline]
+    pub fn share_token(&self) -> Option<ShareToken> {
+        None
+    }
+
+    #[inline]
+    pub unsafe fn raw_config(&self) -> Option<(*const raw::c_void, *const raw::c_void)> {
+        None
+    }
+
+    #[inline]
+    pub fn config_id(&self) -> Option<i32> {
+        None
+    }
+
+    #[inline]
+    pub fn egl_extensions(&self) -> Option<&[String]> {
+        None
+    }
+
+    #[inline]
+    pub fn egl_version(&self) -> Option<(i32, i32)> {
+        None
+    }
+
     #[inline]
     pub fn get_api(&self) -> Api {
         Api::OpenGlEs
     }
+
+    #[inline]
+    pub fn supports_vsync_mode(&self, mode: VSyncMode) -> bool {
+        // `presentRenderbuffer:` always waits for the display refresh.
+        matches!(mode, VSyncMode::On)
+    }
+
+    #[inline]
+    pub fn supports_mailbox(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn set_vsync_mode(&self, mode: VSyncMode) -> Result<(), VSyncError> {
+        if self.supports_vsync_mode(mode) {
+            Ok(())
+        } else {
+            Err(VSyncError::UnsupportedVSyncMode(mode))
+        }
+    }
+
+    #[inline]
+    pub fn get_vsync_mode(&self) -> Option<VSyncMode> {
+        Some(VSyncMode::On)
+    }
+
+    #[inline]
+    pub fn vsync_range(&self) -> Option<(i32, i32)> {
+        Some((1, 1))
+    }
+
+    #[inline]
+    pub fn context_priority(&self) -> Option<Priority> {
+        None
+    }
 }
 
 fn create_view_class() {
@@ -6,10 +6,6 @@
     target_os = "openbsd",
 ))]
 
-pub mod ffi {
-    pub use osmesa_sys::OSMesaContext;
-}
-
 use crate::{
     Api, ContextError, CreationError, GlAttributes, GlProfile, GlRequest, PixelFormatRequirements,
     Robustness,
@@ -66,7 +62,7 @@ impl std::error::Error for LoadingError {
 
 impl OsMesaContext {
     pub fn new(
-        _pf_reqs: &PixelFormatRequirements,
+        pf_reqs: &PixelFormatRequirements,
         opengl: &GlAttributes<&OsMesaContext>,
         size: dpi::PhysicalSize<u32>,
     ) -> Result<Self, CreationError> {
@@ -77,6 +73,8 @@ impl OsMesaContext {
         if opengl.sharing.is_some() {
             panic!("Context sharing not possible with OsMesa")
         }
+        opengl.check_no_external_share()?;
+        opengl.check_no_config_context()?;
 
         match opengl.robustness {
             Robustness::RobustNoResetNotification | Robustness::RobustLoseContextOnReset => {
@@ -87,6 +85,11 @@ impl OsMesaContext {
 
         // TODO: use `pf_reqs` for the format
 
+        // OsMesa buffers are never multisampled.
+        if !pf_reqs.multisampling_satisfied_by(None) {
+            return Err(CreationError::NoAvailablePixelFormat);
+        }
+
         let mut attribs = Vec::new();
 
         if let Some(profile) = opengl.profile {
@@ -113,7 +116,8 @@ impl OsMesaContext {
             GlRequest::Specific(Api::OpenGlEs, _) | GlRequest::Specific(Api::WebGl, _) => {
                 return Err(CreationError::NoBackendAvailable(Box::new(NoEsOrWebGlSupported)));
             }
-            GlRequest::GlThenGles { opengl_version: (major, minor), .. } => {
+            GlRequest::GlThenGles { opengl_version: (major, minor), .. }
+            | GlRequest::GlesThenGl { opengl_version: (major, minor), .. } => {
                 attribs.push(osmesa_sys::OSMESA_CONTEXT_MAJOR_VERSION);
                 attribs.push(major as raw::c_int);
                 attribs.push(osmesa_sys::OSMESA_CONTEXT_MINOR_VERSION);
@@ -129,9 +133,7 @@ impl OsMesaContext {
         Ok(OsMesaContext {
             width: size.0,
             height: size.1,
-            buffer: std::iter::repeat(unsafe { std::mem::zeroed() })
-                .take((size.0 * size.1) as usize)
-                .collect(),
+            buffer: vec![unsafe { std::mem::zeroed() }; (size.0 * size.1) as usize],
             context: unsafe {
                 let ctx =
                     osmesa_sys::OSMesaCreateContextAttribs(attribs.as_ptr(), std::ptr::null_mut());
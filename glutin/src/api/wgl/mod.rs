@@ -1,10 +1,11 @@
-#![cfg(any(target_os = "windows"))]
+#![cfg(target_os = "windows")]
 
 mod make_current_guard;
 
 use crate::{
-    Api, ContextError, CreationError, GlAttributes, GlProfile, GlRequest, PixelFormat,
-    PixelFormatRequirements, ReleaseBehavior, Robustness, VSyncError, VSyncMode,
+    Api, Backend, ColorBufferType, ContextError, CreationError, CreationReport, GlAttributes,
+    GlProfile, GlRequest, PixelFormat, PixelFormatRequirements, ReleaseBehavior, Robustness,
+    SwapMethod, TransparentType, VSyncError, VSyncMode,
 };
 
 use self::make_current_guard::CurrentContextGuard;
@@ -33,6 +34,9 @@ pub struct Context {
 
     supports_adaptive_vsync: bool,
 
+    /// The space-separated list of supported WGL extensions.
+    extensions: String,
+
     hdc: HDC,
 
     /// Bound to `opengl32.dll`.
@@ -146,6 +150,12 @@ impl Context {
                 .map_err(|_| CreationError::NoAvailablePixelFormat)?
         };
 
+        if !pf_reqs.multisampling_satisfied_by(pixel_format.multisampling)
+            || !pf_reqs.alpha_satisfied_by(pixel_format.alpha_bits)
+        {
+            return Err(CreationError::NoAvailablePixelFormat);
+        }
+
         // creating the OpenGL context
         let context =
             create_context(Some((&extra_functions, pf_reqs, opengl, &extensions)), win, hdc)?;
@@ -172,6 +182,7 @@ impl Context {
             pixel_format,
             extra_functions,
             supports_adaptive_vsync,
+            extensions,
         })
     }
 
@@ -263,6 +274,25 @@ impl Context {
     pub fn get_pixel_format(&self) -> PixelFormat {
         self.pixel_format.clone()
     }
+
+    #[inline]
+    pub fn creation_report(&self) -> CreationReport {
+        CreationReport {
+            backend: Backend::Wgl,
+            backend_version: None,
+            api: Api::OpenGl,
+            version: None,
+            robustness: None,
+            debug: None,
+            extensions: self
+                .extensions
+                .split(' ')
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect(),
+            pixel_format: self.pixel_format.clone(),
+        }
+    }
 }
 
 unsafe impl Send for Context {}
@@ -283,6 +313,8 @@ unsafe fn create_context(
     let share;
 
     if let Some((extra_functions, _pf_reqs, opengl, extensions)) = extra {
+        opengl.check_no_external_share()?;
+        opengl.check_no_config_context()?;
         share = opengl.sharing.unwrap_or(std::ptr::null_mut());
 
         if extensions.split(' ').any(|i| i == "WGL_ARB_create_context") {
@@ -312,7 +344,8 @@ unsafe fn create_context(
                 GlRequest::Specific(_, _) => {
                     return Err(CreationError::OpenGlVersionNotSupported);
                 }
-                GlRequest::GlThenGles { opengl_version: (major, minor), .. } => {
+                GlRequest::GlThenGles { opengl_version: (major, minor), .. }
+                | GlRequest::GlesThenGl { opengl_version: (major, minor), .. } => {
                     attributes.push(gl::wgl_extra::CONTEXT_MAJOR_VERSION_ARB as raw::c_int);
                     attributes.push(major as raw::c_int);
                     attributes.push(gl::wgl_extra::CONTEXT_MINOR_VERSION_ARB as raw::c_int);
@@ -452,6 +485,13 @@ unsafe fn choose_native_pixel_format_id(
         return Err(());
     }
 
+    if pf_reqs.color_buffer_type == ColorBufferType::Luminance
+        || pf_reqs.color_key.is_some()
+        || pf_reqs.bindable_to_texture
+    {
+        return Err(());
+    }
+
     if pf_reqs.release_behavior != ReleaseBehavior::Flush {
         return Err(());
     }
@@ -469,7 +509,14 @@ unsafe fn choose_native_pixel_format_id(
 
             let f2 = if pf_reqs.stereoscopy { PFD_STEREO } else { 0 };
 
-            PFD_DRAW_TO_WINDOW | PFD_SUPPORT_OPENGL | f1 | f2
+            // Only hints for `ChoosePixelFormat`.
+            let f3 = match pf_reqs.swap_method {
+                Some(SwapMethod::Exchange) => PFD_SWAP_EXCHANGE,
+                Some(SwapMethod::Copy) => PFD_SWAP_COPY,
+                Some(SwapMethod::Undefined) | None => 0,
+            };
+
+            PFD_DRAW_TO_WINDOW | PFD_SUPPORT_OPENGL | f1 | f2 | f3
         },
         iPixelType: PFD_TYPE_RGBA,
         cColorBits: pf_reqs.color_bits.unwrap_or(0),
@@ -539,11 +586,18 @@ unsafe fn choose_native_pixel_format(
         color_bits: output.cRedBits + output.cGreenBits + output.cBlueBits,
         alpha_bits: output.cAlphaBits,
         depth_bits: output.cDepthBits,
+        float_depth_attachments: false,
         stencil_bits: output.cStencilBits,
+        packed_depth_stencil: false,
         stereoscopy: (output.dwFlags & PFD_STEREO) != 0,
         double_buffer: (output.dwFlags & PFD_DOUBLEBUFFER) != 0,
         multisampling: None,
         srgb: false,
+        native_renderable: false,
+        bindable_to_texture: false,
+        color_buffer_type: ColorBufferType::Rgb,
+        transparent_type: TransparentType::None,
+        transparent_color: (0, 0, 0),
     };
 
     if pf_desc.alpha_bits < pf_reqs.alpha_bits.unwrap_or(0) {
@@ -601,6 +655,13 @@ unsafe fn choose_arb_pixel_format_id(
             out.push(gl::wgl_extra::TYPE_RGBA_ARB as raw::c_int);
         }
 
+        if pf_reqs.color_buffer_type == ColorBufferType::Luminance
+            || pf_reqs.color_key.is_some()
+            || pf_reqs.bindable_to_texture
+        {
+            return Err(());
+        }
+
         if let Some(hardware_accelerated) = pf_reqs.hardware_accelerated {
             out.push(gl::wgl_extra::ACCELERATION_ARB as raw::c_int);
             out.push(if hardware_accelerated {
@@ -647,6 +708,15 @@ unsafe fn choose_arb_pixel_format_id(
             }
         }
 
+        if let Some(swap_method) = pf_reqs.swap_method {
+            out.push(gl::wgl_extra::SWAP_METHOD_ARB as raw::c_int);
+            out.push(match swap_method {
+                SwapMethod::Exchange => gl::wgl_extra::SWAP_EXCHANGE_ARB,
+                SwapMethod::Copy => gl::wgl_extra::SWAP_COPY_ARB,
+                SwapMethod::Undefined => gl::wgl_extra::SWAP_UNDEFINED_ARB,
+            } as raw::c_int);
+        }
+
         out.push(gl::wgl_extra::STEREO_ARB as raw::c_int);
         out.push(if pf_reqs.stereoscopy { 1 } else { 0 });
 
@@ -724,7 +794,9 @@ unsafe fn choose_arb_pixel_format(
             + get_info(gl::wgl_extra::BLUE_BITS_ARB) as u8,
         alpha_bits: get_info(gl::wgl_extra::ALPHA_BITS_ARB) as u8,
         depth_bits: get_info(gl::wgl_extra::DEPTH_BITS_ARB) as u8,
+        float_depth_attachments: false,
         stencil_bits: get_info(gl::wgl_extra::STENCIL_BITS_ARB) as u8,
+        packed_depth_stencil: false,
         stereoscopy: get_info(gl::wgl_extra::STEREO_ARB) != 0,
         double_buffer: get_info(gl::wgl_extra::DOUBLE_BUFFER_ARB) != 0,
         multisampling: {
@@ -744,6 +816,11 @@ unsafe fn choose_arb_pixel_format(
         } else {
             false
         },
+        native_renderable: false,
+        bindable_to_texture: false,
+        color_buffer_type: ColorBufferType::Rgb,
+        transparent_type: TransparentType::None,
+        transparent_color: (0, 0, 0),
     };
 
     Ok(pf_desc)
@@ -1,6 +1,9 @@
 use super::*;
 
+use std::ffi::CStr;
 use std::marker::PhantomData;
+use std::thread::{self, ThreadId};
+use std::time::Duration;
 use winit::event_loop::EventLoopWindowTarget;
 
 /// Represents an OpenGL [`Context`].
@@ -21,7 +24,7 @@ use winit::event_loop::EventLoopWindowTarget;
 /// #    .build_windowed(wb, &el)
 /// #    .unwrap();
 /// let cb = glutin::ContextBuilder::new()
-///     .with_vsync(true)
+///     .with_vsync(glutin::VSyncMode::On)
 ///     .with_multisampling(8)
 ///     .with_shared_lists(some_context.context());
 /// # }
@@ -29,9 +32,105 @@ use winit::event_loop::EventLoopWindowTarget;
 #[derive(Debug)]
 pub struct Context<T: ContextCurrentState> {
     pub(crate) context: platform_impl::Context,
+    pub(crate) owner: Option<ThreadId>,
+    pub(crate) debug_callback: DebugCallbackSlot,
     pub(crate) phantom: PhantomData<T>,
 }
 
+/// An EGL fence sync object, created with [`Context::create_fence()`] and
+/// destroyed on drop.
+#[derive(Debug)]
+pub struct EglSync(pub(crate) platform_impl::EglSync);
+
+impl EglSync {
+    /// Waits up to `timeout_ns` nanoseconds for the fence to be signaled,
+    /// with `eglClientWaitSyncKHR`.
+    ///
+    /// The commands up to the fence are flushed first, so waiting from
+    /// another thread or API can't stall on commands that were never
+    /// submitted. A timeout of `0` only polls the fence.
+    pub fn client_wait(&self, timeout_ns: u64) -> Result<SyncStatus, ContextError> {
+        Ok(if self.0.wait(timeout_ns)? { SyncStatus::Signaled } else { SyncStatus::TimeoutExpired })
+    }
+}
+
+/// The outcome of [`EglSync::client_wait()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SyncStatus {
+    /// The fence was signaled, so every command before it has completed.
+    Signaled,
+    /// The timeout elapsed before the fence was signaled.
+    TimeoutExpired,
+}
+
+/// A message from the GL debug output, delivered to the callback installed
+/// with [`Context::set_debug_callback()`].
+///
+/// Apart from `message`, the fields hold the raw `GLenum` and `GLuint` values
+/// passed by the driver, e.g. `GL_DEBUG_SEVERITY_HIGH` (`0x9146`) for
+/// `severity`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DebugMessage {
+    /// What generated the message, e.g. `GL_DEBUG_SOURCE_API`.
+    pub source: u32,
+    /// The kind of message, e.g. `GL_DEBUG_TYPE_ERROR`.
+    pub type_: u32,
+    /// The implementation-specific identifier of the message.
+    pub id: u32,
+    /// How severe the reported issue is, e.g. `GL_DEBUG_SEVERITY_MEDIUM`.
+    pub severity: u32,
+    /// The message itself.
+    pub message: String,
+}
+
+type DebugCallback = Mutex<Box<dyn FnMut(DebugMessage) + Send>>;
+
+/// Owns the callback passed to `glDebugMessageCallback`, which has to outlive
+/// the GL context it's installed on.
+#[derive(Default)]
+pub(crate) struct DebugCallbackSlot(Mutex<Option<Box<DebugCallback>>>);
+
+impl std::fmt::Debug for DebugCallbackSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_tuple("DebugCallbackSlot").finish()
+    }
+}
+
+type GlDebugProc = extern "system" fn(
+    u32,
+    u32,
+    u32,
+    u32,
+    i32,
+    *const std::os::raw::c_char,
+    *mut core::ffi::c_void,
+);
+
+extern "system" fn debug_callback_trampoline(
+    source: u32,
+    type_: u32,
+    id: u32,
+    severity: u32,
+    length: i32,
+    message: *const std::os::raw::c_char,
+    user_param: *mut core::ffi::c_void,
+) {
+    let callback = unsafe { &*(user_param as *const DebugCallback) };
+    let message = unsafe {
+        if length < 0 {
+            CStr::from_ptr(message).to_string_lossy().into_owned()
+        } else {
+            let bytes = std::slice::from_raw_parts(message as *const u8, length as usize);
+            String::from_utf8_lossy(bytes).into_owned()
+        }
+    };
+    // Without `GL_DEBUG_OUTPUT_SYNCHRONOUS`, drivers may call this from
+    // several threads at once.
+    if let Ok(mut callback) = callback.lock() {
+        callback(DebugMessage { source, type_, id, severity, message });
+    }
+}
+
 #[derive(Debug)]
 pub enum VSyncError {
     ContextError(ContextError),
@@ -51,31 +150,137 @@ impl std::fmt::Display for VSyncError {
 
 impl std::error::Error for VSyncError {}
 
+impl Context<NotCurrent> {
+    pub(crate) fn new(context: platform_impl::Context, thread_affinity: bool) -> Self {
+        let owner = if thread_affinity { Some(thread::current().id()) } else { None };
+        Context {
+            context,
+            owner,
+            debug_callback: DebugCallbackSlot::default(),
+            phantom: PhantomData,
+        }
+    }
+}
+
 impl<T: ContextCurrentState> Context<T> {
+    /// Fails if the context was built with
+    /// [`ContextBuilder::with_thread_affinity()`] on another thread.
+    fn check_thread(&self) -> Result<(), ContextError> {
+        match self.owner {
+            Some(owner) if owner != thread::current().id() => Err(ContextError::OsError(format!(
+                "context is bound to thread {:?}, but was used from {:?}",
+                owner,
+                thread::current().id()
+            ))),
+            _ => Ok(()),
+        }
+    }
+
     /// See [`ContextWrapper::make_current()`].
     pub unsafe fn make_current(self) -> Result<Context<PossiblyCurrent>, (Self, ContextError)> {
+        if let Err(err) = self.check_thread() {
+            return Err((self, err));
+        }
         match self.context.make_current() {
-            Ok(()) => Ok(Context { context: self.context, phantom: PhantomData }),
-            Err(err) => Err((Context { context: self.context, phantom: PhantomData }, err)),
+            Ok(()) => Ok(Context {
+                context: self.context,
+                owner: self.owner,
+                debug_callback: self.debug_callback,
+                phantom: PhantomData,
+            }),
+            Err(err) => Err((
+                Context {
+                    context: self.context,
+                    owner: self.owner,
+                    debug_callback: self.debug_callback,
+                    phantom: PhantomData,
+                },
+                err,
+            )),
+        }
+    }
+
+    /// Like [`make_current()`][Self::make_current()], but keeps reading from
+    /// `read`'s surface while drawing to this context's surface.
+    ///
+    /// With both surfaces bound, `glBlitFramebuffer` between the default
+    /// framebuffers copies from `read` to this context's surface, e.g. to
+    /// downscale a large offscreen surface without a shader pass.
+    ///
+    /// Both contexts must have a surface, and `read`'s surface must have been
+    /// created on the same display with the same config as this context,
+    /// otherwise [`ContextError::OsError`] is returned.
+    ///
+    /// This is only supported with EGL, and returns
+    /// [`ContextError::FunctionUnavailable`] on other backends.
+    pub unsafe fn make_current_asymmetric<U: ContextCurrentState>(
+        self,
+        read: &Context<U>,
+    ) -> Result<Context<PossiblyCurrent>, (Self, ContextError)> {
+        if let Err(err) = self.check_thread() {
+            return Err((self, err));
+        }
+        match self.context.make_current_asymmetric(&read.context) {
+            Ok(()) => Ok(Context {
+                context: self.context,
+                owner: self.owner,
+                debug_callback: self.debug_callback,
+                phantom: PhantomData,
+            }),
+            Err(err) => Err((
+                Context {
+                    context: self.context,
+                    owner: self.owner,
+                    debug_callback: self.debug_callback,
+                    phantom: PhantomData,
+                },
+                err,
+            )),
         }
     }
 
     /// See [`ContextWrapper::make_not_current()`].
     pub unsafe fn make_not_current(self) -> Result<Context<NotCurrent>, (Self, ContextError)> {
+        if let Err(err) = self.check_thread() {
+            return Err((self, err));
+        }
         match self.context.make_not_current() {
-            Ok(()) => Ok(Context { context: self.context, phantom: PhantomData }),
-            Err(err) => Err((Context { context: self.context, phantom: PhantomData }, err)),
+            Ok(()) => Ok(Context {
+                context: self.context,
+                owner: self.owner,
+                debug_callback: self.debug_callback,
+                phantom: PhantomData,
+            }),
+            Err(err) => Err((
+                Context {
+                    context: self.context,
+                    owner: self.owner,
+                    debug_callback: self.debug_callback,
+                    phantom: PhantomData,
+                },
+                err,
+            )),
         }
     }
 
     /// See [`ContextWrapper::treat_as_not_current()`].
     pub unsafe fn treat_as_not_current(self) -> Context<NotCurrent> {
-        Context { context: self.context, phantom: PhantomData }
+        Context {
+            context: self.context,
+            owner: self.owner,
+            debug_callback: self.debug_callback,
+            phantom: PhantomData,
+        }
     }
 
     /// See [`ContextWrapper::treat_as_current()`].
     pub unsafe fn treat_as_current(self) -> Context<PossiblyCurrent> {
-        Context { context: self.context, phantom: PhantomData }
+        Context {
+            context: self.context,
+            owner: self.owner,
+            debug_callback: self.debug_callback,
+            phantom: PhantomData,
+        }
     }
 
     /// See [`ContextWrapper::is_current()`].
@@ -88,6 +293,109 @@ impl<T: ContextCurrentState> Context<T> {
         self.context.get_api()
     }
 
+    /// Returns the robustness the context was actually created with, e.g. to
+    /// find out whether [`Robustness::TryRobustLoseContextOnReset`] got a
+    /// robust context.
+    ///
+    /// `Try*` variants are resolved to either their strict counterpart or
+    /// [`Robustness::NotRobust`]. Backends that don't keep track of it, such
+    /// as WGL and CGL, report [`Robustness::NotRobust`].
+    pub fn robustness(&self) -> Robustness {
+        self.context.creation_report().robustness.unwrap_or(Robustness::NotRobust)
+    }
+
+    /// Whether the context was created with robust buffer access, see
+    /// [`robustness()`][Self::robustness()].
+    pub fn is_robust(&self) -> bool {
+        matches!(
+            self.robustness(),
+            Robustness::RobustNoResetNotification | Robustness::RobustLoseContextOnReset
+        )
+    }
+
+    /// Binds this context's [`Api`] as the calling thread's EGL rendering API.
+    ///
+    /// [`make_current()`][Self::make_current()] already does this, so you only
+    /// need it when driving EGL state directly, e.g. through [`raw_handle()`].
+    /// This is a no-op on backends other than EGL.
+    ///
+    /// [`raw_handle()`]: crate::platform::ContextTraitExt::raw_handle()
+    pub fn bind_api(&self) -> Result<(), ContextError> {
+        self.context.bind_api()
+    }
+
+    /// Returns the handles another library needs to create a context sharing
+    /// OpenGL objects with this one, see [`ShareToken`].
+    ///
+    /// Returns [`None`] on backends other than EGL.
+    pub fn share_token(&self) -> Option<ShareToken> {
+        self.context.share_token()
+    }
+
+    /// Returns the `EGLDisplay` and `EGLConfig` this context was created
+    /// with, e.g. to match glutin's choice against `eglGetConfigs()`.
+    ///
+    /// The config is `EGL_NO_CONFIG_KHR` for contexts created without one,
+    /// except with [`ContextBuilder::with_no_config_context()`], where it is
+    /// the config of the context's surface.
+    /// Returns [`None`] on backends other than EGL.
+    pub unsafe fn raw_config(
+        &self,
+    ) -> Option<(*const std::os::raw::c_void, *const std::os::raw::c_void)> {
+        self.context.raw_config()
+    }
+
+    /// Returns the `EGL_CONFIG_ID` of the config this context was created
+    /// with, or `Some(0)` if it was created without one.
+    ///
+    /// Returns [`None`] on backends other than EGL.
+    pub fn config_id(&self) -> Option<i32> {
+        self.context.config_id()
+    }
+
+    /// Returns the extensions of the EGL display, as glutin queried them when
+    /// building this context.
+    ///
+    /// Returns [`None`] on backends other than EGL.
+    pub fn egl_extensions(&self) -> Option<&[String]> {
+        self.context.egl_extensions()
+    }
+
+    /// Returns the version of the EGL display as `(major, minor)`.
+    ///
+    /// Returns [`None`] on backends other than EGL.
+    pub fn egl_version(&self) -> Option<(i32, i32)> {
+        self.context.egl_version()
+    }
+
+    /// Returns whether this context can be made current without a surface,
+    /// i.e. whether surfaceless rendering into framebuffer objects works.
+    ///
+    /// This lets you pick between a surfaceless context and a dummy pbuffer
+    /// without a failing [`make_current()`][Self::make_current()]. On EGL it
+    /// checks `EGL_KHR_surfaceless_context` and, for OpenGL ES before 3.0,
+    /// `GL_OES_surfaceless_context`, the latter by briefly making the context
+    /// current without a surface. Always [`false`] on other backends.
+    pub fn supports_surfaceless(&self) -> bool {
+        self.context.supports_surfaceless()
+    }
+
+    /// Replaces the pbuffer of a headless context with a new one of `size`,
+    /// rather than recreating the whole context to render offscreen at
+    /// another resolution.
+    ///
+    /// The contents of the pbuffer are lost. If the context is current on the
+    /// calling thread, the new pbuffer is bound in place of the old one. On
+    /// another thread, it keeps rendering to the old one until made current
+    /// again.
+    ///
+    /// This is only supported for EGL pbuffers, and returns
+    /// [`ContextError::FunctionUnavailable`] for windowed or surfaceless
+    /// contexts and on other backends.
+    pub fn resize_pbuffer(&self, size: dpi::PhysicalSize<u32>) -> Result<(), ContextError> {
+        self.context.resize_pbuffer(size)
+    }
+
     pub fn supports_vsync_mode(&self, mode: VSyncMode) -> bool {
         self.context.supports_vsync_mode(mode)
     }
@@ -95,13 +403,505 @@ impl<T: ContextCurrentState> Context<T> {
     pub fn set_vsync_mode(&self, mode: VSyncMode) -> Result<(), VSyncError> {
         self.context.set_vsync_mode(mode)
     }
+
+    /// Returns the vsync mode currently applied to this context, e.g. the
+    /// swap interval picked by [`ContextBuilder::with_lowest_latency()`].
+    ///
+    /// Only EGL and EAGL (iOS) contexts keep track of it; others return
+    /// [`None`].
+    pub fn get_vsync_mode(&self) -> Option<VSyncMode> {
+        self.context.get_vsync_mode()
+    }
+
+    /// Returns the smallest and largest swap intervals accepted by
+    /// [`set_vsync_mode()`][Self::set_vsync_mode()], e.g. to gray out
+    /// unsupported options in a vsync setting. Negative values are adaptive
+    /// vsync.
+    ///
+    /// GLX, WGL and OsMesa contexts don't expose a range and return [`None`];
+    /// use [`supports_vsync_mode()`][Self::supports_vsync_mode()] there.
+    pub fn vsync_range(&self) -> Option<(i32, i32)> {
+        self.context.vsync_range()
+    }
+
+    /// Returns the GPU scheduling priority the context was given, see
+    /// [`ContextBuilder::with_context_priority()`].
+    ///
+    /// Returns [`None`] if it can't be queried, i.e. without
+    /// `EGL_IMG_context_priority` or on backends other than EGL.
+    pub fn context_priority(&self) -> Option<Priority> {
+        self.context.context_priority()
+    }
+
+    /// Returns the [`PresentMode`]s this context can be switched to with
+    /// [`set_vsync_mode()`][Self::set_vsync_mode()].
+    pub fn supported_present_modes(&self) -> Vec<PresentMode> {
+        [PresentMode::Fifo, PresentMode::FifoRelaxed, PresentMode::Mailbox, PresentMode::Immediate]
+            .iter()
+            .copied()
+            .filter(|mode| match mode {
+                PresentMode::Mailbox => {
+                    self.context.supports_mailbox()
+                        && self.context.supports_vsync_mode(mode.vsync_mode())
+                }
+                _ => self.context.supports_vsync_mode(mode.vsync_mode()),
+            })
+            .collect()
+    }
 }
 
 impl Context<PossiblyCurrent> {
+    /// Makes this context current on the calling thread through a borrow, for
+    /// contexts kept in a struct field while currency is managed by hand.
+    ///
+    /// This does what [`make_current()`][Self::make_current()] does without
+    /// moving the context, which is fine as [`PossiblyCurrent`] never
+    /// promised the context isn't current to begin with. Keeping track of
+    /// which context is current is then up to the caller.
+    pub unsafe fn make_current_in_place(&self) -> Result<(), ContextError> {
+        self.check_thread()?;
+        self.context.make_current()
+    }
+
+    /// Makes this context current on the calling thread, unless it already
+    /// is, and returns whether it had to switch.
+    ///
+    /// `Ok(false)` means the context was already current and nothing was
+    /// called, so GL state cached by the caller is still valid. After
+    /// `Ok(true)`, another context may have been current in between. Like
+    /// [`make_current()`][Self::make_current()], switching makes the context
+    /// that was current on the calling thread not current.
+    pub unsafe fn ensure_current(&self) -> Result<bool, ContextError> {
+        self.check_thread()?;
+        if self.context.is_current() {
+            return Ok(false);
+        }
+        self.context.make_current()?;
+        Ok(true)
+    }
+
     /// See [`ContextWrapper::get_proc_address()`].
     pub fn get_proc_address(&self, addr: &str) -> *const core::ffi::c_void {
         self.context.get_proc_address(addr)
     }
+
+    /// See [`ContextWrapper::get_proc_address_opt()`].
+    pub fn get_proc_address_opt(&self, addr: &str) -> Option<*const core::ffi::c_void> {
+        let ptr = self.get_proc_address(addr);
+        if ptr.is_null() {
+            None
+        } else {
+            Some(ptr)
+        }
+    }
+
+    /// Blocks until all previously issued GL commands have completed, or until
+    /// `timeout` has elapsed, whichever comes first.
+    ///
+    /// Unlike `glFinish`, this can't wedge the calling thread forever if the
+    /// GPU hangs. Returns `Ok(true)` if the commands completed and `Ok(false)`
+    /// if the wait timed out.
+    ///
+    /// This is implemented with `EGL_KHR_fence_sync`. Other backends, and EGL
+    /// displays lacking the extension, return
+    /// [`ContextError::FunctionUnavailable`].
+    pub fn finish_with_timeout(&self, timeout: Duration) -> Result<bool, ContextError> {
+        self.context.finish_with_timeout(timeout)
+    }
+
+    /// Inserts a fence after every GL command issued so far, e.g. to wait on
+    /// with [`ContextWrapper::swap_buffers_after()`].
+    ///
+    /// This needs `EGL_KHR_fence_sync`, like
+    /// [`finish_with_timeout()`][Self::finish_with_timeout()].
+    pub fn create_fence(&self) -> Result<EglSync, ContextError> {
+        self.context.create_fence().map(EglSync)
+    }
+
+    /// Submits every GL command issued so far to the GPU without waiting for
+    /// them, and returns a fence to wait on later.
+    ///
+    /// The fence is created before calling `glFlush`, so that the fence
+    /// itself is submitted too and waiting on it can't stall on unflushed
+    /// commands. Like [`create_fence()`][Self::create_fence()], this needs
+    /// `EGL_KHR_fence_sync`.
+    pub fn flush_with_fence(&self) -> Result<EglSync, ContextError> {
+        let fence = self.create_fence()?;
+        self.flush()?;
+        Ok(fence)
+    }
+
+    /// Calls `glFlush`, submitting every GL command issued so far to the GPU
+    /// without waiting for them, e.g. before another context uses the
+    /// objects they render to.
+    ///
+    /// Returns [`ContextError::FunctionUnavailable`] if `glFlush` can't be
+    /// loaded.
+    pub fn flush(&self) -> Result<(), ContextError> {
+        self.call_gl("glFlush")
+    }
+
+    /// Calls `glFinish`, blocking until every GL command issued so far has
+    /// completed. See [`finish_with_timeout()`][Self::finish_with_timeout()]
+    /// for a variant that can't block forever.
+    ///
+    /// Returns [`ContextError::FunctionUnavailable`] if `glFinish` can't be
+    /// loaded.
+    pub fn finish(&self) -> Result<(), ContextError> {
+        self.call_gl("glFinish")
+    }
+
+    /// Calls the GL function `name`, which takes no arguments and returns
+    /// nothing.
+    fn call_gl(&self, name: &str) -> Result<(), ContextError> {
+        let ptr = self.get_proc_address_opt(name).ok_or(ContextError::FunctionUnavailable)?;
+        let f =
+            unsafe { std::mem::transmute::<*const core::ffi::c_void, extern "system" fn()>(ptr) };
+        f();
+        Ok(())
+    }
+
+    /// Returns the age of the back buffer of this context's surface, like
+    /// [`WindowedContext::buffer_age()`], e.g. for a pbuffer.
+    ///
+    /// Returns [`None`] for surfaceless contexts too.
+    pub fn buffer_age(&self) -> Option<u32> {
+        self.context.buffer_age()
+    }
+
+    /// Returns whether [`buffer_age()`][Self::buffer_age()] can report the
+    /// age of the back buffer, see
+    /// [`WindowedContext::buffer_age_supported()`].
+    pub fn buffer_age_supported(&self) -> bool {
+        self.context.buffer_age_supported()
+    }
+
+    /// Returns `true` if the context was lost because of a graphics reset.
+    ///
+    /// This calls `glGetGraphicsResetStatus` (or its ARB, EXT or KHR variant)
+    /// and is only meaningful for contexts created with
+    /// [`Robustness::RobustLoseContextOnReset`] or
+    /// [`Robustness::TryRobustLoseContextOnReset`]. A render loop can poll it
+    /// once per frame to recreate its context before anything else fails.
+    ///
+    /// Returns `false` if none of these functions are available.
+    pub fn check_context_lost(&self) -> bool {
+        const GL_NO_ERROR: u32 = 0;
+
+        match self.graphics_reset_status() {
+            Some(status) => status != GL_NO_ERROR,
+            None => false,
+        }
+    }
+
+    /// Returns whether and why the context was reset, e.g. after a suspected
+    /// GPU hang.
+    ///
+    /// Like `glGetGraphicsResetStatus`, which this calls, reading the status
+    /// clears it. This needs a context whose reset notification strategy is
+    /// to lose the context, i.e. one created with
+    /// [`Robustness::RobustLoseContextOnReset`] or with
+    /// [`Robustness::TryRobustLoseContextOnReset`] where the driver supports
+    /// it, as reported by [`robustness()`][Self::robustness()]. Returns
+    /// [`ContextError::FunctionUnavailable`] otherwise, or if
+    /// `glGetGraphicsResetStatus` can't be loaded.
+    pub fn reset_status(&self) -> Result<ResetStatus, ContextError> {
+        const GL_NO_ERROR: u32 = 0;
+        const GL_GUILTY_CONTEXT_RESET: u32 = 0x8253;
+        const GL_INNOCENT_CONTEXT_RESET: u32 = 0x8254;
+
+        // Querying `GL_RESET_NOTIFICATION_STRATEGY` instead would raise
+        // `GL_INVALID_ENUM` on contexts without robustness.
+        if self.robustness() != Robustness::RobustLoseContextOnReset {
+            return Err(ContextError::FunctionUnavailable);
+        }
+
+        Ok(match self.graphics_reset_status().ok_or(ContextError::FunctionUnavailable)? {
+            GL_NO_ERROR => ResetStatus::NoError,
+            GL_GUILTY_CONTEXT_RESET => ResetStatus::GuiltyContextReset,
+            GL_INNOCENT_CONTEXT_RESET => ResetStatus::InnocentContextReset,
+            // Including `GL_PURGED_CONTEXT_RESET_NV`.
+            _ => ResetStatus::UnknownContextReset,
+        })
+    }
+
+    /// Calls `glGetGraphicsResetStatus` or whichever of its variants is
+    /// available, returning [`None`] if there is none.
+    fn graphics_reset_status(&self) -> Option<u32> {
+        let ptr = [
+            "glGetGraphicsResetStatus",
+            "glGetGraphicsResetStatusKHR",
+            "glGetGraphicsResetStatusARB",
+            "glGetGraphicsResetStatusEXT",
+        ]
+        .iter()
+        .find_map(|name| self.get_proc_address_opt(name))?;
+
+        let get_graphics_reset_status = unsafe {
+            std::mem::transmute::<*const core::ffi::c_void, extern "system" fn() -> u32>(ptr)
+        };
+        Some(get_graphics_reset_status())
+    }
+
+    /// Pops the oldest error flag recorded by the GL with `glGetError`,
+    /// returning [`None`] once there are no more (`GL_NO_ERROR`).
+    ///
+    /// Call this in a loop to drain every flag, e.g. after a suspicious batch
+    /// of calls when no debug callback is installed.
+    pub fn gl_error(&self) -> Option<u32> {
+        const GL_NO_ERROR: u32 = 0;
+
+        let ptr = self.get_proc_address_opt("glGetError")?;
+        let get_error = unsafe {
+            std::mem::transmute::<*const core::ffi::c_void, extern "system" fn() -> u32>(ptr)
+        };
+        match get_error() {
+            GL_NO_ERROR => None,
+            error => Some(error),
+        }
+    }
+
+    /// Installs `cb` to receive the messages of the GL debug output, through
+    /// `glDebugMessageCallback` or its `KHR`/`ARB` variants, and enables
+    /// `GL_DEBUG_OUTPUT`.
+    ///
+    /// Most drivers only report messages for contexts built with
+    /// [`ContextBuilder::with_gl_debug_flag()`]. Unless
+    /// `GL_DEBUG_OUTPUT_SYNCHRONOUS` is enabled, `cb` may be called from
+    /// driver threads. The callback is kept until it's replaced or the
+    /// context is destroyed.
+    ///
+    /// Returns [`ContextError::FunctionUnavailable`] if none of the entry
+    /// points can be loaded.
+    ///
+    /// # Safety
+    ///
+    /// The context must be current on the calling thread.
+    pub unsafe fn set_debug_callback(
+        &self,
+        cb: Box<dyn FnMut(DebugMessage) + Send>,
+    ) -> Result<(), ContextError> {
+        const GL_DEBUG_OUTPUT: u32 = 0x92E0;
+
+        let ptr =
+            ["glDebugMessageCallback", "glDebugMessageCallbackKHR", "glDebugMessageCallbackARB"]
+                .iter()
+                .find_map(|name| self.get_proc_address_opt(name))
+                .ok_or(ContextError::FunctionUnavailable)?;
+        let debug_message_callback = std::mem::transmute::<
+            *const core::ffi::c_void,
+            extern "system" fn(Option<GlDebugProc>, *const core::ffi::c_void),
+        >(ptr);
+
+        let callback: Box<DebugCallback> = Box::new(Mutex::new(cb));
+        debug_message_callback(
+            Some(debug_callback_trampoline),
+            &*callback as *const DebugCallback as *const _,
+        );
+        if let Some(ptr) = self.get_proc_address_opt("glEnable") {
+            let enable =
+                std::mem::transmute::<*const core::ffi::c_void, extern "system" fn(u32)>(ptr);
+            enable(GL_DEBUG_OUTPUT);
+        }
+
+        // The driver no longer refers to the previous callback, if any.
+        *self.debug_callback.0.lock().unwrap() = Some(callback);
+        Ok(())
+    }
+
+    /// Returns `GL_MAX_TEXTURE_SIZE`, the largest width or height of a
+    /// texture the implementation accepts.
+    pub fn max_texture_size(&self) -> i32 {
+        const GL_MAX_TEXTURE_SIZE: u32 = 0x0D33;
+
+        let mut value = [0];
+        self.get_integerv(GL_MAX_TEXTURE_SIZE, &mut value);
+        value[0]
+    }
+
+    /// Returns `GL_MAX_RENDERBUFFER_SIZE`, the largest width or height of a
+    /// renderbuffer the implementation accepts.
+    pub fn max_renderbuffer_size(&self) -> i32 {
+        const GL_MAX_RENDERBUFFER_SIZE: u32 = 0x84E8;
+
+        let mut value = [0];
+        self.get_integerv(GL_MAX_RENDERBUFFER_SIZE, &mut value);
+        value[0]
+    }
+
+    /// Returns `GL_MAX_VIEWPORT_DIMS`, the largest viewport width and height
+    /// the implementation accepts.
+    ///
+    /// Tiled renderers can use these limits to pick a tile size that fits
+    /// every render target.
+    pub fn max_viewport_dims(&self) -> (i32, i32) {
+        const GL_MAX_VIEWPORT_DIMS: u32 = 0x0D3A;
+
+        let mut value = [0; 2];
+        self.get_integerv(GL_MAX_VIEWPORT_DIMS, &mut value);
+        (value[0], value[1])
+    }
+
+    /// Returns whether the graphics reset status reports that video memory was
+    /// purged, in which case the contents of every buffer and texture are
+    /// lost and need to be uploaded again.
+    ///
+    /// Like `glGetGraphicsResetStatus`, which this calls, reading the status
+    /// clears it. This only ever returns `true` for contexts built with
+    /// [`ContextBuilder::with_memory_purge_handling()`].
+    pub fn was_memory_purged(&self) -> bool {
+        const GL_PURGED_CONTEXT_RESET_NV: u32 = 0x92BB;
+
+        self.graphics_reset_status() == Some(GL_PURGED_CONTEXT_RESET_NV)
+    }
+
+    /// Returns every GLSL version the context compiles shaders for, in
+    /// ascending order, as `(major, minor)` pairs matching the `#version`
+    /// directive, e.g. `(3, 30)` for `#version 330`.
+    ///
+    /// On desktop OpenGL 4.3 onwards, this enumerates
+    /// `GL_SHADING_LANGUAGE_VERSION` with `glGetStringi`. OpenGL ES and older
+    /// contexts only report their highest version, which is then the only one
+    /// returned, so lower versions it also accepts aren't listed. OpenGL ES
+    /// and desktop GLSL versions aren't told apart.
+    pub fn supported_glsl_versions(&self) -> Vec<(u8, u8)> {
+        const GL_VERSION: u32 = 0x1F02;
+        const GL_SHADING_LANGUAGE_VERSION: u32 = 0x8B8C;
+        const GL_NUM_SHADING_LANGUAGE_VERSIONS: u32 = 0x82E9;
+
+        // `GL_NUM_SHADING_LANGUAGE_VERSIONS` only exists in desktop OpenGL.
+        let has_indexed_query = match self.get_string(GL_VERSION) {
+            Some(version) if !version.starts_with("OpenGL ES") => {
+                parse_gl_version(&version) >= Some((4, 3))
+            }
+            _ => false,
+        };
+        let get_stringi = self.get_proc_address("glGetStringi");
+
+        let mut versions = Vec::new();
+        if has_indexed_query && !get_stringi.is_null() {
+            let get_stringi = unsafe {
+                std::mem::transmute::<
+                    *const core::ffi::c_void,
+                    extern "system" fn(u32, u32) -> *const u8,
+                >(get_stringi)
+            };
+
+            let mut count = [0];
+            self.get_integerv(GL_NUM_SHADING_LANGUAGE_VERSIONS, &mut count);
+            for index in 0..count[0].max(0) as u32 {
+                let ptr = get_stringi(GL_SHADING_LANGUAGE_VERSION, index);
+                if ptr.is_null() {
+                    continue;
+                }
+                let version = unsafe { CStr::from_ptr(ptr as *const _) }.to_string_lossy();
+                // An empty string stands for shaders without a `#version`.
+                if version.is_empty() {
+                    versions.push((1, 10));
+                } else if let Some(version) = parse_glsl_version(&version) {
+                    versions.push(version);
+                }
+            }
+        }
+
+        if versions.is_empty() {
+            if let Some(version) = self
+                .get_string(GL_SHADING_LANGUAGE_VERSION)
+                .and_then(|version| parse_glsl_version(&version))
+            {
+                versions.push(version);
+            }
+        }
+
+        versions.sort_unstable();
+        versions.dedup();
+        versions
+    }
+
+    /// Returns the `GL_RENDERER` string, e.g. `"llvmpipe (LLVM 12.0.0, 256
+    /// bits)"`, to tell software rasterizers apart from GPUs where
+    /// [`PixelFormat::hardware_accelerated`] isn't reliable.
+    ///
+    /// The context is made current first if it isn't, as with
+    /// [`ensure_current()`][Self::ensure_current()], and stays current
+    /// afterwards. Returns [`ContextError::FunctionUnavailable`] if
+    /// `glGetString` can't be loaded.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`ensure_current()`][Self::ensure_current()]: the context that
+    /// was current on the calling thread, if any, is made not current.
+    pub unsafe fn renderer_string(&self) -> Result<String, ContextError> {
+        const GL_RENDERER: u32 = 0x1F01;
+
+        self.ensure_current()?;
+        if self.get_proc_address_opt("glGetString").is_none() {
+            return Err(ContextError::FunctionUnavailable);
+        }
+        self.get_string(GL_RENDERER).ok_or_else(|| {
+            ContextError::OsError("glGetString(GL_RENDERER) returned NULL".to_string())
+        })
+    }
+
+    /// Calls `glGetString`, returning [`None`] if it can't be loaded or fails.
+    fn get_string(&self, name: u32) -> Option<String> {
+        let ptr = self.get_proc_address_opt("glGetString")?;
+        let get_string = unsafe {
+            std::mem::transmute::<*const core::ffi::c_void, extern "system" fn(u32) -> *const u8>(
+                ptr,
+            )
+        };
+        let string = get_string(name);
+        if string.is_null() {
+            return None;
+        }
+        Some(unsafe { CStr::from_ptr(string as *const _) }.to_string_lossy().into_owned())
+    }
+
+    /// Calls `glGetIntegerv`, leaving `out` untouched if it can't be loaded.
+    ///
+    /// `out` must be large enough for every value `pname` returns.
+    fn get_integerv(&self, pname: u32, out: &mut [i32]) {
+        let ptr = match self.get_proc_address_opt("glGetIntegerv") {
+            Some(ptr) => ptr,
+            None => return,
+        };
+        let get_integerv = unsafe {
+            std::mem::transmute::<*const core::ffi::c_void, extern "system" fn(u32, *mut i32)>(ptr)
+        };
+        get_integerv(pname, out.as_mut_ptr());
+    }
+}
+
+/// Parses the `major.minor` version at the start of a `GL_VERSION` string,
+/// skipping the `OpenGL ES` prefix of OpenGL ES contexts.
+fn parse_gl_version(version: &str) -> Option<(u8, u8)> {
+    let version = version.trim_start_matches("OpenGL ES").trim_start_matches("-CM").trim_start();
+    let mut numbers = version.split(|c: char| !c.is_ascii_digit());
+    let major = numbers.next()?.parse().ok()?;
+    let minor = numbers.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+/// Parses a GLSL version, either as reported by `GL_SHADING_LANGUAGE_VERSION`
+/// (`4.60 NVIDIA`, `OpenGL ES GLSL ES 3.20`) or as written in a `#version`
+/// directive (`460 core`, `300 es`).
+fn parse_glsl_version(version: &str) -> Option<(u8, u8)> {
+    let version = version.trim_start_matches("OpenGL ES GLSL ES").trim_start();
+    let version = version.split_whitespace().next()?;
+    match version.split_once('.') {
+        Some((major, minor)) => {
+            let digits = &minor[..minor.find(|c: char| !c.is_ascii_digit()).unwrap_or(minor.len())];
+            let minor: u8 = digits.parse().ok()?;
+            // `4.6` means the same as `4.60`.
+            let minor = if digits.len() == 1 { minor * 10 } else { minor };
+            Some((major.parse().ok()?, minor))
+        }
+        None => {
+            let number: u16 = version.parse().ok()?;
+            Some(((number / 100) as u8, (number % 100) as u8))
+        }
+    }
 }
 
 impl<'a, T: ContextCurrentState> ContextBuilder<'a, T> {
@@ -150,10 +950,85 @@ impl<'a, T: ContextCurrentState> ContextBuilder<'a, T> {
         el: &EventLoopWindowTarget<TE>,
         size: dpi::PhysicalSize<u32>,
     ) -> Result<Context<NotCurrent>, CreationError> {
-        let ContextBuilder { pf_reqs, gl_attr } = self;
+        let ContextBuilder { pf_reqs, gl_attr, software_fallback } = self;
         let gl_attr = gl_attr.map_sharing(|ctx| &ctx.context);
-        platform_impl::Context::new_headless(el, &pf_reqs, &gl_attr, size)
-            .map(|context| Context { context, phantom: PhantomData })
+        SoftwareFallback::retry(software_fallback.as_ref(), &pf_reqs, |pf_reqs| {
+            platform_impl::Context::new_headless(el, pf_reqs, &gl_attr, size)
+        })
+        .map(|context| Context::new(context, gl_attr.thread_affinity))
+    }
+
+    /// Builds a context sharing its objects with `existing`, meant to be sent
+    /// to a background thread and made current there, e.g. to upload
+    /// textures without stalling the render thread.
+    ///
+    /// The worker has no window, so it gets a 1x1 offscreen surface, or
+    /// whatever [`build_headless()`][Self::build_headless()] uses on the
+    /// platform. Any sharing set on the builder is replaced by `existing`.
+    /// As noted on [`ContextBuilder`], `el` has to be the event loop
+    /// `existing` was built with on Wayland.
+    ///
+    /// Objects created on the worker are only guaranteed to be visible to
+    /// `existing` once the worker has called `glFinish` or a fence it
+    /// created has been waited on.
+    pub fn build_shared_worker<TE, T2: ContextCurrentState>(
+        self,
+        el: &EventLoopWindowTarget<TE>,
+        existing: &'a Context<T2>,
+    ) -> Result<Context<NotCurrent>, CreationError> {
+        self.with_shared_lists(existing).build_headless(el, dpi::PhysicalSize::new(1, 1))
+    }
+
+    /// Returns how many pixel formats match the builder's requirements, without
+    /// creating a context.
+    ///
+    /// This is a cheap capability probe: a result of `0` means that building a
+    /// context with these requirements is bound to fail with
+    /// [`CreationError::NoAvailablePixelFormat`]. A non-zero count is not a
+    /// guarantee, as context creation applies additional filtering (e.g. on
+    /// the vsync mode).
+    ///
+    /// This is only supported with EGL, and returns
+    /// [`CreationError::NotSupported`] on other backends.
+    pub fn count_matching_formats<TE>(
+        &self,
+        el: &EventLoopWindowTarget<TE>,
+    ) -> Result<usize, CreationError> {
+        let gl_attr = self.gl_attr.clone().map_sharing(|ctx| &ctx.context);
+        platform_impl::Context::count_matching_formats(el, &self.pf_reqs, &gl_attr)
+    }
+
+    /// Lists every pixel format the driver offers for the builder's
+    /// requirements, without creating a context, e.g. to attach to a report
+    /// of [`CreationError::NoAvailablePixelFormat`].
+    ///
+    /// Like [`count_matching_formats()`][Self::count_matching_formats()],
+    /// this skips the additional filtering done when creating a context, and
+    /// surface-dependent fields such as [`PixelFormat::srgb`] aren't filled
+    /// in. This is only supported with EGL, and returns
+    /// [`CreationError::NotSupported`] on other backends.
+    pub fn enumerate_pixel_formats<TE>(
+        &self,
+        el: &EventLoopWindowTarget<TE>,
+    ) -> Result<Vec<PixelFormat>, CreationError> {
+        let gl_attr = self.gl_attr.clone().map_sharing(|ctx| &ctx.context);
+        platform_impl::Context::enumerate_pixel_formats(el, &self.pf_reqs, &gl_attr)
+    }
+
+    /// Returns the `(min, max)` swap intervals supported by the pixel formats
+    /// matching the builder's requirements, without creating a context, e.g.
+    /// to only offer vsync modes the driver can honour.
+    ///
+    /// The builder's own vsync mode is ignored, and the range spans every
+    /// matching format, so the format eventually picked may support a
+    /// narrower one. This is only supported with EGL, and returns
+    /// [`CreationError::NotSupported`] on other backends.
+    pub fn query_swap_interval_range<TE>(
+        &self,
+        el: &EventLoopWindowTarget<TE>,
+    ) -> Result<(i32, i32), CreationError> {
+        let gl_attr = self.gl_attr.clone().map_sharing(|ctx| &ctx.context);
+        platform_impl::Context::query_swap_interval_range(el, &self.pf_reqs, &gl_attr)
     }
 }
 
@@ -186,6 +1061,7 @@ pub trait ContextCurrentState: std::fmt::Debug + Clone {}
 impl ContextCurrentState for PossiblyCurrent {}
 impl ContextCurrentState for NotCurrent {}
 
+#[allow(dead_code)]
 trait FailToCompileIfNotSendSync
 where
     Self: Send + Sync,
@@ -63,8 +63,8 @@
 "
 )]
 #![deny(missing_debug_implementations)]
-#![allow(clippy::missing_safety_doc, clippy::too_many_arguments)]
-#![cfg_attr(feature = "cargo-clippy", deny(warnings))]
+#![allow(clippy::missing_safety_doc, clippy::result_large_err, clippy::too_many_arguments)]
+#![cfg_attr(clippy, deny(warnings))]
 
 #[cfg(any(
     target_os = "windows",
@@ -86,15 +86,18 @@ pub mod platform;
 mod api;
 mod context;
 mod platform_impl;
+mod pool;
 mod windowed;
 
 pub use crate::context::*;
+pub use crate::pool::*;
 pub use crate::windowed::*;
 pub use winit::*;
 
 use winit::error::OsError;
 
 use std::io;
+use std::sync::{Arc, Mutex};
 
 /// An object that allows you to build [`Context`]s, [`RawContext<T>`]s and
 /// [`WindowedContext<T>`]s.
@@ -107,11 +110,91 @@ pub struct ContextBuilder<'a, T: ContextCurrentState> {
     pub gl_attr: GlAttributes<&'a Context<T>>,
     /// The pixel format requirements
     pub pf_reqs: PixelFormatRequirements,
+    /// Asked whether to retry without hardware acceleration, see
+    /// [`ContextBuilder::with_software_fallback_confirm()`].
+    pub software_fallback: Option<SoftwareFallback<'a>>,
 }
 
 impl Default for ContextBuilder<'_, NotCurrent> {
     fn default() -> Self {
-        Self { gl_attr: Default::default(), pf_reqs: Default::default() }
+        Self { gl_attr: Default::default(), pf_reqs: Default::default(), software_fallback: None }
+    }
+}
+
+/// A callback deciding whether a context may fall back to software
+/// rendering.
+///
+/// Clones share the callback, which only ever runs once.
+#[derive(Clone)]
+pub struct SoftwareFallback<'a>(Arc<Mutex<Option<ConfirmFn<'a>>>>);
+
+type ConfirmFn<'a> = Box<dyn FnOnce() -> bool + Send + 'a>;
+
+impl std::fmt::Debug for SoftwareFallback<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "SoftwareFallback(...)")
+    }
+}
+
+impl<'a> SoftwareFallback<'a> {
+    /// Runs `build` with `pf_reqs`. If no hardware accelerated pixel format
+    /// was found and `fallback` confirms it, runs it again accepting any.
+    pub(crate) fn retry<R>(
+        fallback: Option<&Self>,
+        pf_reqs: &PixelFormatRequirements,
+        mut build: impl FnMut(&PixelFormatRequirements) -> Result<R, CreationError>,
+    ) -> Result<R, CreationError> {
+        match build(pf_reqs) {
+            Err(CreationError::NoAvailablePixelFormat)
+                if pf_reqs.hardware_accelerated == Some(true) =>
+            {
+                let confirmed = match fallback.and_then(|f| f.0.lock().unwrap().take()) {
+                    Some(confirm) => confirm(),
+                    None => false,
+                };
+                if confirmed {
+                    build(&PixelFormatRequirements {
+                        hardware_accelerated: None,
+                        ..pf_reqs.clone()
+                    })
+                } else {
+                    Err(CreationError::NoAvailablePixelFormat)
+                }
+            }
+            result => result,
+        }
+    }
+}
+
+/// A callback picking one of the pixel formats matching the
+/// [`PixelFormatRequirements`], see [`ContextBuilder::with_config_selector()`].
+///
+/// Clones share the callback.
+#[derive(Clone)]
+pub struct ConfigSelector(Arc<Mutex<SelectFn>>);
+
+type SelectFn = Box<dyn FnMut(&[PixelFormat]) -> usize + Send>;
+
+impl std::fmt::Debug for ConfigSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "ConfigSelector(...)")
+    }
+}
+
+impl ConfigSelector {
+    /// Returns the index of the format the callback picked out of `formats`.
+    #[allow(dead_code)]
+    pub(crate) fn select(&self, formats: &[PixelFormat]) -> Result<usize, CreationError> {
+        let index = (self.0.lock().unwrap())(formats);
+        if index < formats.len() {
+            Ok(index)
+        } else {
+            Err(CreationError::PlatformSpecific(format!(
+                "the config selector picked format {} out of {}",
+                index,
+                formats.len()
+            )))
+        }
     }
 }
 
@@ -137,6 +220,25 @@ impl<'a, T: ContextCurrentState> ContextBuilder<'a, T> {
         self
     }
 
+    /// Requests OpenGL ES `major.minor`, a shortcut for
+    /// [`with_gl()`][Self::with_gl()] with
+    /// `GlRequest::Specific(Api::OpenGlEs, (major, minor))`.
+    #[inline]
+    pub fn with_gl_es(self, major: u8, minor: u8) -> Self {
+        self.with_gl(GlRequest::Specific(Api::OpenGlEs, (major, minor)))
+    }
+
+    /// Requests a desktop OpenGL `major.minor` core profile context, a
+    /// shortcut for [`with_gl()`][Self::with_gl()] with
+    /// `GlRequest::Specific(Api::OpenGl, (major, minor))` and
+    /// [`with_gl_profile()`][Self::with_gl_profile()] with
+    /// [`GlProfile::Core`].
+    #[inline]
+    pub fn with_gl_core(self, major: u8, minor: u8) -> Self {
+        self.with_gl(GlRequest::Specific(Api::OpenGl, (major, minor)))
+            .with_gl_profile(GlProfile::Core)
+    }
+
     /// Sets the *debug* flag for the OpenGL [`Context`].
     ///
     /// The default value for this flag is `cfg!(debug_assertions)`, which means
@@ -162,16 +264,180 @@ impl<'a, T: ContextCurrentState> ContextBuilder<'a, T> {
     #[inline]
     pub fn with_vsync(mut self, vsync: VSyncMode) -> Self {
         self.gl_attr.vsync = vsync;
+        self.gl_attr.vsync_fallback = Vec::new();
+        self
+    }
+
+    /// Requests the first of `modes` that a pixel format supports, e.g.
+    /// `[Adaptive, On, Off]` to fall back from adaptive vsync, in place of
+    /// [`with_vsync()`][Self::with_vsync()].
+    ///
+    /// Only EGL filters pixel formats by swap interval, so other backends
+    /// always use the first mode. Use [`Context::get_vsync_mode()`] to find
+    /// out which one was picked.
+    #[inline]
+    pub fn with_swap_interval_fallback(mut self, modes: Vec<VSyncMode>) -> Self {
+        self.gl_attr.vsync = modes.first().copied().unwrap_or(VSyncMode::Off);
+        self.gl_attr.vsync_fallback = modes;
+        self
+    }
+
+    /// Requests a presentation mode, in place of [`with_vsync()`].
+    ///
+    /// This only picks the matching [`VSyncMode`]; check
+    /// [`Context::supported_present_modes()`] once the context is built to
+    /// find out whether the platform actually honors it.
+    ///
+    /// [`with_vsync()`]: Self::with_vsync()
+    #[inline]
+    pub fn with_present_mode(self, mode: PresentMode) -> Self {
+        self.with_vsync(mode.vsync_mode())
+    }
+
+    /// Requests the smallest swap interval the driver accepts, instead of
+    /// requiring vsync to be off.
+    ///
+    /// Some drivers force vsync by only allowing swap intervals of `1` and
+    /// up, in which case [`VSyncMode::Off`] finds no pixel format at all.
+    /// With this option, EGL prefers the configs with the lowest minimum swap
+    /// interval and applies it; use [`Context::get_vsync_mode()`] to find out
+    /// which one was picked. Other backends always allow turning vsync off,
+    /// so this is the same as [`VSyncMode::Off`] there.
+    #[inline]
+    pub fn with_lowest_latency(mut self) -> Self {
+        self.gl_attr.vsync = VSyncMode::Off;
+        self.gl_attr.lowest_latency = true;
         self
     }
 
     /// Share the display lists with the given [`Context`].
+    ///
+    /// With EGL, both contexts have to be on the same display, otherwise
+    /// building fails with [`CreationError::SharingNotPossible`].
     #[inline]
     pub fn with_shared_lists<T2: ContextCurrentState>(
         self,
         other: &'a Context<T2>,
     ) -> ContextBuilder<'a, T2> {
-        ContextBuilder { gl_attr: self.gl_attr.set_sharing(Some(other)), pf_reqs: self.pf_reqs }
+        ContextBuilder {
+            gl_attr: self.gl_attr.set_sharing(Some(other)),
+            pf_reqs: self.pf_reqs,
+            software_fallback: self.software_fallback,
+        }
+    }
+
+    /// Requests that purges of video memory, e.g. on NVIDIA after suspend and
+    /// resume, are reported through the graphics reset status, so that they
+    /// can be detected with [`Context::was_memory_purged()`].
+    ///
+    /// This needs `EGL_NV_robustness_video_memory_purge` and a context that
+    /// is lost on reset, see [`Robustness::RobustLoseContextOnReset`]. It is
+    /// silently ignored otherwise, including on backends other than EGL.
+    #[inline]
+    pub fn with_memory_purge_handling(mut self, handling: bool) -> Self {
+        self.gl_attr.memory_purge_handling = handling;
+        self
+    }
+
+    /// Ties the context to the thread that creates it.
+    ///
+    /// The context can still be moved around, but
+    /// [`make_current()`][ContextWrapper::make_current()] and
+    /// [`make_not_current()`][ContextWrapper::make_not_current()] return
+    /// [`ContextError::OsError`] when called from any other thread, instead
+    /// of leaving it to the driver to deal with the misuse.
+    #[inline]
+    pub fn with_thread_affinity(mut self, thread_affinity: bool) -> Self {
+        self.gl_attr.thread_affinity = thread_affinity;
+        self
+    }
+
+    /// Sets whether dropping the context waits for the GPU with `glFinish`
+    /// before destroying it.
+    ///
+    /// Some drivers only release a context once its work is done, but that
+    /// wait can take seconds at shutdown. Turn it off if you know the GPU is
+    /// idle by then. This is only taken into account with EGL.
+    ///
+    /// The default value is [`true`].
+    #[inline]
+    pub fn with_finish_on_drop(mut self, finish_on_drop: bool) -> Self {
+        self.gl_attr.finish_on_drop = finish_on_drop;
+        self
+    }
+
+    /// Sets whether the EGL display is terminated with `eglTerminate` once the
+    /// last glutin context on it is gone.
+    ///
+    /// Only displays glutin initialized itself are ever terminated. Still,
+    /// another library that started using the display afterwards loses it
+    /// along with every resource it created there, so only turn this on if
+    /// nothing else in the process uses EGL. This is only taken into account
+    /// with EGL.
+    ///
+    /// The default value is [`false`], which leaves displays initialized
+    /// until the process exits.
+    #[inline]
+    pub fn with_egl_display_termination(mut self, terminate: bool) -> Self {
+        self.gl_attr.terminate_display = terminate;
+        self
+    }
+
+    /// Creates the context with `EGL_NO_CONFIG_KHR`, so that it can be made
+    /// current with surfaces of any config rather than only the one glutin
+    /// chose.
+    ///
+    /// The context's own surface still uses the chosen config, which
+    /// [`Context::raw_config()`] keeps reporting. This requires
+    /// `EGL_KHR_no_config_context`, and creation fails with
+    /// [`CreationError::NotSupported`] without it or on backends other than
+    /// EGL.
+    #[inline]
+    pub fn with_no_config_context(mut self) -> Self {
+        self.gl_attr.no_config_context = true;
+        self
+    }
+
+    /// Asks for a context scheduled with the given priority on the GPU, e.g.
+    /// a high one for VR compositors that can't afford to be preempted.
+    ///
+    /// This needs `EGL_IMG_context_priority`, and is silently ignored
+    /// without it or on backends other than EGL. Drivers may also give the
+    /// context a lower priority than asked for, which
+    /// [`Context::context_priority()`] reports.
+    #[inline]
+    pub fn with_context_priority(mut self, priority: Priority) -> Self {
+        self.gl_attr.priority = Some(priority);
+        self
+    }
+
+    /// Appends attribute and value pairs to the ones glutin passes to
+    /// `eglCreateContext`, e.g. `EGL_CONTEXT_PRIORITY_LEVEL_IMG` for drivers
+    /// glutin has no option for.
+    ///
+    /// The pairs are passed through unchecked: an attribute the driver
+    /// doesn't know fails creation with `EGL_BAD_ATTRIBUTE`, and one glutin
+    /// sets as well is given twice. They are only applied with EGL and
+    /// silently ignored on other backends.
+    #[inline]
+    pub fn with_raw_context_attributes(mut self, attributes: Vec<(i32, i32)>) -> Self {
+        self.gl_attr.raw_context_attributes = attributes;
+        self
+    }
+
+    /// Shares OpenGL objects with a context created outside of glutin, e.g. by
+    /// another library using EGL.
+    ///
+    /// Only EGL backends support this; others fail with
+    /// [`CreationError::NotSupported`]. The new context must be created on the
+    /// token's `EGLDisplay`, and [`with_shared_lists()`] can't be used at the
+    /// same time.
+    ///
+    /// [`with_shared_lists()`]: Self::with_shared_lists()
+    #[inline]
+    pub fn with_external_share(mut self, token: ShareToken) -> Self {
+        self.gl_attr.external_share = Some(token);
+        self
     }
 
     /// Sets the multisampling level to request. A value of `0` indicates that
@@ -192,6 +458,20 @@ impl<'a, T: ContextCurrentState> ContextBuilder<'a, T> {
         self
     }
 
+    /// Like [`with_multisampling()`][Self::with_multisampling()], but context
+    /// creation fails with [`CreationError::NoAvailablePixelFormat`] instead
+    /// of silently settling for a format with fewer samples.
+    ///
+    /// # Panic
+    ///
+    /// Will panic if `samples` is not a power of two.
+    #[inline]
+    pub fn with_required_multisampling(mut self, samples: u16) -> Self {
+        self = self.with_multisampling(samples);
+        self.pf_reqs.multisampling_required = true;
+        self
+    }
+
     /// Sets the number of bits in the depth buffer.
     #[inline]
     pub fn with_depth_buffer(mut self, bits: u8) -> Self {
@@ -199,6 +479,37 @@ impl<'a, T: ContextCurrentState> ContextBuilder<'a, T> {
         self
     }
 
+    /// Sets whether the color buffer must be in a floating point format, see
+    /// [`PixelFormatRequirements::float_color_buffer`].
+    #[inline]
+    pub fn with_float_color_buffer(mut self, float_color_buffer: bool) -> Self {
+        self.pf_reqs.float_color_buffer = float_color_buffer;
+        self
+    }
+
+    /// Sets whether pbuffers must be bindable to a texture, for
+    /// render-to-texture with `eglBindTexImage`. See
+    /// [`PixelFormatRequirements::bindable_to_texture`].
+    #[inline]
+    pub fn with_bindable_to_texture(mut self, bindable_to_texture: bool) -> Self {
+        self.pf_reqs.bindable_to_texture = bindable_to_texture;
+        self
+    }
+
+    /// Prefers formats conformant with a client API that supports
+    /// `GL_DEPTH_COMPONENT32F` renderbuffers, e.g. for reverse-Z rendering
+    /// into a framebuffer object.
+    ///
+    /// This doesn't affect the depth buffer of the window itself. Other
+    /// formats are still used if nothing else matches, so check
+    /// [`PixelFormat::float_depth_attachments`] afterwards. This is only taken
+    /// into account with EGL.
+    #[inline]
+    pub fn with_float_depth_attachments(mut self, float_depth_attachments: bool) -> Self {
+        self.pf_reqs.float_depth_attachments = float_depth_attachments;
+        self
+    }
+
     /// Sets the number of bits in the stencil buffer.
     #[inline]
     pub fn with_stencil_buffer(mut self, bits: u8) -> Self {
@@ -206,15 +517,113 @@ impl<'a, T: ContextCurrentState> ContextBuilder<'a, T> {
         self
     }
 
+    /// Prefers formats storing depth and stencil together in a packed
+    /// `DEPTH24_STENCIL8` buffer, which is all some OpenGL ES drivers offer.
+    ///
+    /// Formats with separate buffers are still used if nothing else matches,
+    /// so check [`PixelFormat::packed_depth_stencil`] afterwards. Whether a
+    /// format is packed is only guessed from its bit sizes, and this is only
+    /// taken into account with EGL.
+    #[inline]
+    pub fn with_packed_depth_stencil(mut self, packed: bool) -> Self {
+        self.pf_reqs.packed_depth_stencil = packed;
+        self
+    }
+
     /// Sets the number of bits in the color buffer.
     #[inline]
     pub fn with_pixel_format(mut self, color_bits: u8, alpha_bits: u8) -> Self {
         self.pf_reqs.color_bits = Some(color_bits);
+        self.pf_reqs.color_component_bits = None;
         self.pf_reqs.alpha_bits = Some(alpha_bits);
         self
     }
 
+    /// Sets the minimum number of bits of each channel of the color buffer,
+    /// e.g. `(10, 10, 10, 2)`, rather than only their total.
+    ///
+    /// With EGL and GLX, each channel is requested on its own. Other
+    /// backends only see the sum of `red`, `green` and `blue`, like with
+    /// [`with_pixel_format()`][Self::with_pixel_format()].
+    #[inline]
+    pub fn with_color_component_bits(mut self, red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        self.pf_reqs.color_component_bits = Some((red, green, blue));
+        self.pf_reqs.color_bits = Some(red.saturating_add(green).saturating_add(blue));
+        self.pf_reqs.alpha_bits = Some(alpha);
+        self
+    }
+
+    /// Sets whether the alpha size of the chosen format must match the one
+    /// requested with [`with_pixel_format()`][Self::with_pixel_format()]
+    /// exactly, rather than being a minimum.
+    ///
+    /// Use this with an `alpha_bits` of `0` to make sure an opaque window
+    /// doesn't end up with an alpha channel, which compositors would blend.
+    #[inline]
+    pub fn with_exact_alpha(mut self, exact_alpha: bool) -> Self {
+        self.pf_reqs.exact_alpha = exact_alpha;
+        self
+    }
+
+    /// Sets which GPU to prefer on systems with both an integrated and a
+    /// discrete one.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only macOS can pick the GPU per context: with
+    /// [`HighPerformance`][PowerPreference::HighPerformance] the context
+    /// keeps the discrete GPU powered, otherwise the system is free to stay on
+    /// the integrated one. On other platforms the GPU is chosen for the whole
+    /// process, e.g. through `DRI_PRIME` with Mesa or the driver's control
+    /// panel on Windows, and this is a no-op.
+    #[inline]
+    pub fn with_power_preference(mut self, preference: PowerPreference) -> Self {
+        self.pf_reqs.power_preference = Some(preference);
+        self
+    }
+
+    /// Sets the kind of color buffer to request.
+    ///
+    /// The default value is [`ColorBufferType::Rgb`]. When requesting
+    /// [`ColorBufferType::Luminance`], the color bits set with
+    /// [`with_pixel_format()`][Self::with_pixel_format()] are used as the size
+    /// of the luminance channel.
+    ///
+    /// ## Platform-specific
+    ///
+    /// [`ColorBufferType::Luminance`] is only supported with EGL. Other
+    /// backends fail with [`CreationError::NoAvailablePixelFormat`].
+    #[inline]
+    pub fn with_color_buffer_type(mut self, color_buffer_type: ColorBufferType) -> Self {
+        self.pf_reqs.color_buffer_type = color_buffer_type;
+        self
+    }
+
+    /// Requests a format using color-key transparency, where pixels of the
+    /// given red, green and blue values are transparent to the window system,
+    /// as found on legacy overlay hardware.
+    ///
+    /// This is unrelated to alpha blending with
+    /// [`WindowBuilder::with_transparent()`], and few drivers have such
+    /// formats.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only supported with EGL. Other backends fail with
+    /// [`CreationError::NoAvailablePixelFormat`].
+    ///
+    /// [`WindowBuilder::with_transparent()`]: crate::window::WindowBuilder::with_transparent()
+    #[inline]
+    pub fn with_color_key_transparency(mut self, rgb: (u32, u32, u32)) -> Self {
+        self.pf_reqs.color_key = Some(rgb);
+        self
+    }
+
     /// Request the backend to be stereoscopic.
+    ///
+    /// EGL has no stereo configs, so there this is only a request, honored
+    /// for windows through `EGL_EXT_multiview_window`. Check
+    /// [`PixelFormat::stereoscopy`] to know whether it was.
     #[inline]
     pub fn with_stereoscopy(mut self) -> Self {
         self.pf_reqs.stereoscopy = true;
@@ -223,10 +632,29 @@ impl<'a, T: ContextCurrentState> ContextBuilder<'a, T> {
 
     /// Sets whether sRGB should be enabled on the window.
     ///
+    /// With EGL, asking for sRGB here needs EGL 1.5 or
+    /// `EGL_KHR_gl_colorspace`, and creation fails with
+    /// [`CreationError::NotSupported`] without them. Without calling this,
+    /// EGL surfaces are only sRGB for desktop OpenGL, and only where the
+    /// display and config allow it, see [`PixelFormat::srgb`]. OpenGL ES
+    /// always encodes writes to an sRGB surface, so it has to be asked for.
+    ///
     /// The default value is [`true`].
     #[inline]
     pub fn with_srgb(mut self, srgb_enabled: bool) -> Self {
         self.pf_reqs.srgb = srgb_enabled;
+        self.pf_reqs.srgb_required = srgb_enabled;
+        self
+    }
+
+    /// Sets whether the format must, or must not, allow native rendering
+    /// APIs such as Xlib to draw to the same surface, e.g. when embedding
+    /// OpenGL into a natively drawn widget.
+    ///
+    /// This is only taken into account with EGL.
+    #[inline]
+    pub fn with_native_renderable(mut self, native_renderable: bool) -> Self {
+        self.pf_reqs.native_renderable = Some(native_renderable);
         self
     }
 
@@ -241,12 +669,39 @@ impl<'a, T: ContextCurrentState> ContextBuilder<'a, T> {
     ///   * MacOS
     ///   * Unix operating systems using GLX with X
     ///   * Windows using WGL
+    ///   * EGL, where only window surfaces can be single-buffered
     #[inline]
     pub fn with_double_buffer(mut self, double_buffer: Option<bool>) -> Self {
         self.pf_reqs.double_buffer = double_buffer;
         self
     }
 
+    /// Sets how swapping buffers moves the back buffer to the front, which
+    /// decides whether its contents are preserved afterwards.
+    ///
+    /// The default value is [`None`], letting the driver choose.
+    ///
+    /// ## Platform-specific
+    ///
+    /// This option will be taken into account on the following platforms:
+    ///
+    ///   * MacOS, where only [`SwapMethod::Copy`] makes a difference, by
+    ///     requesting a backing store
+    ///   * Unix operating systems using GLX with X, which fail with
+    ///     [`CreationError::NoAvailablePixelFormat`] without
+    ///     `GLX_OML_swap_method`
+    ///   * Windows using WGL
+    ///
+    /// With EGL, use [`set_swap_behavior_preserved()`] on the created context
+    /// instead.
+    ///
+    /// [`set_swap_behavior_preserved()`]: crate::ContextWrapper::set_swap_behavior_preserved()
+    #[inline]
+    pub fn with_swap_method(mut self, swap_method: Option<SwapMethod>) -> Self {
+        self.pf_reqs.swap_method = swap_method;
+        self
+    }
+
     /// Sets whether hardware acceleration is required.
     ///
     /// The default value is `Some(true)`
@@ -264,6 +719,65 @@ impl<'a, T: ContextCurrentState> ContextBuilder<'a, T> {
         self.pf_reqs.hardware_accelerated = acceleration;
         self
     }
+
+    /// Sets which config caveats are acceptable, in place of the mapping from
+    /// [`with_hardware_acceleration()`][Self::with_hardware_acceleration()].
+    ///
+    /// The default value is [`None`], which keeps that mapping: `Some(true)`
+    /// only accepts configs without a caveat and `Some(false)` only slow
+    /// ones. Among the accepted configs, those without a caveat still come
+    /// first.
+    ///
+    /// ## Platform-specific
+    ///
+    /// This is only taken into account with EGL, through
+    /// `EGL_CONFIG_CAVEAT`.
+    #[inline]
+    pub fn with_caveat_tolerance(mut self, tolerance: Option<CaveatTolerance>) -> Self {
+        self.pf_reqs.caveat_tolerance = tolerance;
+        self
+    }
+
+    /// Lets `confirm` decide whether to accept software rendering when
+    /// hardware acceleration is required but no accelerated pixel format is
+    /// available.
+    ///
+    /// `confirm` runs at most once, e.g. to ask the user whether to continue
+    /// with slow rendering. If it returns `true`, creating the context is
+    /// retried as if [`with_hardware_acceleration(None)`] had been used;
+    /// otherwise [`CreationError::NoAvailablePixelFormat`] is returned.
+    ///
+    /// This applies to [`build_windowed()`] and [`build_headless()`].
+    ///
+    /// [`with_hardware_acceleration(None)`]: Self::with_hardware_acceleration()
+    /// [`build_windowed()`]: Self::build_windowed()
+    /// [`build_headless()`]: Self::build_headless()
+    #[inline]
+    pub fn with_software_fallback_confirm(
+        mut self,
+        confirm: impl FnOnce() -> bool + Send + 'a,
+    ) -> Self {
+        self.software_fallback =
+            Some(SoftwareFallback(Arc::new(Mutex::new(Some(Box::new(confirm))))));
+        self
+    }
+
+    /// Lets `select` pick the pixel format out of all those matching the
+    /// requirements, by returning its index, e.g. to prefer the one with the
+    /// fewest alpha bits.
+    ///
+    /// The formats describe the configs alone, so surface-dependent fields
+    /// such as [`PixelFormat::srgb`] aren't filled in yet. Only EGL calls
+    /// `select`; other backends pick a format as usual. Returning an index
+    /// out of bounds fails with [`CreationError::PlatformSpecific`].
+    #[inline]
+    pub fn with_config_selector(
+        mut self,
+        select: impl FnMut(&[PixelFormat]) -> usize + Send + 'static,
+    ) -> Self {
+        self.pf_reqs.config_selector = Some(ConfigSelector(Arc::new(Mutex::new(Box::new(select)))));
+        self
+    }
 }
 
 /// Error that can happen while creating a window or a headless renderer.
@@ -279,6 +793,17 @@ pub enum CreationError {
     Window(OsError),
     /// We received multiple errors, instead of one.
     CreationErrors(Vec<Box<CreationError>>),
+    /// An EGL call failed.
+    EglError {
+        /// The name of the EGL function, e.g. `"eglCreateWindowSurface"`.
+        call: &'static str,
+        /// The code returned by `eglGetError()`, e.g. `0x3009` for
+        /// `EGL_BAD_MATCH`.
+        code: u32,
+    },
+    /// The context to share with can't share objects with the new one, e.g.
+    /// because it belongs to another EGL display.
+    SharingNotPossible(String),
 }
 
 impl CreationError {
@@ -306,7 +831,8 @@ impl std::fmt::Display for CreationError {
         f.write_str(match self {
             CreationError::OsError(text)
             | CreationError::NotSupported(text)
-            | CreationError::PlatformSpecific(text) => text,
+            | CreationError::PlatformSpecific(text)
+            | CreationError::SharingNotPossible(text) => text,
             CreationError::NoBackendAvailable(err) => {
                 return write!(f, "No backend is available: {}", err);
             }
@@ -329,10 +855,37 @@ impl std::fmt::Display for CreationError {
                 }
                 return Ok(());
             }
+            CreationError::EglError { call, code } => {
+                return match egl_error_name(*code) {
+                    Some(name) => write!(f, "{} failed with {}", call, name),
+                    None => write!(f, "{} failed with error 0x{:x}", call, code),
+                };
+            }
         })
     }
 }
 
+/// The name of the core EGL error `code`.
+fn egl_error_name(code: u32) -> Option<&'static str> {
+    Some(match code {
+        0x3001 => "EGL_NOT_INITIALIZED",
+        0x3002 => "EGL_BAD_ACCESS",
+        0x3003 => "EGL_BAD_ALLOC",
+        0x3004 => "EGL_BAD_ATTRIBUTE",
+        0x3005 => "EGL_BAD_CONFIG",
+        0x3006 => "EGL_BAD_CONTEXT",
+        0x3007 => "EGL_BAD_CURRENT_SURFACE",
+        0x3008 => "EGL_BAD_DISPLAY",
+        0x3009 => "EGL_BAD_MATCH",
+        0x300A => "EGL_BAD_NATIVE_PIXMAP",
+        0x300B => "EGL_BAD_NATIVE_WINDOW",
+        0x300C => "EGL_BAD_PARAMETER",
+        0x300D => "EGL_BAD_SURFACE",
+        0x300E => "EGL_CONTEXT_LOST",
+        _ => return None,
+    })
+}
+
 impl std::error::Error for CreationError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
@@ -416,6 +969,19 @@ pub enum GlRequest {
         /// The version to use for OpenGL ES.
         opengles_version: (u8, u8),
     },
+
+    /// If OpenGL ES is available, create an OpenGL ES [`Context`] with the
+    /// specified `opengles_version`. Else if OpenGL is available, create a
+    /// context with the specified `opengl_version`.
+    ///
+    /// On Windows and X11, EGL is tried before WGL and GLX. Contexts that end
+    /// up on GLX, WGL, CGL or OsMesa always use `opengl_version`.
+    GlesThenGl {
+        /// The version to use for OpenGL ES.
+        opengles_version: (u8, u8),
+        /// The version to use for OpenGL.
+        opengl_version: (u8, u8),
+    },
 }
 
 impl GlRequest {
@@ -424,6 +990,7 @@ impl GlRequest {
         match self {
             GlRequest::Specific(Api::OpenGl, opengl_version) => Some(opengl_version),
             GlRequest::GlThenGles { opengl_version, .. } => Some(opengl_version),
+            GlRequest::GlesThenGl { opengl_version, .. } => Some(opengl_version),
             _ => None,
         }
     }
@@ -463,8 +1030,9 @@ pub enum Robustness {
 
     /// Everything is checked to avoid any crash. If a problem occurs, the
     /// context will enter a "context lost" state. It must then be
-    /// recreated. For the moment, glutin doesn't provide a way to recreate
-    /// a context with the same window :-/
+    /// recreated, which [`WindowedContext::recreate()`] does in place for
+    /// EGL windows, or [`WindowedContext::recreate_surface()`] along with
+    /// the window surface.
     RobustLoseContextOnReset,
 
     /// Same as [`RobustLoseContextOnReset`][Self::RobustLoseContextOnReset]
@@ -472,6 +1040,84 @@ pub enum Robustness {
     TryRobustLoseContextOnReset,
 }
 
+/// The graphics reset status of a context, see [`Context::reset_status()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ResetStatus {
+    /// No reset happened since the status was last read.
+    NoError,
+    /// A reset was caused by this context.
+    GuiltyContextReset,
+    /// A reset was caused by another context.
+    InnocentContextReset,
+    /// A reset happened, but its cause is unknown.
+    UnknownContextReset,
+}
+
+/// When a frame went through the stages of presentation, see
+/// [`ContextWrapper::frame_timestamps()`].
+///
+/// The times are in nanoseconds on the `CLOCK_MONOTONIC` clock. [`None`] means
+/// the stage hasn't happened yet, or that the time isn't available.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FrameTimestamps {
+    /// The presentation time requested with
+    /// [`ContextWrapper::set_presentation_time()`].
+    pub requested_present_time: Option<i64>,
+    /// When the GPU finished rendering the frame.
+    pub rendering_complete_time: Option<i64>,
+    /// When the compositor latched the frame for composition.
+    pub composition_latch_time: Option<i64>,
+    /// When the frame started being shown on the display.
+    pub display_present_time: Option<i64>,
+}
+
+/// A requirement [`ContextBuilder::build_windowed_relaxed()`] dropped to find
+/// a pixel format, in the order it drops them.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RelaxedRequirement {
+    /// Multisampling was turned off.
+    Multisampling,
+    /// Any number of stencil bits was accepted, including none.
+    Stencil,
+    /// Any number of alpha bits was accepted, including none.
+    Alpha,
+    /// Any depth buffer size was accepted, including none.
+    Depth,
+}
+
+impl RelaxedRequirement {
+    /// Drops the requirement from `pf_reqs`, returning `false` if there was
+    /// nothing to drop.
+    pub(crate) fn relax(self, pf_reqs: &mut PixelFormatRequirements) -> bool {
+        let bits = match self {
+            RelaxedRequirement::Multisampling => {
+                pf_reqs.multisampling_required = false;
+                return pf_reqs.multisampling.take().unwrap_or(0) > 0;
+            }
+            RelaxedRequirement::Stencil => &mut pf_reqs.stencil_bits,
+            RelaxedRequirement::Alpha => {
+                pf_reqs.exact_alpha = false;
+                &mut pf_reqs.alpha_bits
+            }
+            RelaxedRequirement::Depth => &mut pf_reqs.depth_bits,
+        };
+        bits.take().unwrap_or(0) > 0
+    }
+}
+
+/// The scheduling priority of a context on the GPU, see
+/// [`ContextBuilder::with_context_priority()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Priority {
+    /// Below the default priority. The driver may ignore the request.
+    Low,
+    /// The default priority of a context.
+    Medium,
+    /// Above the default priority. The driver may downgrade the request, e.g.
+    /// when the process lacks the privileges for it.
+    High,
+}
+
 /// The behavior of the driver when you change the current context.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ReleaseBehavior {
@@ -483,6 +1129,95 @@ pub enum ReleaseBehavior {
     Flush,
 }
 
+/// The kind of color buffer a pixel format has.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ColorBufferType {
+    /// Separate red, green and blue channels.
+    Rgb,
+
+    /// A single luminance channel, as found on some grayscale displays.
+    ///
+    /// Only supported with EGL.
+    Luminance,
+}
+
+/// What happens to the back buffer on a swap, as reported by
+/// [`ContextWrapper::swap_behavior()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SwapBehavior {
+    /// The contents of the back buffer are kept.
+    Preserved,
+
+    /// The contents of the back buffer are undefined after a swap.
+    Destroyed,
+}
+
+/// How a multisampled surface is resolved on a swap, as reported by
+/// [`ContextWrapper::multisample_resolve()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MultisampleResolve {
+    /// The implementation's default filter.
+    Default,
+
+    /// A one-pixel wide box filter, weighting every sample equally.
+    Box,
+}
+
+/// Which config caveats to accept, see
+/// [`ContextBuilder::with_caveat_tolerance()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CaveatTolerance {
+    /// Only configs without a caveat.
+    NoneOnly,
+
+    /// Configs without a caveat, or marked as slow, e.g. software rendered.
+    AllowSlow,
+
+    /// Configs without a caveat, or that don't pass the conformance tests,
+    /// which are sometimes faster.
+    AllowNonConformant,
+
+    /// Any config.
+    AllowAny,
+}
+
+/// How swapping buffers presents the back buffer, see
+/// [`ContextBuilder::with_swap_method()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SwapMethod {
+    /// The front and back buffers are exchanged, leaving the back buffer with
+    /// the contents of the previous frame.
+    Exchange,
+
+    /// The back buffer is copied to the front buffer and keeps its contents.
+    Copy,
+
+    /// The contents of the back buffer are undefined after a swap.
+    Undefined,
+}
+
+/// How a pixel format marks pixels as transparent to the window system.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TransparentType {
+    /// No pixel is transparent.
+    None,
+
+    /// Pixels matching [`PixelFormat::transparent_color`] are transparent.
+    ///
+    /// Only reported by EGL.
+    Rgb,
+}
+
+/// Which GPU to prefer on systems with more than one, such as laptops with
+/// hybrid graphics.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PowerPreference {
+    /// Prefer the integrated GPU to save power.
+    LowPower,
+    /// Prefer the discrete GPU.
+    HighPerformance,
+}
+
 /// Describes a possible format.
 #[allow(missing_docs)]
 #[derive(Debug, Clone)]
@@ -492,17 +1227,95 @@ pub struct PixelFormat {
     pub color_bits: u8,
     pub alpha_bits: u8,
     pub depth_bits: u8,
+    /// Whether the format is conformant with a client API that supports
+    /// `GL_DEPTH_COMPONENT32F` renderbuffers, i.e. OpenGL ES 3.0 or desktop
+    /// OpenGL.
+    ///
+    /// This is a capability of the API, not of the format's own depth buffer,
+    /// which is described by `depth_bits`. Only EGL reports it, through
+    /// `EGL_CONFORMANT`. With other backends this is always `false`, and the
+    /// context version has to be checked instead.
+    pub float_depth_attachments: bool,
     pub stencil_bits: u8,
+    /// Whether depth and stencil likely share a packed `DEPTH24_STENCIL8`
+    /// buffer.
+    ///
+    /// This is a heuristic: EGL configs don't say how the buffers are stored,
+    /// so it's `true` for any config with a 24-bit depth buffer and 8 stencil
+    /// bits, which the driver may still keep apart. It's always `false` with
+    /// other backends, meaning "unknown" rather than "separate".
+    pub packed_depth_stencil: bool,
     pub stereoscopy: bool,
     pub double_buffer: bool,
     /// [`None`] if multisampling is disabled, otherwise `Some(N)` where `N` is
     /// the multisampling level.
     pub multisampling: Option<u16>,
     pub srgb: bool,
+    /// Whether native rendering APIs, such as Xlib on X11, can draw to
+    /// surfaces of this format too. Only EGL reports this, through
+    /// `EGL_NATIVE_RENDERABLE`; it is `false` with other backends.
+    pub native_renderable: bool,
+    /// Whether pbuffers of this format can be bound to a texture with
+    /// `eglBindTexImage`, through `EGL_BIND_TO_TEXTURE_RGB` or
+    /// `EGL_BIND_TO_TEXTURE_RGBA`. Only EGL reports this; it is `false` with
+    /// other backends.
+    pub bindable_to_texture: bool,
+    /// With [`ColorBufferType::Luminance`], [`color_bits`][Self::color_bits]
+    /// is the size of the luminance channel.
+    pub color_buffer_type: ColorBufferType,
+    /// Whether this format uses color-key transparency, see
+    /// [`ContextBuilder::with_color_key_transparency()`].
+    pub transparent_type: TransparentType,
+    /// The red, green and blue values of the transparent color. Only
+    /// meaningful with [`TransparentType::Rgb`], `(0, 0, 0)` otherwise.
+    pub transparent_color: (u32, u32, u32),
+}
+
+/// The platform interface a [`Context`] was created through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// EGL, on any platform.
+    Egl,
+    /// GLX on X11.
+    Glx,
+    /// WGL on Windows.
+    Wgl,
+    /// OSMesa off-screen rendering.
+    OsMesa,
+    /// `NSOpenGLContext` on macOS.
+    Cgl,
+    /// `EAGLContext` on iOS.
+    Eagl,
+}
+
+/// Describes how a [`Context`] was actually created, as opposed to what was
+/// requested from the [`ContextBuilder`].
+///
+/// Fields that the backend doesn't keep track of are [`None`].
+#[derive(Debug, Clone)]
+pub struct CreationReport {
+    /// The platform interface the context was created through.
+    pub backend: Backend,
+    /// The version of the platform interface, e.g. `(1, 5)` for EGL 1.5.
+    pub backend_version: Option<(u8, u8)>,
+    /// The client API of the context.
+    pub api: Api,
+    /// The GL version that was passed to the platform interface. When
+    /// falling back through several versions, this is the one that
+    /// succeeded.
+    pub version: Option<(u8, u8)>,
+    /// The robustness that was applied. `Try*` variants are resolved to
+    /// either their strict counterpart or [`Robustness::NotRobust`].
+    pub robustness: Option<Robustness>,
+    /// Whether a debug context was requested from the platform interface.
+    pub debug: Option<bool>,
+    /// The extensions of the platform interface (EGL, GLX, ...).
+    pub extensions: Vec<String>,
+    /// The pixel format that was chosen for the context.
+    pub pixel_format: PixelFormat,
 }
 
 /// Describes how the backend should choose a pixel format.
-// TODO: swap method? (swap, copy)
 #[derive(Clone, Debug)]
 pub struct PixelFormatRequirements {
     /// If true, only hardware-accelerated formats will be considered. If
@@ -510,10 +1323,25 @@ pub struct PixelFormatRequirements {
     /// is `Some(true)`.
     pub hardware_accelerated: Option<bool>,
 
+    /// Which config caveats are acceptable. If set, this replaces
+    /// [`hardware_accelerated`][Self::hardware_accelerated] on EGL. The
+    /// default is [`None`].
+    pub caveat_tolerance: Option<CaveatTolerance>,
+
     /// Minimum number of bits for the color buffer, excluding alpha. [`None`]
     /// means "don't care". The default is `Some(24)`.
     pub color_bits: Option<u8>,
 
+    /// Minimum number of bits of the red, green and blue channels. If set,
+    /// this replaces [`color_bits`][Self::color_bits] where the backend can
+    /// ask for each channel. The default is [`None`].
+    pub color_component_bits: Option<(u8, u8, u8)>,
+
+    /// The kind of color buffer. With [`ColorBufferType::Luminance`],
+    /// [`color_bits`][Self::color_bits] is the minimum size of the luminance
+    /// channel. The default is [`ColorBufferType::Rgb`].
+    pub color_buffer_type: ColorBufferType,
+
     /// If true, the color buffer must be in a floating point format. Default
     /// is [`false`].
     ///
@@ -521,39 +1349,95 @@ pub struct PixelFormatRequirements {
     /// 1.0]` range.
     pub float_color_buffer: bool,
 
+    /// If true, only formats that can be bound to a texture are considered,
+    /// see [`PixelFormat::bindable_to_texture`]. Only EGL has such formats,
+    /// so other backends find none. The default is [`false`].
+    pub bindable_to_texture: bool,
+
     /// Minimum number of bits for the alpha in the color buffer. [`None`] means
     /// "don't care". The default is `Some(8)`.
     pub alpha_bits: Option<u8>,
 
+    /// If true, [`alpha_bits`] is the exact number of alpha bits rather than
+    /// a minimum. The default is [`false`].
+    ///
+    /// [`alpha_bits`]: Self::alpha_bits
+    pub exact_alpha: bool,
+
     /// Minimum number of bits for the depth buffer. [`None`] means "don't care".
     /// The default value is `Some(24)`.
     pub depth_bits: Option<u8>,
 
+    /// If true, formats conformant with an API supporting float depth
+    /// renderbuffers are preferred, see
+    /// [`PixelFormat::float_depth_attachments`]. The default is [`false`].
+    pub float_depth_attachments: bool,
+
     /// Minimum number of stencil bits. [`None`] means "don't care".
     /// The default value is `Some(8)`.
     pub stencil_bits: Option<u8>,
 
+    /// If true, formats with a packed `DEPTH24_STENCIL8` depth-stencil buffer
+    /// are preferred, see [`PixelFormat::packed_depth_stencil`]. The default
+    /// is [`false`].
+    pub packed_depth_stencil: bool,
+
     /// If true, only double-buffered formats will be considered. If false,
     /// only single-buffer formats. [`None`] means "don't care". The default
     /// is `Some(true)`.
     pub double_buffer: Option<bool>,
 
+    /// How swapping buffers presents the back buffer. [`None`] means "don't
+    /// care". The default is [`None`].
+    pub swap_method: Option<SwapMethod>,
+
     /// Contains the minimum number of samples per pixel in the color, depth
     /// and stencil buffers. [`None`] means "don't care". Default is [`None`].
     /// A value of `Some(0)` indicates that multisampling must not be enabled.
     pub multisampling: Option<u16>,
 
+    /// If true, formats with fewer samples than [`multisampling`] are
+    /// rejected, even if the platform would otherwise pick one. The default
+    /// is [`false`].
+    ///
+    /// [`multisampling`]: Self::multisampling
+    pub multisampling_required: bool,
+
     /// If true, only stereoscopic formats will be considered. If false, only
     /// non-stereoscopic formats. The default is [`false`].
     pub stereoscopy: bool,
 
     /// If true, only sRGB-capable formats will be considered. If false, don't
-    /// care. The default is [`true`].
+    /// care. With EGL, this is best-effort unless set through
+    /// [`ContextBuilder::with_srgb()`], and OpenGL ES surfaces are only sRGB
+    /// if it was. The default is [`true`].
     pub srgb: bool,
 
+    /// If `Some(true)`, only formats that native rendering APIs can draw to
+    /// as well will be considered, see [`PixelFormat::native_renderable`].
+    /// [`None`] means "don't care". The default is [`None`].
+    pub native_renderable: Option<bool>,
+
+    /// If set, only formats with color-key transparency using this red, green
+    /// and blue key are considered, see [`TransparentType::Rgb`]. The default
+    /// is [`None`], which only considers formats without it.
+    pub color_key: Option<(u32, u32, u32)>,
+
     /// The behavior when changing the current context. Default is `Flush`.
     pub release_behavior: ReleaseBehavior,
 
+    /// Which GPU to prefer. [`None`] leaves the choice to the system. The
+    /// default is [`None`].
+    pub power_preference: Option<PowerPreference>,
+
+    /// Picks the format among the matching ones, see
+    /// [`ContextBuilder::with_config_selector()`]. The default is [`None`].
+    pub config_selector: Option<ConfigSelector>,
+
+    /// Set by [`ContextBuilder::with_srgb()`], so that EGL fails without sRGB
+    /// support instead of falling back to a linear surface.
+    pub(crate) srgb_required: bool,
+
     /// X11 only: set internally to ensure a certain visual xid is used when
     /// choosing the fbconfig.
     #[allow(dead_code)]
@@ -565,21 +1449,157 @@ impl Default for PixelFormatRequirements {
     fn default() -> PixelFormatRequirements {
         PixelFormatRequirements {
             hardware_accelerated: Some(true),
+            caveat_tolerance: None,
             color_bits: Some(24),
+            color_component_bits: None,
+            color_buffer_type: ColorBufferType::Rgb,
             float_color_buffer: false,
+            bindable_to_texture: false,
             alpha_bits: Some(8),
+            exact_alpha: false,
             depth_bits: Some(24),
+            float_depth_attachments: false,
             stencil_bits: Some(8),
+            packed_depth_stencil: false,
             double_buffer: None,
+            swap_method: None,
             multisampling: None,
+            multisampling_required: false,
             stereoscopy: false,
             srgb: true,
+            native_renderable: None,
+            color_key: None,
             release_behavior: ReleaseBehavior::Flush,
+            power_preference: None,
+            config_selector: None,
+            srgb_required: false,
             x11_visual_xid: None,
         }
     }
 }
 
+impl PixelFormatRequirements {
+    /// Requirements for 2D rendering, which needs neither a depth nor a
+    /// stencil buffer, nor multisampling.
+    ///
+    /// Like the other presets, this is a starting point to tweak:
+    ///
+    /// ```no_run
+    /// # fn main() {
+    /// let el = glutin::event_loop::EventLoop::new();
+    /// let wb = glutin::window::WindowBuilder::new();
+    /// let mut cb = glutin::ContextBuilder::new();
+    /// cb.pf_reqs = glutin::PixelFormatRequirements::gl_2d();
+    /// cb.pf_reqs.alpha_bits = None;
+    /// let windowed_context = cb.build_windowed(wb, &el).unwrap();
+    /// # }
+    /// ```
+    pub fn gl_2d() -> Self {
+        PixelFormatRequirements {
+            depth_bits: None,
+            stencil_bits: None,
+            multisampling: Some(0),
+            ..Default::default()
+        }
+    }
+
+    /// Requirements for 3D rendering, with a 24-bit depth buffer and 8
+    /// stencil bits.
+    ///
+    /// ```no_run
+    /// # fn main() {
+    /// let el = glutin::event_loop::EventLoop::new();
+    /// let wb = glutin::window::WindowBuilder::new();
+    /// let mut cb = glutin::ContextBuilder::new();
+    /// cb.pf_reqs = glutin::PixelFormatRequirements::gl_3d();
+    /// let windowed_context = cb.with_multisampling(4).build_windowed(wb, &el).unwrap();
+    /// # }
+    /// ```
+    pub fn gl_3d() -> Self {
+        PixelFormatRequirements {
+            depth_bits: Some(24),
+            stencil_bits: Some(8),
+            ..Default::default()
+        }
+    }
+
+    /// Requirements for HDR rendering to a pbuffer or a surfaceless context,
+    /// with a floating point color buffer of at least 16 bits per channel.
+    ///
+    /// sRGB is turned off, as float color buffers hold linear values.
+    ///
+    /// ```no_run
+    /// # fn main() {
+    /// let el = glutin::event_loop::EventLoop::new();
+    /// let size = glutin::dpi::PhysicalSize::new(1920, 1080);
+    /// let mut cb = glutin::ContextBuilder::new();
+    /// cb.pf_reqs = glutin::PixelFormatRequirements::offscreen_hdr();
+    /// let headless_context = cb.build_headless(&el, size).unwrap();
+    /// # }
+    /// ```
+    pub fn offscreen_hdr() -> Self {
+        PixelFormatRequirements {
+            float_color_buffer: true,
+            color_component_bits: Some((16, 16, 16)),
+            color_bits: Some(48),
+            alpha_bits: Some(16),
+            srgb: false,
+            ..Default::default()
+        }
+    }
+
+    /// Returns `false` if multisampling is required and a format with
+    /// `samples` samples doesn't have enough of them.
+    pub(crate) fn multisampling_satisfied_by(&self, samples: Option<u16>) -> bool {
+        match self.multisampling {
+            Some(requested) if self.multisampling_required => samples.unwrap_or(0) >= requested,
+            _ => true,
+        }
+    }
+
+    /// Returns `false` if an exact alpha size was requested and a format with
+    /// `alpha_bits` alpha bits doesn't match it.
+    pub(crate) fn alpha_satisfied_by(&self, alpha_bits: u8) -> bool {
+        match self.alpha_bits {
+            Some(requested) if self.exact_alpha => alpha_bits == requested,
+            _ => true,
+        }
+    }
+}
+
+/// How finished frames are queued for display, named after the present modes
+/// of Vulkan and wgpu.
+///
+/// OpenGL only knows about swap intervals, so each mode maps to a
+/// [`VSyncMode`], see [`PresentMode::vsync_mode()`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PresentMode {
+    /// Frames are shown in order, one per refresh. Swapping blocks while the
+    /// queue is full.
+    Fifo,
+    /// Like [`Fifo`][Self::Fifo], but a frame that misses a refresh is shown
+    /// right away, possibly tearing.
+    FifoRelaxed,
+    /// Swapping never blocks and the latest finished frame is shown on the
+    /// next refresh, without tearing. Only reported as supported where the
+    /// compositor already works this way.
+    Mailbox,
+    /// Swapping never blocks and frames are shown right away, possibly
+    /// tearing.
+    Immediate,
+}
+
+impl PresentMode {
+    /// The swap interval this mode is implemented with.
+    pub fn vsync_mode(&self) -> VSyncMode {
+        match self {
+            PresentMode::Fifo => VSyncMode::On,
+            PresentMode::FifoRelaxed => VSyncMode::Adaptive,
+            PresentMode::Mailbox | PresentMode::Immediate => VSyncMode::Off,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum VSyncMode {
     Adaptive,
@@ -637,6 +1657,118 @@ pub struct GlAttributes<S> {
     ///
     /// The default is [`VSyncMode::Off`].
     pub vsync: VSyncMode,
+
+    /// The modes to try in order in place of [`vsync`][Self::vsync], see
+    /// [`ContextBuilder::with_swap_interval_fallback()`]. Empty if only
+    /// `vsync` should be used.
+    ///
+    /// The default is empty.
+    pub vsync_fallback: Vec<VSyncMode>,
+
+    /// Whether to use the smallest swap interval the driver allows, in place
+    /// of [`vsync`][Self::vsync]. See
+    /// [`ContextBuilder::with_lowest_latency()`].
+    ///
+    /// The default is `false`.
+    pub lowest_latency: bool,
+
+    /// Whether video memory purges should be reported as context resets,
+    /// see [`ContextBuilder::with_memory_purge_handling()`].
+    ///
+    /// The default is `false`.
+    pub memory_purge_handling: bool,
+
+    /// A context created outside of glutin to share OpenGL objects with,
+    /// in place of [`sharing`][Self::sharing].
+    ///
+    /// The default is [`None`].
+    pub external_share: Option<ShareToken>,
+
+    /// Whether the context may only be made current or not current on the
+    /// thread that created it, see [`ContextBuilder::with_thread_affinity()`].
+    ///
+    /// The default is `false`.
+    pub thread_affinity: bool,
+
+    /// Whether to call `glFinish` when dropping the context, see
+    /// [`ContextBuilder::with_finish_on_drop()`].
+    ///
+    /// The default is `true`.
+    pub finish_on_drop: bool,
+
+    /// Whether to terminate the EGL display once the last context on it is
+    /// gone, see [`ContextBuilder::with_egl_display_termination()`].
+    ///
+    /// The default is `false`.
+    pub terminate_display: bool,
+
+    /// Whether to create the context without tying it to a config, see
+    /// [`ContextBuilder::with_no_config_context()`].
+    ///
+    /// The default is `false`.
+    pub no_config_context: bool,
+
+    /// The GPU scheduling priority to ask for, see
+    /// [`ContextBuilder::with_context_priority()`].
+    ///
+    /// The default is [`None`], leaving it to the driver.
+    pub priority: Option<Priority>,
+
+    /// Attributes appended to those glutin passes to `eglCreateContext`, see
+    /// [`ContextBuilder::with_raw_context_attributes()`].
+    ///
+    /// The default is empty.
+    pub raw_context_attributes: Vec<(i32, i32)>,
+}
+
+/// An EGL context together with its display and config, used to share OpenGL
+/// objects across libraries.
+///
+/// Get one for a glutin context with [`Context::share_token()`] and hand its
+/// handles to another library, or wrap another library's context with
+/// [`ShareToken::from_raw()`] and pass it to
+/// [`ContextBuilder::with_external_share()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShareToken {
+    display: *const std::os::raw::c_void,
+    config: *const std::os::raw::c_void,
+    context: *const std::os::raw::c_void,
+}
+
+// EGL handles can be used from any thread.
+unsafe impl Send for ShareToken {}
+unsafe impl Sync for ShareToken {}
+
+impl ShareToken {
+    /// Wraps the `EGLDisplay`, `EGLConfig` and `EGLContext` of a context that
+    /// glutin didn't create.
+    ///
+    /// # Safety
+    ///
+    /// The handles must be valid and stay alive for as long as contexts are
+    /// being created from this token.
+    pub unsafe fn from_raw(
+        display: *const std::os::raw::c_void,
+        config: *const std::os::raw::c_void,
+        context: *const std::os::raw::c_void,
+    ) -> Self {
+        ShareToken { display, config, context }
+    }
+
+    /// The `EGLDisplay` the context belongs to.
+    pub fn display(&self) -> *const std::os::raw::c_void {
+        self.display
+    }
+
+    /// The `EGLConfig` the context was created with.
+    pub fn config(&self) -> *const std::os::raw::c_void {
+        self.config
+    }
+
+    /// The `EGLContext` itself.
+    pub fn context(&self) -> *const std::os::raw::c_void {
+        self.context
+    }
 }
 
 impl<S> GlAttributes<S> {
@@ -653,6 +1785,16 @@ impl<S> GlAttributes<S> {
             debug: self.debug,
             robustness: self.robustness,
             vsync: self.vsync,
+            vsync_fallback: self.vsync_fallback,
+            lowest_latency: self.lowest_latency,
+            memory_purge_handling: self.memory_purge_handling,
+            external_share: self.external_share,
+            thread_affinity: self.thread_affinity,
+            finish_on_drop: self.finish_on_drop,
+            terminate_display: self.terminate_display,
+            no_config_context: self.no_config_context,
+            priority: self.priority,
+            raw_context_attributes: self.raw_context_attributes,
         }
     }
 
@@ -666,7 +1808,41 @@ impl<S> GlAttributes<S> {
             debug: self.debug,
             robustness: self.robustness,
             vsync: self.vsync,
+            vsync_fallback: self.vsync_fallback,
+            lowest_latency: self.lowest_latency,
+            memory_purge_handling: self.memory_purge_handling,
+            external_share: self.external_share,
+            thread_affinity: self.thread_affinity,
+            finish_on_drop: self.finish_on_drop,
+            terminate_display: self.terminate_display,
+            no_config_context: self.no_config_context,
+            priority: self.priority,
+            raw_context_attributes: self.raw_context_attributes,
+        }
+    }
+
+    /// Fails if [`external_share`][Self::external_share] is set, for backends
+    /// other than EGL.
+    #[allow(dead_code)]
+    pub(crate) fn check_no_external_share(&self) -> Result<(), CreationError> {
+        match self.external_share {
+            Some(_) => Err(CreationError::NotSupported(
+                "sharing with an external context requires EGL".to_string(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Fails if [`no_config_context`][Self::no_config_context] is set, for
+    /// backends other than EGL.
+    #[allow(dead_code)]
+    pub(crate) fn check_no_config_context(&self) -> Result<(), CreationError> {
+        if self.no_config_context {
+            return Err(CreationError::NotSupported(
+                "creating a context without a config requires EGL".to_string(),
+            ));
         }
+        Ok(())
     }
 }
 
@@ -680,11 +1856,22 @@ impl<S> Default for GlAttributes<S> {
             debug: cfg!(debug_assertions),
             robustness: Robustness::NotRobust,
             vsync: VSyncMode::Off,
+            vsync_fallback: Vec::new(),
+            lowest_latency: false,
+            memory_purge_handling: false,
+            external_share: None,
+            thread_affinity: false,
+            finish_on_drop: true,
+            terminate_display: false,
+            no_config_context: false,
+            priority: None,
+            raw_context_attributes: Vec::new(),
         }
     }
 }
 
-// Rectangles to submit as buffer damage.
+/// A rectangle to submit as buffer damage, see
+/// [`ContextWrapper::swap_buffers_with_damage()`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Rect {
     pub x: u32,
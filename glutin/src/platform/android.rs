@@ -1,7 +1,8 @@
 #![cfg(target_os = "android")]
 
 use crate::platform::ContextTraitExt;
-use crate::{Context, ContextCurrentState};
+pub use crate::platform_impl::HeadlessContextExt;
+use crate::{Context, ContextCurrentState, ContextError};
 pub use glutin_egl_sys::EGLContext;
 
 pub use winit::platform::android::*;
@@ -20,4 +21,24 @@ impl<T: ContextCurrentState> ContextTraitExt for Context<T> {
     unsafe fn get_egl_display(&self) -> Option<*const raw::c_void> {
         Some(self.context.get_egl_display())
     }
+
+    #[inline]
+    fn current_draw_surface(&self) -> Option<*const raw::c_void> {
+        self.context.current_surface(false)
+    }
+
+    #[inline]
+    fn current_read_surface(&self) -> Option<*const raw::c_void> {
+        self.context.current_surface(true)
+    }
+
+    #[inline]
+    unsafe fn raw_surface(&self) -> Option<*const raw::c_void> {
+        self.context.raw_surface()
+    }
+
+    #[inline]
+    unsafe fn replace_surface(&self, new_surface: *const raw::c_void) -> Result<(), ContextError> {
+        self.context.replace_surface(new_surface)
+    }
 }
@@ -1,7 +1,7 @@
 #![cfg(target_os = "macos")]
 
 use crate::platform::ContextTraitExt;
-use crate::{Context, ContextCurrentState};
+use crate::{Context, ContextCurrentState, ContextError};
 
 pub use winit::platform::macos::*;
 
@@ -19,4 +19,24 @@ impl<T: ContextCurrentState> ContextTraitExt for Context<T> {
     unsafe fn get_egl_display(&self) -> Option<*const raw::c_void> {
         None
     }
+
+    #[inline]
+    fn current_draw_surface(&self) -> Option<*const raw::c_void> {
+        None
+    }
+
+    #[inline]
+    fn current_read_surface(&self) -> Option<*const raw::c_void> {
+        None
+    }
+
+    #[inline]
+    unsafe fn raw_surface(&self) -> Option<*const raw::c_void> {
+        None
+    }
+
+    #[inline]
+    unsafe fn replace_surface(&self, _: *const raw::c_void) -> Result<(), ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
 }
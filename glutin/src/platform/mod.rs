@@ -34,6 +34,29 @@ pub mod run_return {
     pub use winit::platform::run_return::*;
 }
 
+use crate::ContextError;
+
+#[cfg(any(
+    target_os = "windows",
+    target_os = "linux",
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+pub use crate::api::egl::NativeDisplay;
+#[cfg(any(
+    target_os = "windows",
+    target_os = "linux",
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+pub use glutin_egl_sys::EGLNativeWindowType;
+
 use std::os::raw;
 
 /// Platform-specific extensions for OpenGL [`Context`][crate::Context]s.
@@ -50,4 +73,45 @@ pub trait ContextTraitExt {
     /// Return [`None`] if the context doesn't use EGL.
     // The pointer will become invalid when the context is destroyed.
     unsafe fn get_egl_display(&self) -> Option<*const raw::c_void>;
+
+    /// Returns the `EGLSurface` bound for drawing on the calling thread, as
+    /// reported by `eglGetCurrentSurface(EGL_DRAW)`.
+    ///
+    /// Returns [`None`] if this context isn't current on the calling thread,
+    /// is current without a draw surface, or doesn't use EGL.
+    fn current_draw_surface(&self) -> Option<*const raw::c_void>;
+
+    /// Like [`current_draw_surface()`][Self::current_draw_surface()], but for
+    /// the surface bound for reading.
+    fn current_read_surface(&self) -> Option<*const raw::c_void>;
+
+    /// Returns the `EGLSurface` this context renders to, whether or not the
+    /// context is current, e.g. to query its attributes from another library.
+    ///
+    /// Returns [`None`] for surfaceless contexts and contexts that don't use
+    /// EGL.
+    // The surface is destroyed along with the context, and replaced by
+    // `WindowedContext::recreate()` and `Context::resize_pbuffer()`.
+    unsafe fn raw_surface(&self) -> Option<*const raw::c_void>;
+
+    /// Replaces the `EGLSurface` this context renders to without recreating
+    /// the context, e.g. after creating a new `wl_egl_window` on Wayland.
+    ///
+    /// If the context is current on the calling thread, it's made not current,
+    /// the old surface is destroyed, and the context is made current again on
+    /// `new_surface`.
+    ///
+    /// Returns [`ContextError::FunctionUnavailable`] for surfaceless contexts
+    /// and contexts that don't use EGL. Afterwards,
+    /// [`WindowedContext::recreate_surface()`][crate::WindowedContext::recreate_surface()]
+    /// and [`Context::resize_pbuffer()`][crate::Context::resize_pbuffer()]
+    /// fail, as glutin no longer knows where the surface came from.
+    ///
+    /// # Safety
+    ///
+    /// `new_surface` must have been created on this context's EGL display
+    /// from a config compatible with the context's. Ownership of it passes to
+    /// glutin: it's destroyed along with the context or by the next
+    /// replacement, and must not be destroyed by the caller.
+    unsafe fn replace_surface(&self, new_surface: *const raw::c_void) -> Result<(), ContextError>;
 }
@@ -8,7 +8,7 @@
 
 use crate::platform::ContextTraitExt;
 pub use crate::platform_impl::{HeadlessContextExt, RawContextExt, RawHandle};
-use crate::{Context, ContextCurrentState};
+use crate::{Context, ContextCurrentState, ContextError};
 pub use glutin_egl_sys::EGLContext;
 #[cfg(feature = "x11")]
 pub use glutin_glx_sys::GLXContext;
@@ -17,6 +17,15 @@ pub use winit::platform::unix::*;
 
 use std::os::raw;
 
+/// Helpers for driving EGL devices directly, e.g. for headless multi-monitor
+/// setups on KMS.
+pub mod egl {
+    pub use crate::api::egl::{
+        create_output_stream, enumerate_outputs, query_egl_devices, EglDevice, EglStream, Output,
+        OutputKind,
+    };
+}
+
 impl<T: ContextCurrentState> ContextTraitExt for Context<T> {
     type Handle = RawHandle;
 
@@ -29,4 +38,24 @@ impl<T: ContextCurrentState> ContextTraitExt for Context<T> {
     unsafe fn get_egl_display(&self) -> Option<*const raw::c_void> {
         self.context.get_egl_display()
     }
+
+    #[inline]
+    fn current_draw_surface(&self) -> Option<*const raw::c_void> {
+        self.context.current_surface(false)
+    }
+
+    #[inline]
+    fn current_read_surface(&self) -> Option<*const raw::c_void> {
+        self.context.current_surface(true)
+    }
+
+    #[inline]
+    unsafe fn raw_surface(&self) -> Option<*const raw::c_void> {
+        self.context.raw_surface()
+    }
+
+    #[inline]
+    unsafe fn replace_surface(&self, new_surface: *const raw::c_void) -> Result<(), ContextError> {
+        self.context.replace_surface(new_surface)
+    }
 }
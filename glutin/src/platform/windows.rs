@@ -1,8 +1,8 @@
 #![cfg(target_os = "windows")]
 
 use crate::platform::ContextTraitExt;
-pub use crate::platform_impl::{RawContextExt, RawHandle};
-use crate::{Context, ContextCurrentState};
+pub use crate::platform_impl::{HeadlessContextExt, RawContextExt, RawHandle};
+use crate::{Context, ContextCurrentState, ContextError};
 pub use glutin_egl_sys::EGLContext;
 
 pub use winapi::shared::windef::HGLRC;
@@ -22,4 +22,24 @@ impl<T: ContextCurrentState> ContextTraitExt for Context<T> {
     unsafe fn get_egl_display(&self) -> Option<*const raw::c_void> {
         self.context.get_egl_display()
     }
+
+    #[inline]
+    fn current_draw_surface(&self) -> Option<*const raw::c_void> {
+        self.context.current_surface(false)
+    }
+
+    #[inline]
+    fn current_read_surface(&self) -> Option<*const raw::c_void> {
+        self.context.current_surface(true)
+    }
+
+    #[inline]
+    unsafe fn raw_surface(&self) -> Option<*const raw::c_void> {
+        self.context.raw_surface()
+    }
+
+    #[inline]
+    unsafe fn replace_surface(&self, new_surface: *const raw::c_void) -> Result<(), ContextError> {
+        self.context.replace_surface(new_surface)
+    }
 }
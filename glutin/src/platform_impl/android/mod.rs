@@ -1,17 +1,23 @@
 #![cfg(target_os = "android")]
 
-use crate::api::egl::{Context as EglContext, NativeDisplay, SurfaceType as EglSurfaceType};
+pub use crate::api::egl::EglSync;
+use crate::api::egl::{self, Context as EglContext, NativeDisplay, SurfaceType as EglSurfaceType};
 use crate::CreationError::{self, OsError};
-use crate::{Api, ContextError, GlAttributes, PixelFormat, PixelFormatRequirements, Rect};
+use crate::{
+    Api, ContextCurrentState, ContextError, CreationReport, FrameTimestamps, GlAttributes,
+    MultisampleResolve, NotCurrent, PixelFormat, PixelFormatRequirements, Priority, Rect,
+    ShareToken, SwapBehavior, VSyncError, VSyncMode,
+};
 
 use glutin_egl_sys as ffi;
 use parking_lot::Mutex;
-use raw_window_handle::{AndroidNdkWindowHandle, HasRawWindowHandle, RawWindowHandle};
+use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
 use winit::dpi;
 use winit::event_loop::EventLoopWindowTarget;
 use winit::window::WindowBuilder;
 
 use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(Debug)]
 struct AndroidContext {
@@ -31,26 +37,100 @@ impl Context {
         gl_attr: &GlAttributes<&Self>,
     ) -> Result<(winit::window::Window, Self), CreationError> {
         let win = wb.build(el)?;
+        let context = Self::new_for_window(&win, pf_reqs, gl_attr)?;
+        Ok((win, context))
+    }
+
+    #[inline]
+    pub fn new_window<T>(
+        wb: WindowBuilder,
+        el: &EventLoopWindowTarget<T>,
+        _pf_reqs: &PixelFormatRequirements,
+        _gl_attr: &GlAttributes<&Self>,
+    ) -> Result<winit::window::Window, CreationError> {
+        Ok(wb.build(el)?)
+    }
+
+    #[inline]
+    pub fn new_for_window(
+        win: &winit::window::Window,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Self>,
+    ) -> Result<Self, CreationError> {
         let gl_attr = gl_attr.clone().map_sharing(|c| &c.0.egl_context);
-        let nwin =
-            if let RawWindowHandle::AndroidNdk(AndroidNdkWindowHandle { a_native_window, .. }) =
-                win.raw_window_handle()
-            {
-                a_native_window
-            } else {
-                return Err(OsError("raw_window_handle() is not for Android".to_string()));
-            };
+        let handle = if let RawWindowHandle::AndroidNdk(handle) = win.raw_window_handle() {
+            handle
+        } else {
+            return Err(OsError("raw_window_handle() is not for Android".to_string()));
+        };
         let native_display = NativeDisplay::Android;
         let egl_context =
             EglContext::new(pf_reqs, &gl_attr, native_display, EglSurfaceType::Window, |c, _| {
                 Ok(c[0])
             })
-            .and_then(|p| p.finish(nwin))?;
+            // `ContextWrapper` drops the window after the context.
+            .and_then(|p| unsafe { p.finish_android(handle) })?;
         let ctx = Arc::new(AndroidContext { egl_context, stopped: Some(Mutex::new(false)) });
 
-        let context = Context(ctx);
+        Ok(Context(ctx))
+    }
 
-        Ok((win, context))
+    #[inline]
+    pub unsafe fn new_raw_egl_context(
+        native_display: NativeDisplay,
+        nwin: ffi::EGLNativeWindowType,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Self>,
+    ) -> Result<Self, CreationError> {
+        let gl_attr = gl_attr.clone().map_sharing(|c| &c.0.egl_context);
+        let egl_context =
+            EglContext::new(pf_reqs, &gl_attr, native_display, EglSurfaceType::Window, |c, _| {
+                Ok(c[0])
+            })
+            .and_then(|p| p.finish(nwin))?;
+        let ctx = Arc::new(AndroidContext { egl_context, stopped: None });
+
+        Ok(Context(ctx))
+    }
+
+    #[inline]
+    pub fn count_matching_formats<T>(
+        _el: &EventLoopWindowTarget<T>,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<usize, CreationError> {
+        let gl_attr = gl_attr.clone().set_sharing(None);
+        egl::count_matching_configs(
+            pf_reqs,
+            &gl_attr,
+            NativeDisplay::Android,
+            EglSurfaceType::Window,
+        )
+    }
+
+    #[inline]
+    pub fn enumerate_pixel_formats<T>(
+        _el: &EventLoopWindowTarget<T>,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<Vec<PixelFormat>, CreationError> {
+        let gl_attr = gl_attr.clone().set_sharing(None);
+        egl::enumerate_configs(pf_reqs, &gl_attr, NativeDisplay::Android, EglSurfaceType::Window)
+    }
+
+    #[inline]
+    pub fn query_swap_interval_range<T>(
+        _el: &EventLoopWindowTarget<T>,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<(i32, i32), CreationError> {
+        let gl_attr = gl_attr.clone().set_sharing(None);
+        egl::query_swap_interval_range(
+            pf_reqs,
+            &gl_attr,
+            NativeDisplay::Android,
+            EglSurfaceType::Window,
+        )
     }
 
     #[inline]
@@ -85,6 +165,18 @@ impl Context {
         self.0.egl_context.make_current()
     }
 
+    #[inline]
+    pub unsafe fn make_current_asymmetric(&self, read: &Context) -> Result<(), ContextError> {
+        if let Some(ref stopped) = self.0.stopped {
+            let stopped = stopped.lock();
+            if *stopped {
+                return Err(ContextError::ContextLost);
+            }
+        }
+
+        self.0.egl_context.make_current_asymmetric(&read.0.egl_context)
+    }
+
     #[inline]
     pub unsafe fn make_not_current(&self) -> Result<(), ContextError> {
         if let Some(ref stopped) = self.0.stopped {
@@ -100,21 +192,117 @@ impl Context {
     #[inline]
     pub fn resize(&self, _: u32, _: u32) {}
 
+    #[inline]
+    pub fn update_surface_size(&self, _width: u32, _height: u32) -> Result<bool, ContextError> {
+        if let Some(ref stopped) = self.0.stopped {
+            if *stopped.lock() {
+                return Err(ContextError::ContextLost);
+            }
+        }
+        Ok(false)
+    }
+
     #[inline]
     pub fn is_current(&self) -> bool {
         self.0.egl_context.is_current()
     }
 
+    #[inline]
+    pub fn bind_api(&self) -> Result<(), ContextError> {
+        self.0.egl_context.bind_api()
+    }
+
     #[inline]
     pub fn get_proc_address(&self, addr: &str) -> *const core::ffi::c_void {
         self.0.egl_context.get_proc_address(addr)
     }
 
     #[inline]
-    pub fn buffer_age(&self) -> u32 {
+    pub fn finish_with_timeout(&self, timeout: Duration) -> Result<bool, ContextError> {
+        self.0.egl_context.finish_with_timeout(timeout)
+    }
+
+    #[inline]
+    pub fn create_fence(&self) -> Result<EglSync, ContextError> {
+        self.0.egl_context.create_fence()
+    }
+
+    #[inline]
+    pub fn swap_buffers_after(&self, sync: &EglSync, timeout_ns: u64) -> Result<(), ContextError> {
+        if let Some(ref stopped) = self.0.stopped {
+            if *stopped.lock() {
+                return Err(ContextError::ContextLost);
+            }
+        }
+        self.0.egl_context.swap_buffers_after(sync, timeout_ns)
+    }
+
+    #[inline]
+    pub fn set_swap_behavior_preserved(&self, preserved: bool) -> Result<(), ContextError> {
+        self.0.egl_context.set_swap_behavior_preserved(preserved)
+    }
+
+    #[inline]
+    pub fn set_presentation_time(&self, nanos: i64) -> Result<(), ContextError> {
+        self.0.egl_context.set_presentation_time(nanos)
+    }
+
+    #[inline]
+    pub fn next_frame_id(&self) -> Result<u64, ContextError> {
+        self.0.egl_context.next_frame_id()
+    }
+
+    #[inline]
+    pub fn frame_timestamps(&self, frame_id: u64) -> Result<FrameTimestamps, ContextError> {
+        self.0.egl_context.frame_timestamps(frame_id)
+    }
+
+    #[inline]
+    pub fn recreate(&mut self) -> Result<(), CreationError> {
+        match Arc::get_mut(&mut self.0) {
+            Some(ctx) => ctx.egl_context.recreate(),
+            None => Err(OsError("the context is still referenced elsewhere".to_string())),
+        }
+    }
+
+    #[inline]
+    pub fn recreate_surface(&mut self) -> Result<(), CreationError> {
+        match Arc::get_mut(&mut self.0) {
+            Some(ctx) => ctx.egl_context.recreate_surface(),
+            None => Err(OsError("the context is still referenced elsewhere".to_string())),
+        }
+    }
+
+    #[inline]
+    pub fn swap_behavior(&self) -> Result<SwapBehavior, ContextError> {
+        self.0.egl_context.swap_behavior()
+    }
+
+    #[inline]
+    pub fn multisample_resolve(&self) -> Result<MultisampleResolve, ContextError> {
+        self.0.egl_context.multisample_resolve()
+    }
+
+    #[inline]
+    pub fn buffer_age_supported(&self) -> bool {
+        self.0.egl_context.buffer_age_supported()
+    }
+
+    #[inline]
+    pub fn supports_surfaceless(&self) -> bool {
+        self.0.egl_context.supports_surfaceless()
+    }
+
+    #[inline]
+    pub fn buffer_age(&self) -> Option<u32> {
         self.0.egl_context.buffer_age()
     }
 
+    #[inline]
+    pub fn would_block_on_swap(&self) -> bool {
+        self.0.egl_context.would_block_on_swap()
+    }
+
     #[inline]
     pub fn swap_buffers(&self) -> Result<(), ContextError> {
         if let Some(ref stopped) = self.0.stopped {
@@ -148,11 +336,31 @@ impl Context {
     }
 
     pub fn supports_vsync_mode(&self, mode: VSyncMode) -> bool {
-        self.egl_context.supports_vsync_mode(mode)
+        self.0.egl_context.supports_vsync_mode(mode)
+    }
+
+    #[inline]
+    pub fn supports_mailbox(&self) -> bool {
+        false
     }
 
     pub fn set_vsync_mode(&self, mode: VSyncMode) -> Result<(), VSyncError> {
-        self.egl_context.set_vsync_mode(mode)
+        self.0.egl_context.set_vsync_mode(mode)
+    }
+
+    #[inline]
+    pub fn get_vsync_mode(&self) -> Option<VSyncMode> {
+        Some(self.0.egl_context.get_vsync_mode())
+    }
+
+    #[inline]
+    pub fn vsync_range(&self) -> Option<(i32, i32)> {
+        Some(self.0.egl_context.vsync_range())
+    }
+
+    #[inline]
+    pub fn context_priority(&self) -> Option<Priority> {
+        self.0.egl_context.context_priority()
     }
 
     #[inline]
@@ -160,6 +368,26 @@ impl Context {
         self.0.egl_context.get_pixel_format()
     }
 
+    #[inline]
+    pub fn creation_report(&self) -> CreationReport {
+        self.0.egl_context.creation_report()
+    }
+
+    #[inline]
+    pub fn diagnostic_details(&self) -> Vec<(&'static str, String)> {
+        self.0.egl_context.diagnostic_details()
+    }
+
+    #[inline]
+    pub fn surface_size(&self) -> Option<dpi::PhysicalSize<u32>> {
+        self.0.egl_context.surface_size()
+    }
+
+    #[inline]
+    pub fn resize_pbuffer(&self, size: dpi::PhysicalSize<u32>) -> Result<(), ContextError> {
+        self.0.egl_context.resize_pbuffer(size)
+    }
+
     #[inline]
     pub unsafe fn raw_handle(&self) -> ffi::EGLContext {
         self.0.egl_context.raw_handle()
@@ -169,4 +397,87 @@ impl Context {
     pub unsafe fn get_egl_display(&self) -> ffi::EGLDisplay {
         self.0.egl_context.get_egl_display()
     }
+
+    #[inline]
+    pub fn current_surface(&self, read: bool) -> Option<ffi::egl::types::EGLSurface> {
+        self.0.egl_context.current_surface(read)
+    }
+
+    #[inline]
+    pub unsafe fn raw_surface(&self) -> Option<ffi::egl::types::EGLSurface> {
+        self.0.egl_context.raw_surface()
+    }
+
+    #[inline]
+    pub unsafe fn replace_surface(
+        &self,
+        new_surface: ffi::egl::types::EGLSurface,
+    ) -> Result<(), ContextError> {
+        self.0.egl_context.replace_surface(new_surface)
+    }
+
+    #[inline]
+    pub fn share_token(&self) -> Option<ShareToken> {
+        Some(self.0.egl_context.share_token())
+    }
+
+    #[inline]
+    pub unsafe fn raw_config(&self) -> Option<(ffi::EGLDisplay, ffi::egl::types::EGLConfig)> {
+        Some(self.0.egl_context.raw_config())
+    }
+
+    #[inline]
+    pub fn config_id(&self) -> Option<i32> {
+        Some(self.0.egl_context.config_id())
+    }
+
+    #[inline]
+    pub fn egl_extensions(&self) -> Option<&[String]> {
+        Some(self.0.egl_context.egl_extensions())
+    }
+
+    #[inline]
+    pub fn egl_version(&self) -> Option<(i32, i32)> {
+        Some(self.0.egl_context.egl_version())
+    }
+}
+
+/// An Android-specific extension to the
+/// [`ContextBuilder`][crate::ContextBuilder] for headless contexts.
+pub trait HeadlessContextExt {
+    /// Builds a context without any surface, e.g. for compute-only work,
+    /// rather than one with a dummy pbuffer.
+    ///
+    /// This needs `EGL_KHR_surfaceless_context`, and returns
+    /// [`CreationError::NotSupported`] otherwise.
+    fn build_surfaceless<TE>(
+        self,
+        el: &EventLoopWindowTarget<TE>,
+    ) -> Result<crate::Context<NotCurrent>, CreationError>
+    where
+        Self: Sized;
+}
+
+impl<'a, T: ContextCurrentState> HeadlessContextExt for crate::ContextBuilder<'a, T> {
+    #[inline]
+    fn build_surfaceless<TE>(
+        self,
+        _el: &EventLoopWindowTarget<TE>,
+    ) -> Result<crate::Context<NotCurrent>, CreationError>
+    where
+        Self: Sized,
+    {
+        let crate::ContextBuilder { pf_reqs, gl_attr, .. } = self;
+        let gl_attr = gl_attr.map_sharing(|ctx| &ctx.context.0.egl_context);
+        let egl_context = EglContext::new(
+            &pf_reqs,
+            &gl_attr,
+            NativeDisplay::Android,
+            EglSurfaceType::Surfaceless,
+            |c, _| Ok(c[0]),
+        )?
+        .finish_surfaceless()?;
+        let ctx = Arc::new(AndroidContext { egl_context, stopped: None });
+        Ok(crate::Context::new(Context(ctx), gl_attr.thread_affinity))
+    }
 }
@@ -1,7 +1,8 @@
 use std::cmp::Ordering;
 
 use crate::{
-    CreationError, GlAttributes, GlProfile, GlRequest, PixelFormatRequirements, ReleaseBehavior,
+    ColorBufferType, CreationError, GlAttributes, GlProfile, GlRequest, PixelFormatRequirements,
+    PowerPreference, ReleaseBehavior, SwapMethod,
 };
 
 use cocoa::appkit::*;
@@ -38,8 +39,10 @@ pub fn get_gl_profile<T>(
         // now, find the latest supported version automatically;
         let mut attributes: [u32; 6] = [0; 6];
         let mut current_idx = 0;
-        attributes[current_idx] = NSOpenGLPFAAllowOfflineRenderers as u32;
-        current_idx += 1;
+        if pf_reqs.power_preference != Some(PowerPreference::HighPerformance) {
+            attributes[current_idx] = NSOpenGLPFAAllowOfflineRenderers as u32;
+            current_idx += 1;
+        }
 
         if let Some(true) = pf_reqs.hardware_accelerated {
             attributes[current_idx] = NSOpenGLPFAAccelerated as u32;
@@ -93,9 +96,14 @@ pub fn build_nsattributes(
         pf_reqs.depth_bits.unwrap_or(24) as u32,
         NSOpenGLPFAStencilSize as u32,
         pf_reqs.stencil_bits.unwrap_or(8) as u32,
-        NSOpenGLPFAAllowOfflineRenderers as u32,
     ];
 
+    // Without this, the system switches to the discrete GPU for as long as
+    // the context lives.
+    if pf_reqs.power_preference != Some(PowerPreference::HighPerformance) {
+        attributes.push(NSOpenGLPFAAllowOfflineRenderers as u32);
+    }
+
     if let Some(true) = pf_reqs.hardware_accelerated {
         attributes.push(NSOpenGLPFAAccelerated as u32);
     }
@@ -107,6 +115,11 @@ pub fn build_nsattributes(
         attributes.push(NSOpenGLPFADoubleBuffer as u32);
     }
 
+    // Without a backing store, the back buffer is undefined after a swap.
+    if pf_reqs.swap_method == Some(SwapMethod::Copy) {
+        attributes.push(NSOpenGLPFABackingStore as u32);
+    }
+
     if pf_reqs.release_behavior != ReleaseBehavior::Flush {
         return Err(CreationError::NoAvailablePixelFormat);
     }
@@ -115,6 +128,13 @@ pub fn build_nsattributes(
         unimplemented!(); // TODO:
     }
 
+    if pf_reqs.color_buffer_type == ColorBufferType::Luminance
+        || pf_reqs.color_key.is_some()
+        || pf_reqs.bindable_to_texture
+    {
+        return Err(CreationError::NoAvailablePixelFormat);
+    }
+
     if pf_reqs.float_color_buffer {
         attributes.push(NSOpenGLPFAColorFloat as u32);
     }
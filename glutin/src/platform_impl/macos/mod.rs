@@ -1,8 +1,9 @@
 #![cfg(target_os = "macos")]
 #![allow(clippy::let_unit_value)]
 use crate::{
-    ContextError, CreationError, GlAttributes, PixelFormat, PixelFormatRequirements, Rect,
-    Robustness,
+    Api, Backend, ColorBufferType, ContextError, CreationError, CreationReport, FrameTimestamps,
+    GlAttributes, MultisampleResolve, PixelFormat, PixelFormatRequirements, Priority, Rect,
+    Robustness, ShareToken, SwapBehavior, TransparentType, VSyncError, VSyncMode,
 };
 
 use cgl::{kCGLCECrashOnRemovedFunctions, kCGLCPSurfaceOpacity, CGLEnable, CGLSetParameter};
@@ -23,9 +24,21 @@ use winit::window::{Window, WindowBuilder};
 use std::ops::Deref;
 use std::os::raw;
 use std::str::FromStr;
+use std::time::Duration;
 
 mod helpers;
 
+/// Fences need EGL, so none can ever be created on macOS.
+#[derive(Debug)]
+pub enum EglSync {}
+
+impl EglSync {
+    #[inline]
+    pub fn wait(&self, _timeout_ns: u64) -> Result<bool, ContextError> {
+        match *self {}
+    }
+}
+
 #[derive(Debug)]
 pub enum Context {
     WindowedContext(WindowedContext),
@@ -54,7 +67,39 @@ impl Context {
     ) -> Result<(Window, Self), CreationError> {
         let transparent = wb.transparent();
         let win = wb.build(el)?;
+        let context = Self::new_for_window_impl(&win, transparent, pf_reqs, gl_attr)?;
+        Ok((win, context))
+    }
+
+    #[inline]
+    pub fn new_window<T>(
+        wb: WindowBuilder,
+        el: &EventLoopWindowTarget<T>,
+        _pf_reqs: &PixelFormatRequirements,
+        _gl_attr: &GlAttributes<&Context>,
+    ) -> Result<Window, CreationError> {
+        Ok(wb.build(el)?)
+    }
+
+    #[inline]
+    pub fn new_for_window(
+        win: &Window,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<Self, CreationError> {
+        // winit makes the `NSWindow` non-opaque for transparent windows.
+        let opaque: BOOL = unsafe { msg_send![win.ns_window() as id, isOpaque] };
+        Self::new_for_window_impl(win, opaque == NO, pf_reqs, gl_attr)
+    }
 
+    fn new_for_window_impl(
+        win: &Window,
+        transparent: bool,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<Self, CreationError> {
+        gl_attr.check_no_external_share()?;
+        gl_attr.check_no_config_context()?;
         let share_ctx = gl_attr.sharing.map_or(nil, |c| *c.get_id());
 
         match gl_attr.robustness {
@@ -107,7 +152,9 @@ impl Context {
                         as u8,
                     alpha_bits: get_attr(appkit::NSOpenGLPFAAlphaSize) as u8,
                     depth_bits: get_attr(appkit::NSOpenGLPFADepthSize) as u8,
+                    float_depth_attachments: false,
                     stencil_bits: get_attr(appkit::NSOpenGLPFAStencilSize) as u8,
+                    packed_depth_stencil: false,
                     stereoscopy: get_attr(appkit::NSOpenGLPFAStereo) != 0,
                     double_buffer: get_attr(appkit::NSOpenGLPFADoubleBuffer) != 0,
                     multisampling: if get_attr(appkit::NSOpenGLPFAMultisample) > 0 {
@@ -116,9 +163,20 @@ impl Context {
                         None
                     },
                     srgb: true,
+                    native_renderable: false,
+                    bindable_to_texture: false,
+                    color_buffer_type: ColorBufferType::Rgb,
+                    transparent_type: TransparentType::None,
+                    transparent_color: (0, 0, 0),
                 }
             };
 
+            if !pf_reqs.multisampling_satisfied_by(pixel_format.multisampling)
+                || !pf_reqs.alpha_satisfied_by(pixel_format.alpha_bits)
+            {
+                return Err(CreationError::NoAvailablePixelFormat);
+            }
+
             gl_context.setView_(view);
             let value = gl_attr.vsync.get_swap_interval();
             gl_context.setValues_forParameter_(
@@ -138,10 +196,39 @@ impl Context {
             CGLEnable(gl_context.CGLContextObj() as *mut _, kCGLCECrashOnRemovedFunctions);
 
             let context = WindowedContext { context: gl_context, pixel_format };
-            Ok((win, Context::WindowedContext(context)))
+            Ok(Context::WindowedContext(context))
         }
     }
 
+    #[inline]
+    pub fn count_matching_formats<T>(
+        _el: &EventLoopWindowTarget<T>,
+        _pf_reqs: &PixelFormatRequirements,
+        _gl_attr: &GlAttributes<&Context>,
+    ) -> Result<usize, CreationError> {
+        Err(CreationError::NotSupported("counting pixel formats requires EGL".to_string()))
+    }
+
+    #[inline]
+    pub fn enumerate_pixel_formats<T>(
+        _el: &EventLoopWindowTarget<T>,
+        _pf_reqs: &PixelFormatRequirements,
+        _gl_attr: &GlAttributes<&Context>,
+    ) -> Result<Vec<PixelFormat>, CreationError> {
+        Err(CreationError::NotSupported("enumerating pixel formats requires EGL".to_string()))
+    }
+
+    #[inline]
+    pub fn query_swap_interval_range<T>(
+        _el: &EventLoopWindowTarget<T>,
+        _pf_reqs: &PixelFormatRequirements,
+        _gl_attr: &GlAttributes<&Context>,
+    ) -> Result<(i32, i32), CreationError> {
+        Err(CreationError::NotSupported(
+            "querying the swap interval range requires EGL".to_string(),
+        ))
+    }
+
     #[inline]
     pub fn new_headless<T>(
         _el: &EventLoopWindowTarget<T>,
@@ -149,6 +236,8 @@ impl Context {
         gl_attr: &GlAttributes<&Context>,
         _size: dpi::PhysicalSize<u32>,
     ) -> Result<Self, CreationError> {
+        gl_attr.check_no_external_share()?;
+        gl_attr.check_no_config_context()?;
         let gl_profile = helpers::get_gl_profile(gl_attr, pf_reqs)?;
         let attributes = helpers::build_nsattributes(pf_reqs, gl_profile)?;
         let context = unsafe {
@@ -181,6 +270,11 @@ impl Context {
         }
     }
 
+    #[inline]
+    pub fn update_surface_size(&self, _width: u32, _height: u32) -> Result<bool, ContextError> {
+        Ok(false)
+    }
+
     #[inline]
     pub unsafe fn make_current(&self) -> Result<(), ContextError> {
         match *self {
@@ -196,6 +290,11 @@ impl Context {
         Ok(())
     }
 
+    #[inline]
+    pub unsafe fn make_current_asymmetric(&self, _read: &Context) -> Result<(), ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
+
     #[inline]
     pub unsafe fn make_not_current(&self) -> Result<(), ContextError> {
         if self.is_current() {
@@ -234,6 +333,11 @@ impl Context {
         }
     }
 
+    #[inline]
+    pub fn bind_api(&self) -> Result<(), ContextError> {
+        Ok(())
+    }
+
     pub fn get_proc_address(&self, addr: &str) -> *const core::ffi::c_void {
         let symbol_name: CFString = FromStr::from_str(addr).unwrap();
         let framework_name: CFString = FromStr::from_str("com.apple.opengl").unwrap();
@@ -245,6 +349,61 @@ impl Context {
         symbol as *const _
     }
 
+    #[inline]
+    pub fn finish_with_timeout(&self, _timeout: Duration) -> Result<bool, ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
+
+    #[inline]
+    pub fn create_fence(&self) -> Result<EglSync, ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
+
+    #[inline]
+    pub fn swap_buffers_after(&self, sync: &EglSync, _timeout_ns: u64) -> Result<(), ContextError> {
+        match *sync {}
+    }
+
+    #[inline]
+    pub fn set_swap_behavior_preserved(&self, _preserved: bool) -> Result<(), ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
+
+    #[inline]
+    pub fn set_presentation_time(&self, _nanos: i64) -> Result<(), ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
+
+    #[inline]
+    pub fn next_frame_id(&self) -> Result<u64, ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
+
+    #[inline]
+    pub fn frame_timestamps(&self, frame_id: u64) -> Result<FrameTimestamps, ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
+
+    #[inline]
+    pub fn recreate(&mut self) -> Result<(), CreationError> {
+        Err(CreationError::NotSupported("recreating CGL contexts".to_string()))
+    }
+
+    #[inline]
+    pub fn recreate_surface(&mut self) -> Result<(), CreationError> {
+        Err(CreationError::NotSupported("recreating CGL contexts".to_string()))
+    }
+
+    #[inline]
+    pub fn swap_behavior(&self) -> Result<SwapBehavior, ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
+
+    #[inline]
+    pub fn multisample_resolve(&self) -> Result<MultisampleResolve, ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
+
     #[inline]
     pub fn swap_buffers(&self) -> Result<(), ContextError> {
         unsafe {
@@ -261,8 +420,23 @@ impl Context {
     }
 
     #[inline]
-    pub fn buffer_age(&self) -> u32 {
-        0
+    pub fn buffer_age_supported(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn supports_surfaceless(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn buffer_age(&self) -> Option<u32> {
+        None
+    }
+
+    #[inline]
+    pub fn would_block_on_swap(&self) -> bool {
+        false
     }
 
     #[inline]
@@ -279,14 +453,56 @@ impl Context {
     pub fn get_api(&self) -> crate::Api {
         crate::Api::OpenGl
     }
+
+    #[inline]
     pub fn supports_vsync_mode(&self, mode: VSyncMode) -> bool {
-        todo!()
+        match *self {
+            // `NSOpenGLCPSwapInterval` only distinguishes between vsync on and off.
+            Context::WindowedContext(_) => matches!(mode.get_swap_interval(), 0 | 1),
+            Context::HeadlessContext(_) => false,
+        }
+    }
+
+    #[inline]
+    pub fn supports_mailbox(&self) -> bool {
+        false
     }
 
     pub fn set_vsync_mode(&self, mode: VSyncMode) -> Result<(), VSyncError> {
-        todo!()
+        if !self.supports_vsync_mode(mode) {
+            return Err(VSyncError::UnsupportedVSyncMode(mode));
+        }
+
+        match *self {
+            Context::WindowedContext(ref c) => unsafe {
+                let value = mode.get_swap_interval();
+                c.context.setValues_forParameter_(
+                    &value,
+                    appkit::NSOpenGLContextParameter::NSOpenGLCPSwapInterval,
+                );
+            },
+            Context::HeadlessContext(_) => unreachable!(),
+        }
+        Ok(())
     }
 
+    #[inline]
+    pub fn get_vsync_mode(&self) -> Option<VSyncMode> {
+        None
+    }
+
+    #[inline]
+    pub fn vsync_range(&self) -> Option<(i32, i32)> {
+        match *self {
+            Context::WindowedContext(_) => Some((0, 1)),
+            Context::HeadlessContext(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn context_priority(&self) -> Option<Priority> {
+        None
+    }
 
     #[inline]
     pub fn get_pixel_format(&self) -> PixelFormat {
@@ -296,6 +512,35 @@ impl Context {
         }
     }
 
+    #[inline]
+    pub fn creation_report(&self) -> CreationReport {
+        CreationReport {
+            backend: Backend::Cgl,
+            backend_version: None,
+            api: Api::OpenGl,
+            version: None,
+            robustness: None,
+            debug: None,
+            extensions: Vec::new(),
+            pixel_format: self.get_pixel_format(),
+        }
+    }
+
+    #[inline]
+    pub fn diagnostic_details(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    #[inline]
+    pub fn surface_size(&self) -> Option<dpi::PhysicalSize<u32>> {
+        None
+    }
+
+    #[inline]
+    pub fn resize_pbuffer(&self, _size: dpi::PhysicalSize<u32>) -> Result<(), ContextError> {
+        Err(ContextError::FunctionUnavailable)
+    }
+
     #[inline]
     pub unsafe fn raw_handle(&self) -> *mut raw::c_void {
         match self {
@@ -304,6 +549,31 @@ impl Context {
         }
     }
 
+    #[inline]
+    pub fn share_token(&self) -> Option<ShareToken> {
+        None
+    }
+
+    #[inline]
+    pub unsafe fn raw_config(&self) -> Option<(*const raw::c_void, *const raw::c_void)> {
+        None
+    }
+
+    #[inline]
+    pub fn config_id(&self) -> Option<i32> {
+        None
+    }
+
+    #[inline]
+    pub fn egl_extensions(&self) -> Option<&[String]> {
+        None
+    }
+
+    #[inline]
+    pub fn egl_version(&self) -> Option<(i32, i32)> {
+        None
+    }
+
     #[inline]
     fn get_id(&self) -> IdRef {
         match self {
@@ -14,25 +14,30 @@ mod x11;
 
 #[cfg(feature = "x11")]
 use self::x11::X11Context;
+pub use crate::api::egl::EglSync;
+use crate::api::egl::{
+    Context as EglContext, EglDevice, EglStream, NativeDisplay, SurfaceType as EglSurfaceType, EGL,
+};
 use crate::api::osmesa;
 use crate::{
-    Api, ContextCurrentState, ContextError, CreationError, GlAttributes, NotCurrent, PixelFormat,
-    PixelFormatRequirements, Rect,
+    Api, ContextCurrentState, ContextError, CreationError, CreationReport, FrameTimestamps,
+    GlAttributes, MultisampleResolve, NotCurrent, PixelFormat, PixelFormatRequirements, Priority,
+    Rect, ShareToken, SwapBehavior, VSyncError, VSyncMode,
 };
 #[cfg(feature = "x11")]
 pub use x11::utils as x11_utils;
 
 #[cfg(feature = "x11")]
 use crate::platform::unix::x11::XConnection;
-use crate::platform::unix::EventLoopWindowTargetExtUnix;
+use crate::platform::unix::{EventLoopWindowTargetExtUnix, WindowExtUnix};
 use winit::dpi;
 use winit::event_loop::EventLoopWindowTarget;
 use winit::window::{Window, WindowBuilder};
 
-use std::marker::PhantomData;
 use std::os::raw;
 #[cfg(feature = "x11")]
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Context handles available on Unix-like platforms.
 #[derive(Clone, Debug)]
@@ -50,6 +55,7 @@ pub enum ContextType {
     X11,
     #[cfg(feature = "wayland")]
     Wayland,
+    Egl,
     OsMesa,
 }
 
@@ -59,6 +65,8 @@ pub enum Context {
     X11(x11::Context),
     #[cfg(feature = "wayland")]
     Wayland(wayland::Context),
+    /// A context created directly on an EGL device, without a display server.
+    Egl(EglContext),
     OsMesa(osmesa::OsMesaContext),
 }
 
@@ -73,6 +81,14 @@ impl Context {
                         Err(CreationError::PlatformSpecific(msg.into()))
                     }
                 },
+                ContextType::Egl => match *c {
+                    Context::Egl(_) => Ok(()),
+                    _ => {
+                        let msg =
+                            "Cannot share an EGL device context with a non-EGL device context";
+                        Err(CreationError::PlatformSpecific(msg.into()))
+                    }
+                },
                 #[cfg(feature = "x11")]
                 ContextType::X11 => match *c {
                     Context::X11(_) => Ok(()),
@@ -95,6 +111,26 @@ impl Context {
         }
     }
 
+    #[inline]
+    pub unsafe fn new_raw_egl_context(
+        native_display: NativeDisplay,
+        nwin: glutin_egl_sys::EGLNativeWindowType,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<Self, CreationError> {
+        if EGL.is_none() {
+            return Err(CreationError::NotSupported("libEGL not present".to_string()));
+        }
+        Context::is_compatible(&gl_attr.sharing, ContextType::Egl)?;
+        let gl_attr = gl_attr.clone().map_sharing(|ctx| match *ctx {
+            Context::Egl(ref ctx) => ctx,
+            _ => unreachable!(),
+        });
+        EglContext::new(pf_reqs, &gl_attr, native_display, EglSurfaceType::Window, |c, _| Ok(c[0]))
+            .and_then(|p| p.finish(nwin))
+            .map(Context::Egl)
+    }
+
     #[inline]
     pub fn new_windowed<T>(
         wb: WindowBuilder,
@@ -126,6 +162,66 @@ impl Context {
         panic!("glutin was not compiled with support for this display server")
     }
 
+    /// Builds a window suitable for [`new_for_window()`], without creating a
+    /// context yet.
+    ///
+    /// [`new_for_window()`]: Self::new_for_window()
+    #[inline]
+    pub fn new_window<T>(
+        wb: WindowBuilder,
+        el: &EventLoopWindowTarget<T>,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<Window, CreationError> {
+        #[cfg(feature = "wayland")]
+        if el.is_wayland() {
+            return Ok(wb.build(el)?);
+        }
+        #[cfg(feature = "x11")]
+        if el.is_x11() {
+            let gl_attr = gl_attr.clone().set_sharing(None);
+            return x11::Context::new_window(wb, el, pf_reqs, &gl_attr);
+        }
+        panic!("glutin was not compiled with support for this display server")
+    }
+
+    #[inline]
+    pub fn new_for_window(
+        win: &Window,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<Self, CreationError> {
+        #[cfg(feature = "wayland")]
+        if let Some(surface) = win.wayland_surface() {
+            Context::is_compatible(&gl_attr.sharing, ContextType::Wayland)?;
+            let gl_attr = gl_attr.clone().map_sharing(|ctx| match *ctx {
+                Context::Wayland(ref ctx) => ctx,
+                _ => unreachable!(),
+            });
+            let display_ptr = win.wayland_display().unwrap() as *const _;
+            let (width, height): (u32, u32) = win.inner_size().into();
+            return wayland::Context::new_raw_context(
+                display_ptr,
+                surface,
+                width,
+                height,
+                pf_reqs,
+                &gl_attr,
+            )
+            .map(Context::Wayland);
+        }
+        #[cfg(feature = "x11")]
+        if let (Some(xconn), Some(xwin)) = (win.xlib_xconnection(), win.xlib_window()) {
+            Context::is_compatible(&gl_attr.sharing, ContextType::X11)?;
+            let gl_attr = gl_attr.clone().map_sharing(|ctx| match *ctx {
+                Context::X11(ref ctx) => ctx,
+                _ => unreachable!(),
+            });
+            return x11::Context::new_raw_context(xconn, xwin, pf_reqs, &gl_attr).map(Context::X11);
+        }
+        panic!("glutin was not compiled with support for this display server")
+    }
+
     #[inline]
     pub fn new_headless<T>(
         el: &EventLoopWindowTarget<T>,
@@ -164,6 +260,76 @@ impl Context {
         panic!("glutin was not compiled with support for this display server")
     }
 
+    /// The EGL display matching the display server `el` is connected to.
+    fn native_display<T>(el: &EventLoopWindowTarget<T>) -> Result<NativeDisplay, CreationError> {
+        #[cfg(feature = "wayland")]
+        if let Some(display) = el.wayland_display() {
+            return Ok(NativeDisplay::Wayland(Some(display as *const _)));
+        }
+        #[cfg(feature = "x11")]
+        if let Some(xconn) = el.xlib_xconnection() {
+            return Ok(NativeDisplay::X11(Some(xconn.display as *const _)));
+        }
+        Err(CreationError::NotSupported("no EGL display for this display server".to_string()))
+    }
+
+    #[inline]
+    pub fn count_matching_formats<T>(
+        el: &EventLoopWindowTarget<T>,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<usize, CreationError> {
+        #[cfg(feature = "wayland")]
+        if el.is_wayland() {
+            let gl_attr = gl_attr.clone().set_sharing(None);
+            return wayland::Context::count_matching_formats(el, pf_reqs, &gl_attr);
+        }
+        #[cfg(feature = "x11")]
+        if el.is_x11() {
+            let gl_attr = gl_attr.clone().set_sharing(None);
+            return x11::Context::count_matching_formats(el, pf_reqs, &gl_attr);
+        }
+        panic!("glutin was not compiled with support for this display server")
+    }
+
+    #[inline]
+    pub fn enumerate_pixel_formats<T>(
+        el: &EventLoopWindowTarget<T>,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<Vec<PixelFormat>, CreationError> {
+        #[cfg(feature = "wayland")]
+        if el.is_wayland() {
+            let gl_attr = gl_attr.clone().set_sharing(None);
+            return wayland::Context::enumerate_pixel_formats(el, pf_reqs, &gl_attr);
+        }
+        #[cfg(feature = "x11")]
+        if el.is_x11() {
+            let gl_attr = gl_attr.clone().set_sharing(None);
+            return x11::Context::enumerate_pixel_formats(el, pf_reqs, &gl_attr);
+        }
+        panic!("glutin was not compiled with support for this display server")
+    }
+
+    #[inline]
+    pub fn query_swap_interval_range<T>(
+        el: &EventLoopWindowTarget<T>,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<(i32, i32), CreationError> {
+        #[cfg(feature = "wayland")]
+        if el.is_wayland() {
+            let gl_attr = gl_attr.clone().set_sharing(None);
+            return wayland::Context::query_swap_interval_range(el, pf_reqs, &gl_attr);
+        }
+        #[cfg(feature = "x11")]
+        if el.is_x11() {
+            let gl_attr = gl_attr.clone().set_sharing(None);
+            return x11::Context::query_swap_interval_range(el, pf_reqs, &gl_attr);
+        }
+        panic!("glutin was not compiled with support for this display server")
+    }
+
     #[inline]
     pub unsafe fn make_current(&self) -> Result<(), ContextError> {
         match *self {
@@ -171,10 +337,25 @@ impl Context {
             Context::X11(ref ctx) => ctx.make_current(),
             #[cfg(feature = "wayland")]
             Context::Wayland(ref ctx) => ctx.make_current(),
+            Context::Egl(ref ctx) => ctx.make_current(),
             Context::OsMesa(ref ctx) => ctx.make_current(),
         }
     }
 
+    #[inline]
+    pub unsafe fn make_current_asymmetric(&self, read: &Context) -> Result<(), ContextError> {
+        match (self, read) {
+            #[cfg(feature = "x11")]
+            (Context::X11(ref ctx), Context::X11(ref read)) => ctx.make_current_asymmetric(read),
+            #[cfg(feature = "wayland")]
+            (Context::Wayland(ref ctx), Context::Wayland(ref read)) => {
+                ctx.make_current_asymmetric(read)
+            }
+            (Context::Egl(ref ctx), Context::Egl(ref read)) => ctx.make_current_asymmetric(read),
+            _ => Err(ContextError::FunctionUnavailable),
+        }
+    }
+
     #[inline]
     pub unsafe fn make_not_current(&self) -> Result<(), ContextError> {
         match *self {
@@ -182,6 +363,7 @@ impl Context {
             Context::X11(ref ctx) => ctx.make_not_current(),
             #[cfg(feature = "wayland")]
             Context::Wayland(ref ctx) => ctx.make_not_current(),
+            Context::Egl(ref ctx) => ctx.make_not_current(),
             Context::OsMesa(ref ctx) => ctx.make_not_current(),
         }
     }
@@ -193,10 +375,23 @@ impl Context {
             Context::X11(ref ctx) => ctx.is_current(),
             #[cfg(feature = "wayland")]
             Context::Wayland(ref ctx) => ctx.is_current(),
+            Context::Egl(ref ctx) => ctx.is_current(),
             Context::OsMesa(ref ctx) => ctx.is_current(),
         }
     }
 
+    #[inline]
+    pub fn bind_api(&self) -> Result<(), ContextError> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.bind_api(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.bind_api(),
+            Context::Egl(ref ctx) => ctx.bind_api(),
+            Context::OsMesa(_) => Ok(()),
+        }
+    }
+
     #[inline]
     pub fn get_api(&self) -> Api {
         match *self {
@@ -204,10 +399,82 @@ impl Context {
             Context::X11(ref ctx) => ctx.get_api(),
             #[cfg(feature = "wayland")]
             Context::Wayland(ref ctx) => ctx.get_api(),
+            Context::Egl(ref ctx) => ctx.get_api(),
             Context::OsMesa(ref ctx) => ctx.get_api(),
         }
     }
 
+    #[inline]
+    pub fn supports_vsync_mode(&self, mode: VSyncMode) -> bool {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.supports_vsync_mode(mode),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.supports_vsync_mode(mode),
+            Context::Egl(ref ctx) => ctx.supports_vsync_mode(mode),
+            Context::OsMesa(_) => false,
+        }
+    }
+
+    #[inline]
+    pub fn supports_mailbox(&self) -> bool {
+        // Wayland compositors only ever present the latest committed buffer,
+        // so not waiting for vblank doesn't tear there.
+        #[cfg(feature = "wayland")]
+        if let Context::Wayland(_) = *self {
+            return true;
+        }
+        false
+    }
+
+    #[inline]
+    pub fn set_vsync_mode(&self, mode: VSyncMode) -> Result<(), VSyncError> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.set_vsync_mode(mode),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.set_vsync_mode(mode),
+            Context::Egl(ref ctx) => ctx.set_vsync_mode(mode),
+            Context::OsMesa(_) => Err(VSyncError::UnsupportedVSyncMode(mode)),
+        }
+    }
+
+    #[inline]
+    pub fn get_vsync_mode(&self) -> Option<VSyncMode> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.get_vsync_mode(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => Some(ctx.get_vsync_mode()),
+            Context::Egl(ref ctx) => Some(ctx.get_vsync_mode()),
+            Context::OsMesa(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn vsync_range(&self) -> Option<(i32, i32)> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.vsync_range(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => Some(ctx.vsync_range()),
+            Context::Egl(ref ctx) => Some(ctx.vsync_range()),
+            Context::OsMesa(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn context_priority(&self) -> Option<Priority> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.context_priority(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.context_priority(),
+            Context::Egl(ref ctx) => ctx.context_priority(),
+            Context::OsMesa(_) => None,
+        }
+    }
+
     #[inline]
     pub unsafe fn raw_handle(&self) -> RawHandle {
         match *self {
@@ -218,6 +485,7 @@ impl Context {
             },
             #[cfg(feature = "wayland")]
             Context::Wayland(ref ctx) => RawHandle::Egl(ctx.raw_handle()),
+            Context::Egl(ref ctx) => RawHandle::Egl(ctx.raw_handle()),
             Context::OsMesa(ref ctx) => RawHandle::Egl(ctx.raw_handle()),
         }
     }
@@ -229,10 +497,110 @@ impl Context {
             Context::X11(ref ctx) => ctx.get_egl_display(),
             #[cfg(feature = "wayland")]
             Context::Wayland(ref ctx) => ctx.get_egl_display(),
+            Context::Egl(ref ctx) => Some(ctx.get_egl_display()),
             _ => None,
         }
     }
 
+    #[inline]
+    pub fn current_surface(&self, read: bool) -> Option<*const raw::c_void> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.current_surface(read),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.current_surface(read),
+            Context::Egl(ref ctx) => ctx.current_surface(read),
+            Context::OsMesa(_) => None,
+        }
+    }
+
+    #[inline]
+    pub unsafe fn raw_surface(&self) -> Option<*const raw::c_void> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.raw_surface(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.raw_surface(),
+            Context::Egl(ref ctx) => ctx.raw_surface(),
+            Context::OsMesa(_) => None,
+        }
+    }
+
+    #[inline]
+    pub unsafe fn replace_surface(
+        &self,
+        new_surface: *const raw::c_void,
+    ) -> Result<(), ContextError> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.replace_surface(new_surface),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.replace_surface(new_surface),
+            Context::Egl(ref ctx) => ctx.replace_surface(new_surface),
+            Context::OsMesa(_) => Err(ContextError::FunctionUnavailable),
+        }
+    }
+
+    #[inline]
+    pub fn share_token(&self) -> Option<ShareToken> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.share_token(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => Some(ctx.share_token()),
+            Context::Egl(ref ctx) => Some(ctx.share_token()),
+            Context::OsMesa(_) => None,
+        }
+    }
+
+    #[inline]
+    pub unsafe fn raw_config(&self) -> Option<(*const raw::c_void, *const raw::c_void)> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.raw_config(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => Some(ctx.raw_config()),
+            Context::Egl(ref ctx) => Some(ctx.raw_config()),
+            Context::OsMesa(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn config_id(&self) -> Option<i32> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.config_id(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => Some(ctx.config_id()),
+            Context::Egl(ref ctx) => Some(ctx.config_id()),
+            Context::OsMesa(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn egl_extensions(&self) -> Option<&[String]> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.egl_extensions(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => Some(ctx.egl_extensions()),
+            Context::Egl(ref ctx) => Some(ctx.egl_extensions()),
+            Context::OsMesa(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn egl_version(&self) -> Option<(i32, i32)> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.egl_version(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => Some(ctx.egl_version()),
+            Context::Egl(ref ctx) => Some(ctx.egl_version()),
+            Context::OsMesa(_) => None,
+        }
+    }
+
     #[inline]
     pub fn resize(&self, width: u32, height: u32) {
         #![allow(unused)]
@@ -241,10 +609,22 @@ impl Context {
             Context::X11(_) => (),
             #[cfg(feature = "wayland")]
             Context::Wayland(ref ctx) => ctx.resize(width, height),
+            Context::Egl(_) => (),
             _ => unreachable!(),
         }
     }
 
+    #[inline]
+    pub fn update_surface_size(&self, width: u32, height: u32) -> Result<bool, ContextError> {
+        #![allow(unused)]
+        match *self {
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => Ok(ctx.update_surface_size(width, height)),
+            // X11 drawables follow the window on their own.
+            _ => Ok(false),
+        }
+    }
+
     #[inline]
     pub fn get_proc_address(&self, addr: &str) -> *const core::ffi::c_void {
         match *self {
@@ -252,10 +632,147 @@ impl Context {
             Context::X11(ref ctx) => ctx.get_proc_address(addr),
             #[cfg(feature = "wayland")]
             Context::Wayland(ref ctx) => ctx.get_proc_address(addr),
+            Context::Egl(ref ctx) => ctx.get_proc_address(addr),
             Context::OsMesa(ref ctx) => ctx.get_proc_address(addr),
         }
     }
 
+    #[inline]
+    pub fn finish_with_timeout(&self, timeout: Duration) -> Result<bool, ContextError> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.finish_with_timeout(timeout),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.finish_with_timeout(timeout),
+            Context::Egl(ref ctx) => ctx.finish_with_timeout(timeout),
+            Context::OsMesa(_) => Err(ContextError::FunctionUnavailable),
+        }
+    }
+
+    #[inline]
+    pub fn create_fence(&self) -> Result<EglSync, ContextError> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.create_fence(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.create_fence(),
+            Context::Egl(ref ctx) => ctx.create_fence(),
+            Context::OsMesa(_) => Err(ContextError::FunctionUnavailable),
+        }
+    }
+
+    #[inline]
+    pub fn swap_buffers_after(&self, sync: &EglSync, timeout_ns: u64) -> Result<(), ContextError> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.swap_buffers_after(sync, timeout_ns),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.swap_buffers_after(sync, timeout_ns),
+            Context::Egl(ref ctx) => ctx.swap_buffers_after(sync, timeout_ns),
+            Context::OsMesa(_) => Err(ContextError::FunctionUnavailable),
+        }
+    }
+
+    #[inline]
+    pub fn set_swap_behavior_preserved(&self, preserved: bool) -> Result<(), ContextError> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.set_swap_behavior_preserved(preserved),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.set_swap_behavior_preserved(preserved),
+            Context::Egl(ref ctx) => ctx.set_swap_behavior_preserved(preserved),
+            Context::OsMesa(_) => Err(ContextError::FunctionUnavailable),
+        }
+    }
+
+    #[inline]
+    pub fn set_presentation_time(&self, nanos: i64) -> Result<(), ContextError> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.set_presentation_time(nanos),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.set_presentation_time(nanos),
+            Context::Egl(ref ctx) => ctx.set_presentation_time(nanos),
+            Context::OsMesa(_) => Err(ContextError::FunctionUnavailable),
+        }
+    }
+
+    #[inline]
+    pub fn next_frame_id(&self) -> Result<u64, ContextError> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.next_frame_id(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.next_frame_id(),
+            Context::Egl(ref ctx) => ctx.next_frame_id(),
+            Context::OsMesa(_) => Err(ContextError::FunctionUnavailable),
+        }
+    }
+
+    #[inline]
+    pub fn frame_timestamps(&self, frame_id: u64) -> Result<FrameTimestamps, ContextError> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.frame_timestamps(frame_id),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.frame_timestamps(frame_id),
+            Context::Egl(ref ctx) => ctx.frame_timestamps(frame_id),
+            Context::OsMesa(_) => Err(ContextError::FunctionUnavailable),
+        }
+    }
+
+    #[inline]
+    pub fn recreate(&mut self) -> Result<(), CreationError> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref mut ctx) => ctx.recreate(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref mut ctx) => ctx.recreate(),
+            Context::Egl(ref mut ctx) => ctx.recreate(),
+            Context::OsMesa(_) => {
+                Err(CreationError::NotSupported("recreating OSMesa contexts".to_string()))
+            }
+        }
+    }
+
+    #[inline]
+    pub fn recreate_surface(&mut self) -> Result<(), CreationError> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref mut ctx) => ctx.recreate_surface(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref mut ctx) => ctx.recreate_surface(),
+            Context::Egl(ref mut ctx) => ctx.recreate_surface(),
+            Context::OsMesa(_) => {
+                Err(CreationError::NotSupported("recreating OSMesa contexts".to_string()))
+            }
+        }
+    }
+
+    #[inline]
+    pub fn swap_behavior(&self) -> Result<SwapBehavior, ContextError> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.swap_behavior(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.swap_behavior(),
+            Context::Egl(ref ctx) => ctx.swap_behavior(),
+            Context::OsMesa(_) => Err(ContextError::FunctionUnavailable),
+        }
+    }
+
+    #[inline]
+    pub fn multisample_resolve(&self) -> Result<MultisampleResolve, ContextError> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.multisample_resolve(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.multisample_resolve(),
+            Context::Egl(ref ctx) => ctx.multisample_resolve(),
+            Context::OsMesa(_) => Err(ContextError::FunctionUnavailable),
+        }
+    }
+
     #[inline]
     pub fn swap_buffers(&self) -> Result<(), ContextError> {
         match *self {
@@ -263,6 +780,7 @@ impl Context {
             Context::X11(ref ctx) => ctx.swap_buffers(),
             #[cfg(feature = "wayland")]
             Context::Wayland(ref ctx) => ctx.swap_buffers(),
+            Context::Egl(ref ctx) => ctx.swap_buffers(),
             _ => unreachable!(),
         }
     }
@@ -274,18 +792,56 @@ impl Context {
             Context::X11(ref ctx) => ctx.swap_buffers_with_damage(rects),
             #[cfg(feature = "wayland")]
             Context::Wayland(ref ctx) => ctx.swap_buffers_with_damage(rects),
+            Context::Egl(ref ctx) => ctx.swap_buffers_with_damage(rects),
             _ => unreachable!(),
         }
     }
 
     #[inline]
-    pub fn buffer_age(&self) -> u32 {
+    pub fn buffer_age_supported(&self) -> bool {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.buffer_age_supported(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.buffer_age_supported(),
+            Context::Egl(ref ctx) => ctx.buffer_age_supported(),
+            Context::OsMesa(_) => false,
+        }
+    }
+
+    #[inline]
+    pub fn supports_surfaceless(&self) -> bool {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.supports_surfaceless(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.supports_surfaceless(),
+            Context::Egl(ref ctx) => ctx.supports_surfaceless(),
+            Context::OsMesa(_) => false,
+        }
+    }
+
+    #[inline]
+    pub fn buffer_age(&self) -> Option<u32> {
         match *self {
             #[cfg(feature = "x11")]
             Context::X11(ref ctx) => ctx.buffer_age(),
             #[cfg(feature = "wayland")]
             Context::Wayland(ref ctx) => ctx.buffer_age(),
-            _ => unreachable!(),
+            Context::Egl(ref ctx) => ctx.buffer_age(),
+            Context::OsMesa(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn would_block_on_swap(&self) -> bool {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.would_block_on_swap(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.would_block_on_swap(),
+            Context::Egl(ref ctx) => ctx.would_block_on_swap(),
+            Context::OsMesa(_) => false,
         }
     }
 
@@ -296,6 +852,7 @@ impl Context {
             Context::X11(ref ctx) => ctx.swap_buffers_with_damage_supported(),
             #[cfg(feature = "wayland")]
             Context::Wayland(ref ctx) => ctx.swap_buffers_with_damage_supported(),
+            Context::Egl(ref ctx) => ctx.swap_buffers_with_damage_supported(),
             _ => unreachable!(),
         }
     }
@@ -307,9 +864,58 @@ impl Context {
             Context::X11(ref ctx) => ctx.get_pixel_format(),
             #[cfg(feature = "wayland")]
             Context::Wayland(ref ctx) => ctx.get_pixel_format(),
+            Context::Egl(ref ctx) => ctx.get_pixel_format(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[inline]
+    pub fn creation_report(&self) -> CreationReport {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.creation_report(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.creation_report(),
+            Context::Egl(ref ctx) => ctx.creation_report(),
             _ => unreachable!(),
         }
     }
+
+    #[inline]
+    pub fn diagnostic_details(&self) -> Vec<(&'static str, String)> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.diagnostic_details(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.diagnostic_details(),
+            Context::Egl(ref ctx) => ctx.diagnostic_details(),
+            Context::OsMesa(_) => Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn surface_size(&self) -> Option<dpi::PhysicalSize<u32>> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.surface_size(),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.surface_size(),
+            Context::Egl(ref ctx) => ctx.surface_size(),
+            Context::OsMesa(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn resize_pbuffer(&self, size: dpi::PhysicalSize<u32>) -> Result<(), ContextError> {
+        match *self {
+            #[cfg(feature = "x11")]
+            Context::X11(ref ctx) => ctx.resize_pbuffer(size),
+            #[cfg(feature = "wayland")]
+            Context::Wayland(ref ctx) => ctx.resize_pbuffer(size),
+            Context::Egl(ref ctx) => ctx.resize_pbuffer(size),
+            Context::OsMesa(_) => Err(ContextError::FunctionUnavailable),
+        }
+    }
 }
 
 /// A unix-specific extension to the [`ContextBuilder`][crate::ContextBuilder]
@@ -338,6 +944,56 @@ pub trait HeadlessContextExt {
     ) -> Result<crate::Context<NotCurrent>, CreationError>
     where
         Self: Sized;
+
+    /// Builds an EGL context rendering into `stream`, e.g. one created by
+    /// [`create_output_stream()`] to display frames without a compositor.
+    ///
+    /// Every `eglSwapBuffers` on the context's surface, see
+    /// [`raw_surface()`], hands the rendered frame over to the stream's
+    /// consumer. The context can only share with other contexts built this
+    /// way.
+    ///
+    /// [`create_output_stream()`]: crate::platform::unix::egl::create_output_stream()
+    /// [`raw_surface()`]: crate::platform::ContextTraitExt::raw_surface()
+    fn build_egl_stream(
+        self,
+        stream: EglStream,
+        size: dpi::PhysicalSize<u32>,
+    ) -> Result<crate::Context<NotCurrent>, CreationError>
+    where
+        Self: Sized;
+
+    /// Builds an EGL context with neither a config nor a surface, for work
+    /// that never touches a framebuffer, such as OpenGL ES 3.1 compute
+    /// shaders.
+    ///
+    /// This needs `EGL_KHR_no_config_context` and
+    /// `EGL_KHR_surfaceless_context`, and returns
+    /// [`CreationError::NotSupported`] naming the missing one otherwise. The
+    /// pixel format requirements are ignored. The context can only share
+    /// with other contexts built this way or with
+    /// [`build_egl_stream()`][Self::build_egl_stream()].
+    fn build_compute<TE>(
+        self,
+        el: &EventLoopWindowTarget<TE>,
+    ) -> Result<crate::Context<NotCurrent>, CreationError>
+    where
+        Self: Sized;
+
+    /// Builds an EGL pbuffer context on `device`, e.g. one of the GPUs listed
+    /// by [`query_egl_devices()`], instead of the display of the event loop.
+    ///
+    /// This needs `EGL_EXT_platform_device`. The context can only share with
+    /// other EGL contexts on the same device.
+    ///
+    /// [`query_egl_devices()`]: crate::platform::unix::egl::query_egl_devices()
+    fn build_egl_device(
+        self,
+        device: &EglDevice,
+        size: dpi::PhysicalSize<u32>,
+    ) -> Result<crate::Context<NotCurrent>, CreationError>
+    where
+        Self: Sized;
 }
 
 impl<'a, T: ContextCurrentState> HeadlessContextExt for crate::ContextBuilder<'a, T> {
@@ -349,7 +1005,7 @@ impl<'a, T: ContextCurrentState> HeadlessContextExt for crate::ContextBuilder<'a
     where
         Self: Sized,
     {
-        let crate::ContextBuilder { pf_reqs, gl_attr } = self;
+        let crate::ContextBuilder { pf_reqs, gl_attr, .. } = self;
         let gl_attr = gl_attr.map_sharing(|ctx| &ctx.context);
         Context::is_compatible(&gl_attr.sharing, ContextType::OsMesa)?;
         let gl_attr = gl_attr.clone().map_sharing(|ctx| match *ctx {
@@ -358,7 +1014,7 @@ impl<'a, T: ContextCurrentState> HeadlessContextExt for crate::ContextBuilder<'a
         });
         osmesa::OsMesaContext::new(&pf_reqs, &gl_attr, size)
             .map(Context::OsMesa)
-            .map(|context| crate::Context { context, phantom: PhantomData })
+            .map(|context| crate::Context::new(context, gl_attr.thread_affinity))
     }
 
     #[inline]
@@ -369,10 +1025,84 @@ impl<'a, T: ContextCurrentState> HeadlessContextExt for crate::ContextBuilder<'a
     where
         Self: Sized,
     {
-        let crate::ContextBuilder { pf_reqs, gl_attr } = self;
+        let crate::ContextBuilder { pf_reqs, gl_attr, .. } = self;
         let gl_attr = gl_attr.map_sharing(|ctx| &ctx.context);
         Context::new_headless_impl(el, &pf_reqs, &gl_attr, None)
-            .map(|context| crate::Context { context, phantom: PhantomData })
+            .map(|context| crate::Context::new(context, gl_attr.thread_affinity))
+    }
+
+    #[inline]
+    fn build_egl_stream(
+        self,
+        stream: EglStream,
+        size: dpi::PhysicalSize<u32>,
+    ) -> Result<crate::Context<NotCurrent>, CreationError>
+    where
+        Self: Sized,
+    {
+        let crate::ContextBuilder { pf_reqs, gl_attr, .. } = self;
+        let gl_attr = gl_attr.map_sharing(|ctx| &ctx.context);
+        Context::is_compatible(&gl_attr.sharing, ContextType::Egl)?;
+        let gl_attr = gl_attr.clone().map_sharing(|ctx| match *ctx {
+            Context::Egl(ref ctx) => ctx,
+            _ => unreachable!(),
+        });
+        let native_display = NativeDisplay::Device(stream.device() as *const _);
+        EglContext::new(&pf_reqs, &gl_attr, native_display, EglSurfaceType::Stream, |c, _| Ok(c[0]))
+            .and_then(|p| p.finish_stream(stream, size))
+            .map(Context::Egl)
+            .map(|context| crate::Context::new(context, gl_attr.thread_affinity))
+    }
+
+    #[inline]
+    fn build_compute<TE>(
+        self,
+        el: &EventLoopWindowTarget<TE>,
+    ) -> Result<crate::Context<NotCurrent>, CreationError>
+    where
+        Self: Sized,
+    {
+        let crate::ContextBuilder { gl_attr, .. } = self;
+        let gl_attr = gl_attr.map_sharing(|ctx| &ctx.context);
+        Context::is_compatible(&gl_attr.sharing, ContextType::Egl)?;
+        let gl_attr = gl_attr.clone().map_sharing(|ctx| match *ctx {
+            Context::Egl(ref ctx) => ctx,
+            _ => unreachable!(),
+        });
+        let native_display = Context::native_display(el)?;
+        EglContext::new_no_config(&gl_attr, native_display)
+            .and_then(|p| p.finish_surfaceless())
+            .map(Context::Egl)
+            .map(|context| crate::Context::new(context, gl_attr.thread_affinity))
+    }
+
+    #[inline]
+    fn build_egl_device(
+        self,
+        device: &EglDevice,
+        size: dpi::PhysicalSize<u32>,
+    ) -> Result<crate::Context<NotCurrent>, CreationError>
+    where
+        Self: Sized,
+    {
+        let crate::ContextBuilder { pf_reqs, gl_attr, .. } = self;
+        let gl_attr = gl_attr.map_sharing(|ctx| &ctx.context);
+        Context::is_compatible(&gl_attr.sharing, ContextType::Egl)?;
+        let gl_attr = gl_attr.clone().map_sharing(|ctx| match *ctx {
+            Context::Egl(ref ctx) => ctx,
+            _ => unreachable!(),
+        });
+        let native_display = NativeDisplay::Device(device.handle as *const _);
+        EglContext::new(
+            &pf_reqs,
+            &gl_attr,
+            native_display,
+            EglSurfaceType::PBuffer,
+            |c, _| Ok(c[0]),
+        )
+        .and_then(|p| p.finish_pbuffer(size))
+        .map(Context::Egl)
+        .map(|context| crate::Context::new(context, gl_attr.thread_affinity))
     }
 }
 
@@ -423,7 +1153,7 @@ impl<'a, T: ContextCurrentState> RawContextExt for crate::ContextBuilder<'a, T>
     where
         Self: Sized,
     {
-        let crate::ContextBuilder { pf_reqs, gl_attr } = self;
+        let crate::ContextBuilder { pf_reqs, gl_attr, .. } = self;
         let gl_attr = gl_attr.map_sharing(|ctx| &ctx.context);
         Context::is_compatible(&gl_attr.sharing, ContextType::Wayland)?;
         let gl_attr = gl_attr.clone().map_sharing(|ctx| match *ctx {
@@ -432,7 +1162,7 @@ impl<'a, T: ContextCurrentState> RawContextExt for crate::ContextBuilder<'a, T>
         });
         wayland::Context::new_raw_context(display_ptr, surface, width, height, &pf_reqs, &gl_attr)
             .map(Context::Wayland)
-            .map(|context| crate::Context { context, phantom: PhantomData })
+            .map(|context| crate::Context::new(context, gl_attr.thread_affinity))
             .map(|context| crate::RawContext { context, window: () })
     }
 
@@ -446,7 +1176,7 @@ impl<'a, T: ContextCurrentState> RawContextExt for crate::ContextBuilder<'a, T>
     where
         Self: Sized,
     {
-        let crate::ContextBuilder { pf_reqs, gl_attr } = self;
+        let crate::ContextBuilder { pf_reqs, gl_attr, .. } = self;
         let gl_attr = gl_attr.map_sharing(|ctx| &ctx.context);
         Context::is_compatible(&gl_attr.sharing, ContextType::X11)?;
         let gl_attr = gl_attr.clone().map_sharing(|ctx| match *ctx {
@@ -455,7 +1185,7 @@ impl<'a, T: ContextCurrentState> RawContextExt for crate::ContextBuilder<'a, T>
         });
         x11::Context::new_raw_context(xconn, xwin, &pf_reqs, &gl_attr)
             .map(Context::X11)
-            .map(|context| crate::Context { context, phantom: PhantomData })
+            .map(|context| crate::Context::new(context, gl_attr.thread_affinity))
             .map(|context| crate::RawContext { context, window: () })
     }
 }
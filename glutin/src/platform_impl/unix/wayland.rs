@@ -1,8 +1,11 @@
 #![cfg(feature = "wayland")]
 
-use crate::api::egl::{Context as EglContext, NativeDisplay, SurfaceType as EglSurfaceType};
+use crate::api::egl::{
+    self, Context as EglContext, EglSync, NativeDisplay, SurfaceType as EglSurfaceType,
+};
 use crate::{
-    ContextError, CreationError, GlAttributes, PixelFormat, PixelFormatRequirements, Rect,
+    ContextError, CreationError, CreationReport, GlAttributes, MultisampleResolve, PixelFormat,
+    PixelFormatRequirements, Rect, SwapBehavior, VSyncError, VSyncMode,
 };
 
 use crate::platform::unix::{EventLoopWindowTargetExtUnix, WindowExtUnix};
@@ -13,11 +16,13 @@ use winit::dpi;
 use winit::event_loop::EventLoopWindowTarget;
 use winit::window::{Window, WindowBuilder};
 
+use parking_lot::Mutex;
 use std::ops::Deref;
 use std::os::raw;
 use std::sync::Arc;
+use std::time::Duration;
 
-pub struct EglSurface(Arc<wayland_egl::WlEglSurface>);
+pub struct EglSurface(Arc<wayland_egl::WlEglSurface>, Mutex<(u32, u32)>);
 
 impl std::fmt::Debug for EglSurface {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -81,6 +86,42 @@ impl Context {
         }
     }
 
+    #[inline]
+    pub fn count_matching_formats<T>(
+        el: &EventLoopWindowTarget<T>,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<usize, CreationError> {
+        let gl_attr = gl_attr.clone().map_sharing(|c| &**c);
+        let display_ptr = el.wayland_display().unwrap() as *const _;
+        let native_display = NativeDisplay::Wayland(Some(display_ptr as *const _));
+        egl::count_matching_configs(pf_reqs, &gl_attr, native_display, EglSurfaceType::Window)
+    }
+
+    #[inline]
+    pub fn enumerate_pixel_formats<T>(
+        el: &EventLoopWindowTarget<T>,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<Vec<PixelFormat>, CreationError> {
+        let gl_attr = gl_attr.clone().map_sharing(|c| &**c);
+        let display_ptr = el.wayland_display().unwrap() as *const _;
+        let native_display = NativeDisplay::Wayland(Some(display_ptr as *const _));
+        egl::enumerate_configs(pf_reqs, &gl_attr, native_display, EglSurfaceType::Window)
+    }
+
+    #[inline]
+    pub fn query_swap_interval_range<T>(
+        el: &EventLoopWindowTarget<T>,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<(i32, i32), CreationError> {
+        let gl_attr = gl_attr.clone().map_sharing(|c| &**c);
+        let display_ptr = el.wayland_display().unwrap() as *const _;
+        let native_display = NativeDisplay::Wayland(Some(display_ptr as *const _));
+        egl::query_swap_interval_range(pf_reqs, &gl_attr, native_display, EglSurfaceType::Window)
+    }
+
     #[inline]
     pub fn new<T>(
         wb: WindowBuilder,
@@ -115,18 +156,20 @@ impl Context {
         pf_reqs: &PixelFormatRequirements,
         gl_attr: &GlAttributes<&Context>,
     ) -> Result<Self, CreationError> {
-        let egl_surface = unsafe {
-            wayland_egl::WlEglSurface::new_from_raw(surface as *mut _, width as i32, height as i32)
-        };
-        let context = {
+        let (context, egl_surface) = {
             let gl_attr = gl_attr.clone().map_sharing(|c| &**c);
             let native_display = NativeDisplay::Wayland(Some(display_ptr as *const _));
             EglContext::new(pf_reqs, &gl_attr, native_display, EglSurfaceType::Window, |c, _| {
                 Ok(c[0])
             })
-            .and_then(|p| p.finish(egl_surface.ptr() as *const _))?
+            // `ContextWrapper` drops the window after the context, and
+            // `Context::Windowed` drops the `EglSurface` after the context.
+            .and_then(|p| unsafe { p.finish_wayland(surface, width, height) })?
         };
-        let context = Context::Windowed(context, EglSurface(Arc::new(egl_surface)));
+        let context = Context::Windowed(
+            context,
+            EglSurface(Arc::new(egl_surface), Mutex::new((width, height))),
+        );
         Ok(context)
     }
 
@@ -135,6 +178,11 @@ impl Context {
         (**self).make_current()
     }
 
+    #[inline]
+    pub unsafe fn make_current_asymmetric(&self, read: &Context) -> Result<(), ContextError> {
+        (**self).make_current_asymmetric(read)
+    }
+
     #[inline]
     pub unsafe fn make_not_current(&self) -> Result<(), ContextError> {
         (**self).make_not_current()
@@ -145,6 +193,11 @@ impl Context {
         (**self).is_current()
     }
 
+    #[inline]
+    pub fn bind_api(&self) -> Result<(), ContextError> {
+        (**self).bind_api()
+    }
+
     #[inline]
     pub fn get_api(&self) -> crate::Api {
         (**self).get_api()
@@ -173,16 +226,90 @@ impl Context {
     #[inline]
     pub fn resize(&self, width: u32, height: u32) {
         match self {
-            Context::Windowed(_, surface) => surface.0.resize(width as i32, height as i32, 0, 0),
+            Context::Windowed(_, surface) => {
+                surface.0.resize(width as i32, height as i32, 0, 0);
+                *surface.1.lock() = (width, height);
+            }
             _ => unreachable!(),
         }
     }
 
+    /// Resizes the `wl_egl_window` to `width`x`height` unless it already has
+    /// that size, returning whether it did.
+    #[inline]
+    pub fn update_surface_size(&self, width: u32, height: u32) -> bool {
+        match self {
+            Context::Windowed(_, surface) => {
+                let mut size = surface.1.lock();
+                if *size == (width, height) {
+                    return false;
+                }
+                surface.0.resize(width as i32, height as i32, 0, 0);
+                *size = (width, height);
+                true
+            }
+            _ => false,
+        }
+    }
+
     #[inline]
     pub fn get_proc_address(&self, addr: &str) -> *const core::ffi::c_void {
         (**self).get_proc_address(addr)
     }
 
+    #[inline]
+    pub fn finish_with_timeout(&self, timeout: Duration) -> Result<bool, ContextError> {
+        (**self).finish_with_timeout(timeout)
+    }
+
+    #[inline]
+    pub fn create_fence(&self) -> Result<EglSync, ContextError> {
+        (**self).create_fence()
+    }
+
+    #[inline]
+    pub fn swap_buffers_after(&self, sync: &EglSync, timeout_ns: u64) -> Result<(), ContextError> {
+        (**self).swap_buffers_after(sync, timeout_ns)
+    }
+
+    #[inline]
+    pub fn set_swap_behavior_preserved(&self, preserved: bool) -> Result<(), ContextError> {
+        (**self).set_swap_behavior_preserved(preserved)
+    }
+
+    #[inline]
+    pub fn set_presentation_time(&self, nanos: i64) -> Result<(), ContextError> {
+        (**self).set_presentation_time(nanos)
+    }
+
+    #[inline]
+    pub fn recreate(&mut self) -> Result<(), CreationError> {
+        match self {
+            Context::Windowed(ctx, _) | Context::PBuffer(ctx) | Context::Surfaceless(ctx) => {
+                ctx.recreate()
+            }
+        }
+    }
+
+    #[inline]
+    pub fn recreate_surface(&mut self) -> Result<(), CreationError> {
+        match self {
+            Context::Windowed(ctx, _) | Context::PBuffer(ctx) | Context::Surfaceless(ctx) => {
+                ctx.recreate_surface()
+            }
+        }
+    }
+
+    #[inline]
+    pub fn swap_behavior(&self) -> Result<SwapBehavior, ContextError> {
+        (**self).swap_behavior()
+    }
+
+    #[inline]
+    pub fn multisample_resolve(&self) -> Result<MultisampleResolve, ContextError> {
+        (**self).multisample_resolve()
+    }
+
     #[inline]
     pub fn swap_buffers(&self) -> Result<(), ContextError> {
         (**self).swap_buffers()
@@ -202,4 +329,19 @@ impl Context {
     pub fn get_pixel_format(&self) -> PixelFormat {
         (**self).get_pixel_format()
     }
+
+    #[inline]
+    pub fn creation_report(&self) -> CreationReport {
+        (**self).creation_report()
+    }
+
+    #[inline]
+    pub fn diagnostic_details(&self) -> Vec<(&'static str, String)> {
+        (**self).diagnostic_details()
+    }
+
+    #[inline]
+    pub fn surface_size(&self) -> Option<dpi::PhysicalSize<u32>> {
+        (**self).surface_size()
+    }
 }
@@ -1,15 +1,16 @@
 #![cfg(feature = "x11")]
 
 use crate::api::egl::{
-    self, Context as EglContext, NativeDisplay, SurfaceType as EglSurfaceType, EGL,
+    self, Context as EglContext, EglSync, NativeDisplay, SurfaceType as EglSurfaceType, EGL,
 };
 use crate::api::glx::{Context as GlxContext, GLX};
 use crate::platform::unix::x11::XConnection;
 use crate::platform::unix::{EventLoopWindowTargetExtUnix, WindowBuilderExtUnix, WindowExtUnix};
 use crate::platform_impl::x11_utils;
 use crate::{
-    Api, ContextError, CreationError, GlAttributes, GlRequest, PixelFormat,
-    PixelFormatRequirements, Rect,
+    Api, ContextError, CreationError, CreationReport, FrameTimestamps, GlAttributes, GlRequest,
+    MultisampleResolve, PixelFormat, PixelFormatRequirements, Priority, Rect, ShareToken,
+    SwapBehavior, VSyncError, VSyncMode,
 };
 
 use glutin_glx_sys as ffi;
@@ -21,6 +22,7 @@ use winit::window::{Window, WindowBuilder};
 use std::ops::{Deref, DerefMut};
 use std::os::raw;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub mod utils;
 
@@ -83,19 +85,20 @@ impl DerefMut for Context {
 unsafe impl Send for Context {}
 unsafe impl Sync for Context {}
 
-// FIXME:
-// When using egl, all the configs will not support transparency, even if
-// transparency does work with glx.
-//
-// https://bugs.freedesktop.org/show_bug.cgi?id=67676<Paste>
-// I'm on a patch.
+/// Picks the first config whose visual satisfies `pf_reqs.x11_visual_xid` and,
+/// if `transparent` is `Some(true)`, is a 32-bit ARGB visual.
+///
+/// A transparent window can't be composited without an alpha channel in its
+/// visual, so this fails rather than settling for an opaque one. Old Mesa
+/// versions expose no such visual through EGL, in which case the caller falls
+/// back to GLX.
 pub fn select_config<T, F>(
     xconn: &Arc<XConnection>,
     transparent: Option<bool>,
     pf_reqs: &PixelFormatRequirements,
     config_ids: Vec<T>,
     mut convert_to_xvisualinfo: F,
-) -> Result<(T, ffi::XVisualInfo), ()>
+) -> Result<(T, ffi::XVisualInfo), CreationError>
 where
     F: FnMut(&T) -> Option<ffi::XVisualInfo>,
 {
@@ -146,14 +149,17 @@ where
 
     match lacks_what {
         Some(Ok(())) => (),
-        Some(Err(Lacks::Transparency)) => log::warn!(
-            "Glutin could not a find fb config with an alpha mask. Transparency may be broken."
-        ),
+        Some(Err(Lacks::Transparency)) => {
+            return Err(CreationError::NotSupported(
+                "no config with a 32-bit ARGB visual, required for a transparent window"
+                    .to_string(),
+            ))
+        }
         Some(Err(Lacks::Xid)) => panic!(),
-        None => unreachable!(),
+        None => return Err(CreationError::NoAvailablePixelFormat),
     }
 
-    chosen_config_id.ok_or(())
+    chosen_config_id.ok_or(CreationError::NoAvailablePixelFormat)
 }
 
 impl Context {
@@ -303,7 +309,8 @@ impl Context {
         Ok(match gl_attr.version {
             GlRequest::Latest
             | GlRequest::Specific(Api::OpenGl, _)
-            | GlRequest::GlThenGles { .. } => {
+            | GlRequest::GlThenGles { .. }
+            | GlRequest::GlesThenGl { .. } => {
                 // GLX should be preferred over EGL, otherwise crashes may occur
                 // on X11 – issue #314
                 //
@@ -356,6 +363,14 @@ impl Context {
                     }
                 }
 
+                // OpenGL ES and config-less contexts are only reachable through
+                // EGL, so try it first and leave GLX for the fallback.
+                let prefer_egl = match gl_attr.version {
+                    _ if gl_attr.no_config_context => !force_prefer_unless_only,
+                    GlRequest::GlesThenGl { .. } => !force_prefer_unless_only,
+                    _ => prefer_egl,
+                };
+
                 // force_prefer_unless_only does what it says on the tin, it
                 // forces only the preferred method to happen unless it's the
                 // only method available.
@@ -429,6 +444,72 @@ impl Context {
         })
     }
 
+    #[inline]
+    pub fn count_matching_formats<T>(
+        el: &EventLoopWindowTarget<T>,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<usize, CreationError> {
+        let xconn = match el.xlib_xconnection() {
+            Some(xconn) => xconn,
+            None => {
+                return Err(CreationError::NoBackendAvailable(Box::new(NoX11Connection)));
+            }
+        };
+
+        if EGL.is_none() {
+            return Err(CreationError::NotSupported("libEGL not present".to_string()));
+        }
+
+        let gl_attr = gl_attr.clone().set_sharing(None);
+        let native_display = NativeDisplay::X11(Some(xconn.display as *const _));
+        egl::count_matching_configs(pf_reqs, &gl_attr, native_display, EglSurfaceType::Window)
+    }
+
+    #[inline]
+    pub fn enumerate_pixel_formats<T>(
+        el: &EventLoopWindowTarget<T>,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<Vec<PixelFormat>, CreationError> {
+        let xconn = match el.xlib_xconnection() {
+            Some(xconn) => xconn,
+            None => {
+                return Err(CreationError::NoBackendAvailable(Box::new(NoX11Connection)));
+            }
+        };
+
+        if EGL.is_none() {
+            return Err(CreationError::NotSupported("libEGL not present".to_string()));
+        }
+
+        let gl_attr = gl_attr.clone().set_sharing(None);
+        let native_display = NativeDisplay::X11(Some(xconn.display as *const _));
+        egl::enumerate_configs(pf_reqs, &gl_attr, native_display, EglSurfaceType::Window)
+    }
+
+    #[inline]
+    pub fn query_swap_interval_range<T>(
+        el: &EventLoopWindowTarget<T>,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<(i32, i32), CreationError> {
+        let xconn = match el.xlib_xconnection() {
+            Some(xconn) => xconn,
+            None => {
+                return Err(CreationError::NoBackendAvailable(Box::new(NoX11Connection)));
+            }
+        };
+
+        if EGL.is_none() {
+            return Err(CreationError::NotSupported("libEGL not present".to_string()));
+        }
+
+        let gl_attr = gl_attr.clone().set_sharing(None);
+        let native_display = NativeDisplay::X11(Some(xconn.display as *const _));
+        egl::query_swap_interval_range(pf_reqs, &gl_attr, native_display, EglSurfaceType::Window)
+    }
+
     #[inline]
     pub fn new<T>(
         wb: WindowBuilder,
@@ -475,23 +556,13 @@ impl Context {
             Some(wb.transparent()),
         )?;
 
-        // getting the `visual_infos` (a struct that contains information about
-        // the visual to use)
-        let visual_infos = match context {
-            Prototype::Glx(ref p) => *p.get_visual_infos(),
-            Prototype::Egl(ref p) => {
-                utils::get_visual_info_from_xid(&xconn, p.get_native_visual_id() as ffi::VisualID)
-            }
-        };
-
-        let win =
-            wb.with_x11_visual(&visual_infos as *const _).with_x11_screen(screen_id).build(el)?;
+        let win = Self::build_window(&xconn, screen_id, wb, el, &context)?;
 
         let xwin = win.xlib_window().unwrap();
         // finish creating the OpenGL context
         let context = match context {
             Prototype::Glx(ctx) => X11Context::Glx(ctx.finish(xwin)?),
-            Prototype::Egl(ctx) => X11Context::Egl(ctx.finish(xwin as _)?),
+            Prototype::Egl(ctx) => X11Context::Egl(ctx.finish_x11(xwin)?),
         };
 
         let context = Context::Windowed(ContextInner { context });
@@ -499,6 +570,77 @@ impl Context {
         Ok((win, context))
     }
 
+    /// Builds a window with a visual matching the pixel format requirements,
+    /// so that a context can later be created for it with
+    /// [`new_raw_context()`][Self::new_raw_context()].
+    #[inline]
+    pub fn new_window<T>(
+        wb: WindowBuilder,
+        el: &EventLoopWindowTarget<T>,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<Window, CreationError> {
+        Self::try_then_fallback(|fallback| {
+            Self::new_window_impl(wb.clone(), el, pf_reqs, gl_attr, fallback)
+        })
+    }
+
+    fn new_window_impl<T>(
+        wb: WindowBuilder,
+        el: &EventLoopWindowTarget<T>,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+        fallback: bool,
+    ) -> Result<Window, CreationError> {
+        let xconn = match el.xlib_xconnection() {
+            Some(xconn) => xconn,
+            None => {
+                return Err(CreationError::NoBackendAvailable(Box::new(NoX11Connection)));
+            }
+        };
+
+        let screen_id = unsafe { (xconn.xlib.XDefaultScreen)(xconn.display) };
+
+        let mut builder_glx_u = None;
+        let mut builder_egl_u = None;
+
+        // Choosing a config doesn't create a context, so this only serves to
+        // find the visual.
+        let context = Self::new_first_stage(
+            &xconn,
+            pf_reqs,
+            gl_attr,
+            screen_id,
+            &mut builder_glx_u,
+            &mut builder_egl_u,
+            EglSurfaceType::Window,
+            fallback,
+            fallback,
+            Some(wb.transparent()),
+        )?;
+
+        Self::build_window(&xconn, screen_id, wb, el, &context)
+    }
+
+    fn build_window<T>(
+        xconn: &Arc<XConnection>,
+        screen_id: raw::c_int,
+        wb: WindowBuilder,
+        el: &EventLoopWindowTarget<T>,
+        context: &Prototype<'_>,
+    ) -> Result<Window, CreationError> {
+        // getting the `visual_infos` (a struct that contains information about
+        // the visual to use)
+        let visual_infos = match context {
+            Prototype::Glx(ref p) => *p.get_visual_infos(),
+            Prototype::Egl(ref p) => {
+                utils::get_visual_info_from_xid(xconn, p.get_native_visual_id() as ffi::VisualID)
+            }
+        };
+
+        Ok(wb.with_x11_visual(&visual_infos as *const _).with_x11_screen(screen_id).build(el)?)
+    }
+
     #[inline]
     pub fn new_raw_context(
         xconn: Arc<XConnection>,
@@ -565,7 +707,7 @@ impl Context {
         // finish creating the OpenGL context
         let context = match context {
             Prototype::Glx(ctx) => X11Context::Glx(ctx.finish(xwin)?),
-            Prototype::Egl(ctx) => X11Context::Egl(ctx.finish(xwin as _)?),
+            Prototype::Egl(ctx) => X11Context::Egl(ctx.finish_x11(xwin)?),
         };
 
         let context = Context::Windowed(ContextInner { context });
@@ -581,6 +723,16 @@ impl Context {
         }
     }
 
+    #[inline]
+    pub unsafe fn make_current_asymmetric(&self, read: &Context) -> Result<(), ContextError> {
+        match (&self.context, &read.context) {
+            (X11Context::Egl(ref ctx), X11Context::Egl(ref read)) => {
+                ctx.make_current_asymmetric(read)
+            }
+            _ => Err(ContextError::FunctionUnavailable),
+        }
+    }
+
     #[inline]
     pub unsafe fn make_not_current(&self) -> Result<(), ContextError> {
         match self.context {
@@ -597,6 +749,14 @@ impl Context {
         }
     }
 
+    #[inline]
+    pub fn bind_api(&self) -> Result<(), ContextError> {
+        match self.context {
+            X11Context::Glx(_) => Ok(()),
+            X11Context::Egl(ref ctx) => ctx.bind_api(),
+        }
+    }
+
     #[inline]
     pub fn get_api(&self) -> Api {
         match self.context {
@@ -621,6 +781,30 @@ impl Context {
         }
     }
 
+    #[inline]
+    pub fn get_vsync_mode(&self) -> Option<VSyncMode> {
+        match self.context {
+            X11Context::Egl(ref ctx) => Some(ctx.get_vsync_mode()),
+            X11Context::Glx(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn vsync_range(&self) -> Option<(i32, i32)> {
+        match self.context {
+            X11Context::Egl(ref ctx) => Some(ctx.vsync_range()),
+            X11Context::Glx(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn context_priority(&self) -> Option<Priority> {
+        match self.context {
+            X11Context::Egl(ref ctx) => ctx.context_priority(),
+            X11Context::Glx(_) => None,
+        }
+    }
+
     #[inline]
     pub unsafe fn raw_handle(&self) -> &X11Context {
         &self.context
@@ -634,6 +818,73 @@ impl Context {
         }
     }
 
+    #[inline]
+    pub fn current_surface(&self, read: bool) -> Option<*const raw::c_void> {
+        match self.context {
+            X11Context::Egl(ref ctx) => ctx.current_surface(read),
+            X11Context::Glx(_) => None,
+        }
+    }
+
+    #[inline]
+    pub unsafe fn raw_surface(&self) -> Option<*const raw::c_void> {
+        match self.context {
+            X11Context::Egl(ref ctx) => ctx.raw_surface(),
+            X11Context::Glx(_) => None,
+        }
+    }
+
+    #[inline]
+    pub unsafe fn replace_surface(
+        &self,
+        new_surface: *const raw::c_void,
+    ) -> Result<(), ContextError> {
+        match self.context {
+            X11Context::Egl(ref ctx) => ctx.replace_surface(new_surface),
+            X11Context::Glx(_) => Err(ContextError::FunctionUnavailable),
+        }
+    }
+
+    #[inline]
+    pub fn share_token(&self) -> Option<ShareToken> {
+        match self.context {
+            X11Context::Egl(ref ctx) => Some(ctx.share_token()),
+            X11Context::Glx(_) => None,
+        }
+    }
+
+    #[inline]
+    pub unsafe fn raw_config(&self) -> Option<(*const raw::c_void, *const raw::c_void)> {
+        match self.context {
+            X11Context::Egl(ref ctx) => Some(ctx.raw_config()),
+            X11Context::Glx(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn config_id(&self) -> Option<i32> {
+        match self.context {
+            X11Context::Egl(ref ctx) => Some(ctx.config_id()),
+            X11Context::Glx(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn egl_extensions(&self) -> Option<&[String]> {
+        match self.context {
+            X11Context::Egl(ref ctx) => Some(ctx.egl_extensions()),
+            X11Context::Glx(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn egl_version(&self) -> Option<(i32, i32)> {
+        match self.context {
+            X11Context::Egl(ref ctx) => Some(ctx.egl_version()),
+            X11Context::Glx(_) => None,
+        }
+    }
+
     #[inline]
     pub fn get_proc_address(&self, addr: &str) -> *const core::ffi::c_void {
         match self.context {
@@ -643,13 +894,129 @@ impl Context {
     }
 
     #[inline]
-    pub fn buffer_age(&self) -> u32 {
+    pub fn finish_with_timeout(&self, timeout: Duration) -> Result<bool, ContextError> {
+        match self.context {
+            X11Context::Glx(_) => Err(ContextError::FunctionUnavailable),
+            X11Context::Egl(ref ctx) => ctx.finish_with_timeout(timeout),
+        }
+    }
+
+    #[inline]
+    pub fn create_fence(&self) -> Result<EglSync, ContextError> {
+        match self.context {
+            X11Context::Glx(_) => Err(ContextError::FunctionUnavailable),
+            X11Context::Egl(ref ctx) => ctx.create_fence(),
+        }
+    }
+
+    #[inline]
+    pub fn swap_buffers_after(&self, sync: &EglSync, timeout_ns: u64) -> Result<(), ContextError> {
+        match self.context {
+            X11Context::Glx(_) => Err(ContextError::FunctionUnavailable),
+            X11Context::Egl(ref ctx) => ctx.swap_buffers_after(sync, timeout_ns),
+        }
+    }
+
+    #[inline]
+    pub fn set_swap_behavior_preserved(&self, preserved: bool) -> Result<(), ContextError> {
+        match self.context {
+            X11Context::Glx(_) => Err(ContextError::FunctionUnavailable),
+            X11Context::Egl(ref ctx) => ctx.set_swap_behavior_preserved(preserved),
+        }
+    }
+
+    #[inline]
+    pub fn set_presentation_time(&self, nanos: i64) -> Result<(), ContextError> {
+        match self.context {
+            X11Context::Glx(_) => Err(ContextError::FunctionUnavailable),
+            X11Context::Egl(ref ctx) => ctx.set_presentation_time(nanos),
+        }
+    }
+
+    #[inline]
+    pub fn next_frame_id(&self) -> Result<u64, ContextError> {
+        match self.context {
+            X11Context::Egl(ref ctx) => ctx.next_frame_id(),
+            X11Context::Glx(_) => Err(ContextError::FunctionUnavailable),
+        }
+    }
+
+    #[inline]
+    pub fn frame_timestamps(&self, frame_id: u64) -> Result<FrameTimestamps, ContextError> {
+        match self.context {
+            X11Context::Egl(ref ctx) => ctx.frame_timestamps(frame_id),
+            X11Context::Glx(_) => Err(ContextError::FunctionUnavailable),
+        }
+    }
+
+    #[inline]
+    pub fn recreate(&mut self) -> Result<(), CreationError> {
+        match self.context {
+            X11Context::Glx(_) => {
+                Err(CreationError::NotSupported("recreating GLX contexts".to_string()))
+            }
+            X11Context::Egl(ref mut ctx) => ctx.recreate(),
+        }
+    }
+
+    #[inline]
+    pub fn recreate_surface(&mut self) -> Result<(), CreationError> {
+        match self.context {
+            X11Context::Glx(_) => {
+                Err(CreationError::NotSupported("recreating GLX contexts".to_string()))
+            }
+            X11Context::Egl(ref mut ctx) => ctx.recreate_surface(),
+        }
+    }
+
+    #[inline]
+    pub fn swap_behavior(&self) -> Result<SwapBehavior, ContextError> {
+        match self.context {
+            X11Context::Glx(_) => Err(ContextError::FunctionUnavailable),
+            X11Context::Egl(ref ctx) => ctx.swap_behavior(),
+        }
+    }
+
+    #[inline]
+    pub fn multisample_resolve(&self) -> Result<MultisampleResolve, ContextError> {
+        match self.context {
+            X11Context::Glx(_) => Err(ContextError::FunctionUnavailable),
+            X11Context::Egl(ref ctx) => ctx.multisample_resolve(),
+        }
+    }
+
+    #[inline]
+    pub fn buffer_age_supported(&self) -> bool {
+        match self.context {
+            X11Context::Glx(ref ctx) => ctx.buffer_age_supported(),
+            X11Context::Egl(ref ctx) => ctx.buffer_age_supported(),
+        }
+    }
+
+    #[inline]
+    pub fn supports_surfaceless(&self) -> bool {
+        match self.context {
+            X11Context::Glx(_) => false,
+            X11Context::Egl(ref ctx) => ctx.supports_surfaceless(),
+        }
+    }
+
+    #[inline]
+    pub fn buffer_age(&self) -> Option<u32> {
         match self.context {
             X11Context::Glx(ref ctx) => ctx.buffer_age(),
             X11Context::Egl(ref ctx) => ctx.buffer_age(),
         }
     }
 
+    #[inline]
+    pub fn would_block_on_swap(&self) -> bool {
+        match self.context {
+            X11Context::Glx(_) => false,
+            X11Context::Egl(ref ctx) => ctx.would_block_on_swap(),
+        }
+    }
+
     #[inline]
     pub fn swap_buffers(&self) -> Result<(), ContextError> {
         match self.context {
@@ -683,4 +1050,36 @@ impl Context {
             X11Context::Egl(ref ctx) => ctx.get_pixel_format(),
         }
     }
+
+    #[inline]
+    pub fn creation_report(&self) -> CreationReport {
+        match self.context {
+            X11Context::Glx(ref ctx) => ctx.creation_report(),
+            X11Context::Egl(ref ctx) => ctx.creation_report(),
+        }
+    }
+
+    #[inline]
+    pub fn diagnostic_details(&self) -> Vec<(&'static str, String)> {
+        match self.context {
+            X11Context::Glx(_) => Vec::new(),
+            X11Context::Egl(ref ctx) => ctx.diagnostic_details(),
+        }
+    }
+
+    #[inline]
+    pub fn surface_size(&self) -> Option<dpi::PhysicalSize<u32>> {
+        match self.context {
+            X11Context::Glx(ref ctx) => ctx.surface_size(),
+            X11Context::Egl(ref ctx) => ctx.surface_size(),
+        }
+    }
+
+    #[inline]
+    pub fn resize_pbuffer(&self, size: dpi::PhysicalSize<u32>) -> Result<(), ContextError> {
+        match self.context {
+            X11Context::Glx(_) => Err(ContextError::FunctionUnavailable),
+            X11Context::Egl(ref ctx) => ctx.resize_pbuffer(size),
+        }
+    }
 }
@@ -49,6 +49,10 @@ pub fn examine_visual_info(
 
     unsafe {
         if want_transparency {
+            if visual_infos.depth != 32 {
+                return Err(Lacks::Transparency);
+            }
+
             let pict_format = (xconn.xrender.XRenderFindVisualFormat)(
                 xconn.display as *mut _,
                 visual_infos.visual,
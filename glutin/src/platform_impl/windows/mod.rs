@@ -1,11 +1,15 @@
 #![cfg(target_os = "windows")]
 
 use crate::{
-    Api, ContextCurrentState, ContextError, CreationError, GlAttributes, GlRequest, NotCurrent,
-    PixelFormat, PixelFormatRequirements, Rect, VSyncMode, VSyncError,
+    Api, ContextCurrentState, ContextError, CreationError, CreationReport, FrameTimestamps,
+    GlAttributes, GlRequest, MultisampleResolve, NotCurrent, PixelFormat, PixelFormatRequirements,
+    Priority, Rect, ShareToken, SwapBehavior, VSyncError, VSyncMode,
 };
 
-use crate::api::egl::{Context as EglContext, NativeDisplay, SurfaceType as EglSurfaceType, EGL};
+pub use crate::api::egl::EglSync;
+use crate::api::egl::{
+    self, Context as EglContext, NativeDisplay, SurfaceType as EglSurfaceType, EGL,
+};
 use crate::api::wgl::Context as WglContext;
 use crate::platform::windows::WindowExtWindows;
 
@@ -17,8 +21,8 @@ use winit::event_loop::EventLoopWindowTarget;
 use winit::platform::windows::WindowBuilderExtWindows;
 use winit::window::{Window, WindowBuilder};
 
-use std::marker::PhantomData;
 use std::os::raw;
+use std::time::Duration;
 
 /// Context handles available on Windows.
 #[derive(Clone, Debug)]
@@ -37,6 +41,8 @@ pub enum Context {
     HiddenWindowWgl(Window, WglContext),
     /// An EGL pbuffer.
     EglPbuffer(EglContext),
+    /// An EGL context without any surface.
+    EglSurfaceless(EglContext),
 }
 
 unsafe impl Send for Context {}
@@ -58,6 +64,25 @@ impl Context {
         Ok((win, ctx))
     }
 
+    #[inline]
+    pub fn new_window<T>(
+        wb: WindowBuilder,
+        el: &EventLoopWindowTarget<T>,
+        _pf_reqs: &PixelFormatRequirements,
+        _gl_attr: &GlAttributes<&Self>,
+    ) -> Result<Window, CreationError> {
+        Ok(wb.build(el)?)
+    }
+
+    #[inline]
+    pub fn new_for_window(
+        win: &Window,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Self>,
+    ) -> Result<Self, CreationError> {
+        Self::new_raw_context(win.hwnd() as HWND, pf_reqs, gl_attr)
+    }
+
     #[inline]
     pub fn new_raw_context(
         hwnd: HWND,
@@ -65,7 +90,7 @@ impl Context {
         gl_attr: &GlAttributes<&Self>,
     ) -> Result<Self, CreationError> {
         match gl_attr.version {
-            GlRequest::Specific(Api::OpenGlEs, (_major, _minor)) => {
+            GlRequest::Specific(Api::OpenGlEs, (_major, _minor)) | GlRequest::GlesThenGl { .. } => {
                 match (gl_attr.sharing, &*EGL) {
                     // We must use WGL.
                     (Some(&Context::HiddenWindowWgl(_, _)), _)
@@ -84,6 +109,7 @@ impl Context {
                         let gl_attr_egl = gl_attr.clone().map_sharing(|ctx| match *ctx {
                             Context::Egl(ref c)
                             | Context::EglPbuffer(ref c)
+                            | Context::EglSurfaceless(ref c)
                             | Context::HiddenWindowEgl(_, ref c) => c,
                             _ => unreachable!(),
                         });
@@ -132,6 +158,90 @@ impl Context {
         }
     }
 
+    #[inline]
+    pub unsafe fn new_raw_egl_context(
+        native_display: NativeDisplay,
+        nwin: ffi::EGLNativeWindowType,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Self>,
+    ) -> Result<Self, CreationError> {
+        if EGL.is_none() {
+            return Err(CreationError::NotSupported("libEGL not present".to_string()));
+        }
+        if let Some(&Context::Wgl(_)) | Some(&Context::HiddenWindowWgl(_, _)) = gl_attr.sharing {
+            return Err(CreationError::PlatformSpecific(
+                "Cannot share an EGL context with a WGL context".to_string(),
+            ));
+        }
+        let gl_attr = gl_attr.clone().map_sharing(|ctx| match *ctx {
+            Context::Egl(ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c)
+            | Context::HiddenWindowEgl(_, ref c) => c,
+            _ => unreachable!(),
+        });
+        EglContext::new(pf_reqs, &gl_attr, native_display, EglSurfaceType::Window, |c, _| Ok(c[0]))
+            .and_then(|p| p.finish(nwin))
+            .map(Context::Egl)
+    }
+
+    #[inline]
+    pub fn count_matching_formats<T>(
+        _el: &EventLoopWindowTarget<T>,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<usize, CreationError> {
+        if EGL.is_none() {
+            return Err(CreationError::NotSupported("libEGL not present".to_string()));
+        }
+
+        let gl_attr = gl_attr.clone().set_sharing(None);
+        egl::count_matching_configs(
+            pf_reqs,
+            &gl_attr,
+            NativeDisplay::Other(Some(std::ptr::null())),
+            EglSurfaceType::Window,
+        )
+    }
+
+    #[inline]
+    pub fn enumerate_pixel_formats<T>(
+        _el: &EventLoopWindowTarget<T>,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<Vec<PixelFormat>, CreationError> {
+        if EGL.is_none() {
+            return Err(CreationError::NotSupported("libEGL not present".to_string()));
+        }
+
+        let gl_attr = gl_attr.clone().set_sharing(None);
+        egl::enumerate_configs(
+            pf_reqs,
+            &gl_attr,
+            NativeDisplay::Other(Some(std::ptr::null())),
+            EglSurfaceType::Window,
+        )
+    }
+
+    #[inline]
+    pub fn query_swap_interval_range<T>(
+        _el: &EventLoopWindowTarget<T>,
+        pf_reqs: &PixelFormatRequirements,
+        gl_attr: &GlAttributes<&Context>,
+    ) -> Result<(i32, i32), CreationError> {
+        if EGL.is_none() {
+            return Err(CreationError::NotSupported("libEGL not present".to_string()));
+        }
+
+        let gl_attr = gl_attr.clone().set_sharing(None);
+        egl::query_swap_interval_range(
+            pf_reqs,
+            &gl_attr,
+            NativeDisplay::Other(Some(std::ptr::null())),
+            EglSurfaceType::Window,
+        )
+    }
+
     #[inline]
     pub fn new_headless<T>(
         el: &EventLoopWindowTarget<T>,
@@ -145,10 +255,12 @@ impl Context {
             (None, Some(_))
             | (Some(&Context::Egl(_)), Some(_))
             | (Some(&Context::HiddenWindowEgl(_, _)), Some(_))
-            | (Some(&Context::EglPbuffer(_)), Some(_)) => {
+            | (Some(&Context::EglPbuffer(_)), Some(_))
+            | (Some(&Context::EglSurfaceless(_)), Some(_)) => {
                 let gl_attr_egl = gl_attr.clone().map_sharing(|ctx| match *ctx {
                     Context::Egl(ref c)
                     | Context::EglPbuffer(ref c)
+                    | Context::EglSurfaceless(ref c)
                     | Context::HiddenWindowEgl(_, ref c) => c,
                     _ => unreachable!(),
                 });
@@ -187,13 +299,36 @@ impl Context {
         // Method is for API consistency.
     }
 
+    #[inline]
+    pub fn update_surface_size(&self, _width: u32, _height: u32) -> Result<bool, ContextError> {
+        Ok(false)
+    }
+
     #[inline]
     pub unsafe fn make_current(&self) -> Result<(), ContextError> {
         match *self {
             Context::Wgl(ref c) | Context::HiddenWindowWgl(_, ref c) => c.make_current(),
             Context::Egl(ref c)
             | Context::HiddenWindowEgl(_, ref c)
-            | Context::EglPbuffer(ref c) => c.make_current(),
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.make_current(),
+        }
+    }
+
+    #[inline]
+    pub unsafe fn make_current_asymmetric(&self, read: &Context) -> Result<(), ContextError> {
+        match (self, read) {
+            (
+                Context::Egl(ref c)
+                | Context::HiddenWindowEgl(_, ref c)
+                | Context::EglPbuffer(ref c)
+                | Context::EglSurfaceless(ref c),
+                Context::Egl(ref read)
+                | Context::HiddenWindowEgl(_, ref read)
+                | Context::EglPbuffer(ref read)
+                | Context::EglSurfaceless(ref read),
+            ) => c.make_current_asymmetric(read),
+            _ => Err(ContextError::FunctionUnavailable),
         }
     }
 
@@ -203,7 +338,8 @@ impl Context {
             Context::Wgl(ref c) | Context::HiddenWindowWgl(_, ref c) => c.make_not_current(),
             Context::Egl(ref c)
             | Context::HiddenWindowEgl(_, ref c)
-            | Context::EglPbuffer(ref c) => c.make_not_current(),
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.make_not_current(),
         }
     }
 
@@ -213,7 +349,19 @@ impl Context {
             Context::Wgl(ref c) | Context::HiddenWindowWgl(_, ref c) => c.is_current(),
             Context::Egl(ref c)
             | Context::HiddenWindowEgl(_, ref c)
-            | Context::EglPbuffer(ref c) => c.is_current(),
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.is_current(),
+        }
+    }
+
+    #[inline]
+    pub fn bind_api(&self) -> Result<(), ContextError> {
+        match *self {
+            Context::Wgl(_) | Context::HiddenWindowWgl(_, _) => Ok(()),
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.bind_api(),
         }
     }
 
@@ -223,15 +371,195 @@ impl Context {
             Context::Wgl(ref c) | Context::HiddenWindowWgl(_, ref c) => c.get_proc_address(addr),
             Context::Egl(ref c)
             | Context::HiddenWindowEgl(_, ref c)
-            | Context::EglPbuffer(ref c) => c.get_proc_address(addr),
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.get_proc_address(addr),
+        }
+    }
+
+    #[inline]
+    pub fn finish_with_timeout(&self, timeout: Duration) -> Result<bool, ContextError> {
+        match *self {
+            Context::Wgl(_) | Context::HiddenWindowWgl(_, _) => {
+                Err(ContextError::FunctionUnavailable)
+            }
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.finish_with_timeout(timeout),
+        }
+    }
+
+    #[inline]
+    pub fn create_fence(&self) -> Result<EglSync, ContextError> {
+        match *self {
+            Context::Wgl(_) | Context::HiddenWindowWgl(_, _) => {
+                Err(ContextError::FunctionUnavailable)
+            }
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.create_fence(),
+        }
+    }
+
+    #[inline]
+    pub fn swap_buffers_after(&self, sync: &EglSync, timeout_ns: u64) -> Result<(), ContextError> {
+        match *self {
+            Context::Wgl(_) | Context::HiddenWindowWgl(_, _) => {
+                Err(ContextError::FunctionUnavailable)
+            }
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.swap_buffers_after(sync, timeout_ns),
+        }
+    }
+
+    #[inline]
+    pub fn set_swap_behavior_preserved(&self, preserved: bool) -> Result<(), ContextError> {
+        match *self {
+            Context::Wgl(_) | Context::HiddenWindowWgl(_, _) => {
+                Err(ContextError::FunctionUnavailable)
+            }
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.set_swap_behavior_preserved(preserved),
+        }
+    }
+
+    #[inline]
+    pub fn set_presentation_time(&self, nanos: i64) -> Result<(), ContextError> {
+        match *self {
+            Context::Wgl(_) | Context::HiddenWindowWgl(_, _) => {
+                Err(ContextError::FunctionUnavailable)
+            }
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.set_presentation_time(nanos),
+        }
+    }
+
+    #[inline]
+    pub fn next_frame_id(&self) -> Result<u64, ContextError> {
+        match *self {
+            Context::Wgl(_) | Context::HiddenWindowWgl(_, _) => {
+                Err(ContextError::FunctionUnavailable)
+            }
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.next_frame_id(),
+        }
+    }
+
+    #[inline]
+    pub fn frame_timestamps(&self, frame_id: u64) -> Result<FrameTimestamps, ContextError> {
+        match *self {
+            Context::Wgl(_) | Context::HiddenWindowWgl(_, _) => {
+                Err(ContextError::FunctionUnavailable)
+            }
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.frame_timestamps(frame_id),
+        }
+    }
+
+    #[inline]
+    pub fn recreate(&mut self) -> Result<(), CreationError> {
+        match *self {
+            Context::Wgl(_) | Context::HiddenWindowWgl(_, _) => {
+                Err(CreationError::NotSupported("recreating WGL contexts".to_string()))
+            }
+            Context::Egl(ref mut c)
+            | Context::HiddenWindowEgl(_, ref mut c)
+            | Context::EglPbuffer(ref mut c)
+            | Context::EglSurfaceless(ref mut c) => c.recreate(),
+        }
+    }
+
+    #[inline]
+    pub fn recreate_surface(&mut self) -> Result<(), CreationError> {
+        match *self {
+            Context::Wgl(_) | Context::HiddenWindowWgl(_, _) => {
+                Err(CreationError::NotSupported("recreating WGL contexts".to_string()))
+            }
+            Context::Egl(ref mut c)
+            | Context::HiddenWindowEgl(_, ref mut c)
+            | Context::EglPbuffer(ref mut c)
+            | Context::EglSurfaceless(ref mut c) => c.recreate_surface(),
+        }
+    }
+
+    #[inline]
+    pub fn swap_behavior(&self) -> Result<SwapBehavior, ContextError> {
+        match *self {
+            Context::Wgl(_) | Context::HiddenWindowWgl(_, _) => {
+                Err(ContextError::FunctionUnavailable)
+            }
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.swap_behavior(),
+        }
+    }
+
+    #[inline]
+    pub fn multisample_resolve(&self) -> Result<MultisampleResolve, ContextError> {
+        match *self {
+            Context::Wgl(_) | Context::HiddenWindowWgl(_, _) => {
+                Err(ContextError::FunctionUnavailable)
+            }
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.multisample_resolve(),
+        }
+    }
+
+    #[inline]
+    pub fn buffer_age_supported(&self) -> bool {
+        match *self {
+            Context::Wgl(_) | Context::HiddenWindowWgl(_, _) => false,
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.buffer_age_supported(),
+        }
+    }
+
+    #[inline]
+    pub fn supports_surfaceless(&self) -> bool {
+        match *self {
+            Context::Wgl(_) | Context::HiddenWindowWgl(_, _) => false,
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.supports_surfaceless(),
+        }
+    }
+
+    #[inline]
+    pub fn buffer_age(&self) -> Option<u32> {
+        match *self {
+            Context::Wgl(_) | Context::HiddenWindowWgl(_, _) => None,
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.buffer_age(),
         }
     }
 
     #[inline]
-    pub fn buffer_age(&self) -> u32 {
+    pub fn would_block_on_swap(&self) -> bool {
         match *self {
-            Context::Egl(ref c) => c.buffer_age(),
-            _ => 0,
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.would_block_on_swap(),
+            _ => false,
         }
     }
 
@@ -245,13 +573,19 @@ impl Context {
     }
 
     #[inline]
-    pub fn swap_buffers_with_damage(&self, _rects: &[Rect]) -> Result<(), ContextError> {
-        Err(ContextError::OsError("buffer damage not suported".to_string()))
+    pub fn swap_buffers_with_damage(&self, rects: &[Rect]) -> Result<(), ContextError> {
+        match *self {
+            Context::Egl(ref c) => c.swap_buffers_with_damage(rects),
+            _ => Err(ContextError::FunctionUnavailable),
+        }
     }
 
     #[inline]
     pub fn swap_buffers_with_damage_supported(&self) -> bool {
-        false
+        match *self {
+            Context::Egl(ref c) => c.swap_buffers_with_damage_supported(),
+            _ => false,
+        }
     }
 
     #[inline]
@@ -260,7 +594,8 @@ impl Context {
             Context::Wgl(ref c) | Context::HiddenWindowWgl(_, ref c) => c.get_api(),
             Context::Egl(ref c)
             | Context::HiddenWindowEgl(_, ref c)
-            | Context::EglPbuffer(ref c) => c.get_api(),
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.get_api(),
         }
     }
 
@@ -270,17 +605,57 @@ impl Context {
             Context::Wgl(ref c) | Context::HiddenWindowWgl(_, ref c) => c.supports_vsync_mode(mode),
             Context::Egl(ref c)
             | Context::HiddenWindowEgl(_, ref c)
-            | Context::EglPbuffer(ref c) => c.supports_vsync_mode(mode),
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.supports_vsync_mode(mode),
         }
     }
 
+    #[inline]
+    pub fn supports_mailbox(&self) -> bool {
+        false
+    }
+
     #[inline]
     pub fn set_vsync_mode(&self, mode: VSyncMode) -> Result<(), VSyncError> {
         match *self {
             Context::Wgl(ref c) | Context::HiddenWindowWgl(_, ref c) => c.set_vsync_mode(mode),
             Context::Egl(ref c)
             | Context::HiddenWindowEgl(_, ref c)
-            | Context::EglPbuffer(ref c) => c.set_vsync_mode(mode),
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.set_vsync_mode(mode),
+        }
+    }
+
+    #[inline]
+    pub fn get_vsync_mode(&self) -> Option<VSyncMode> {
+        match *self {
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => Some(c.get_vsync_mode()),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn vsync_range(&self) -> Option<(i32, i32)> {
+        match *self {
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => Some(c.vsync_range()),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn context_priority(&self) -> Option<Priority> {
+        match *self {
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.context_priority(),
+            _ => None,
         }
     }
 
@@ -293,6 +668,45 @@ impl Context {
         }
     }
 
+    #[inline]
+    pub fn creation_report(&self) -> CreationReport {
+        match *self {
+            Context::Wgl(ref c) => c.creation_report(),
+            Context::Egl(ref c) => c.creation_report(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[inline]
+    pub fn diagnostic_details(&self) -> Vec<(&'static str, String)> {
+        match *self {
+            Context::Wgl(_) | Context::HiddenWindowWgl(_, _) => Vec::new(),
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.diagnostic_details(),
+        }
+    }
+
+    #[inline]
+    pub fn surface_size(&self) -> Option<dpi::PhysicalSize<u32>> {
+        match *self {
+            Context::Wgl(_) | Context::HiddenWindowWgl(_, _) => None,
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.surface_size(),
+        }
+    }
+
+    #[inline]
+    pub fn resize_pbuffer(&self, size: dpi::PhysicalSize<u32>) -> Result<(), ContextError> {
+        match *self {
+            Context::EglPbuffer(ref c) => c.resize_pbuffer(size),
+            _ => Err(ContextError::FunctionUnavailable),
+        }
+    }
+
     #[inline]
     pub unsafe fn raw_handle(&self) -> RawHandle {
         match *self {
@@ -301,7 +715,8 @@ impl Context {
             }
             Context::Egl(ref c)
             | Context::HiddenWindowEgl(_, ref c)
-            | Context::EglPbuffer(ref c) => RawHandle::Egl(c.raw_handle()),
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => RawHandle::Egl(c.raw_handle()),
         }
     }
 
@@ -310,10 +725,160 @@ impl Context {
         match *self {
             Context::Egl(ref c)
             | Context::HiddenWindowEgl(_, ref c)
-            | Context::EglPbuffer(ref c) => Some(c.get_egl_display()),
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => Some(c.get_egl_display()),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn current_surface(&self, read: bool) -> Option<*const raw::c_void> {
+        match *self {
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.current_surface(read),
             _ => None,
         }
     }
+
+    #[inline]
+    pub unsafe fn raw_surface(&self) -> Option<*const raw::c_void> {
+        match *self {
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.raw_surface(),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub unsafe fn replace_surface(
+        &self,
+        new_surface: *const raw::c_void,
+    ) -> Result<(), ContextError> {
+        match *self {
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c.replace_surface(new_surface),
+            _ => Err(ContextError::FunctionUnavailable),
+        }
+    }
+
+    #[inline]
+    pub fn share_token(&self) -> Option<ShareToken> {
+        match *self {
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => Some(c.share_token()),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub unsafe fn raw_config(&self) -> Option<(*const raw::c_void, *const raw::c_void)> {
+        match *self {
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => Some(c.raw_config()),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn config_id(&self) -> Option<i32> {
+        match *self {
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => Some(c.config_id()),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn egl_extensions(&self) -> Option<&[String]> {
+        match *self {
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => Some(c.egl_extensions()),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn egl_version(&self) -> Option<(i32, i32)> {
+        match *self {
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => Some(c.egl_version()),
+            _ => None,
+        }
+    }
+}
+
+/// A Windows-specific extension to the [`ContextBuilder`][crate::ContextBuilder]
+/// for headless contexts.
+pub trait HeadlessContextExt {
+    /// Builds an EGL context without any surface, e.g. for compute-only work
+    /// that renders into framebuffer objects, if at all.
+    ///
+    /// This needs libEGL with `EGL_KHR_surfaceless_context`, and returns
+    /// [`CreationError::NotSupported`] otherwise. The context can only share
+    /// with other EGL contexts.
+    fn build_surfaceless<TE>(
+        self,
+        el: &EventLoopWindowTarget<TE>,
+    ) -> Result<crate::Context<NotCurrent>, CreationError>
+    where
+        Self: Sized;
+}
+
+impl<'a, T: ContextCurrentState> HeadlessContextExt for crate::ContextBuilder<'a, T> {
+    #[inline]
+    fn build_surfaceless<TE>(
+        self,
+        _el: &EventLoopWindowTarget<TE>,
+    ) -> Result<crate::Context<NotCurrent>, CreationError>
+    where
+        Self: Sized,
+    {
+        if EGL.is_none() {
+            return Err(CreationError::NotSupported("libEGL not present".to_string()));
+        }
+
+        let crate::ContextBuilder { pf_reqs, gl_attr, .. } = self;
+        let gl_attr = gl_attr.map_sharing(|ctx| &ctx.context);
+        if let Some(&Context::Wgl(_)) | Some(&Context::HiddenWindowWgl(_, _)) = gl_attr.sharing {
+            return Err(CreationError::NotSupported(
+                "surfaceless contexts can't share with WGL contexts".to_string(),
+            ));
+        }
+        let gl_attr_egl = gl_attr.clone().map_sharing(|ctx| match *ctx {
+            Context::Egl(ref c)
+            | Context::HiddenWindowEgl(_, ref c)
+            | Context::EglPbuffer(ref c)
+            | Context::EglSurfaceless(ref c) => c,
+            _ => unreachable!(),
+        });
+
+        EglContext::new(
+            &pf_reqs,
+            &gl_attr_egl,
+            NativeDisplay::Other(None),
+            EglSurfaceType::Surfaceless,
+            |c, _| Ok(c[0]),
+        )
+        .and_then(|prototype| prototype.finish_surfaceless())
+        .map(Context::EglSurfaceless)
+        .map(|context| crate::Context::new(context, gl_attr.thread_affinity))
+    }
 }
 
 pub trait RawContextExt {
@@ -339,10 +904,10 @@ impl<'a, T: ContextCurrentState> RawContextExt for crate::ContextBuilder<'a, T>
     where
         Self: Sized,
     {
-        let crate::ContextBuilder { pf_reqs, gl_attr } = self;
+        let crate::ContextBuilder { pf_reqs, gl_attr, .. } = self;
         let gl_attr = gl_attr.map_sharing(|ctx| &ctx.context);
         Context::new_raw_context(hwnd as *mut _, &pf_reqs, &gl_attr)
-            .map(|context| crate::Context { context, phantom: PhantomData })
+            .map(|context| crate::Context::new(context, gl_attr.thread_affinity))
             .map(|context| crate::RawContext { context, window: () })
     }
 }
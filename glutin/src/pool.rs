@@ -0,0 +1,278 @@
+use super::*;
+
+use std::ops::Deref;
+use std::sync::Condvar;
+use winit::event_loop::EventLoopWindowTarget;
+
+type ResetFn = Box<dyn Fn(&Context<PossiblyCurrent>) + Send + Sync>;
+
+/// A fixed set of headless [`Context`]s that are handed out and taken back
+/// instead of being created and destroyed for every job.
+///
+/// Creating a context means initializing a display, choosing a config and
+/// creating the context and its surface, which adds up when rendering many
+/// short-lived jobs, e.g. thumbnails.
+///
+/// The contexts are surfaceless where that is supported, see
+/// [`build_surfaceless()`], and otherwise have a 1x1 pbuffer, so render into
+/// framebuffer objects.
+///
+/// # Example
+///
+/// ```no_run
+/// # fn main() {
+/// let el = glutin::event_loop::EventLoop::new();
+/// let pool = glutin::HeadlessContextPool::new(glutin::ContextBuilder::new(), &el, 4).unwrap();
+///
+/// let context = pool.acquire().unwrap();
+/// // Render with `context`, then drop it to hand it back.
+/// # }
+/// ```
+#[cfg_attr(
+    any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    ),
+    doc = "\
+[`build_surfaceless()`]: crate::platform::unix::HeadlessContextExt::build_surfaceless()
+"
+)]
+#[cfg_attr(
+    target_os = "android",
+    doc = "\
+[`build_surfaceless()`]: crate::platform::android::HeadlessContextExt::build_surfaceless()
+"
+)]
+#[cfg_attr(
+    target_os = "windows",
+    doc = "\
+[`build_surfaceless()`]: crate::platform::windows::HeadlessContextExt::build_surfaceless()
+"
+)]
+#[cfg_attr(
+    not(any(
+        target_os = "android",
+        target_os = "windows",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    )),
+    doc = "\
+[`build_surfaceless()`]: crate::platform
+"
+)]
+pub struct HeadlessContextPool {
+    slots: Mutex<Slots>,
+    released: Condvar,
+    reset: Option<ResetFn>,
+}
+
+/// The contexts of a [`HeadlessContextPool`], guarded by a single lock so
+/// waiters see the pool shrink.
+#[derive(Debug)]
+struct Slots {
+    idle: Vec<Context<NotCurrent>>,
+    /// Idle contexts plus the ones handed out.
+    total: usize,
+}
+
+impl std::fmt::Debug for HeadlessContextPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("HeadlessContextPool").field("slots", &self.slots).finish()
+    }
+}
+
+impl HeadlessContextPool {
+    /// Creates `count` contexts from `builder` up front.
+    ///
+    /// Fails with the first error any of them runs into, or with
+    /// [`CreationError::NotSupported`] if `count` is zero.
+    pub fn new<TE>(
+        builder: ContextBuilder<'_, NotCurrent>,
+        el: &EventLoopWindowTarget<TE>,
+        count: usize,
+    ) -> Result<Self, CreationError> {
+        if count == 0 {
+            return Err(CreationError::NotSupported(
+                "a context pool needs at least one context".to_string(),
+            ));
+        }
+        let idle =
+            (0..count).map(|_| build_pooled(builder.clone(), el)).collect::<Result<_, _>>()?;
+        Ok(HeadlessContextPool {
+            slots: Mutex::new(Slots { idle, total: count }),
+            released: Condvar::new(),
+            reset: None,
+        })
+    }
+
+    /// Sets a function restoring the OpenGL state of a context before it goes
+    /// back to the pool, so the next job starts from a known state.
+    ///
+    /// glutin can't know which state a job touched, so without this, contexts
+    /// are handed out in whatever state the previous job left them.
+    pub fn with_reset(
+        mut self,
+        reset: impl Fn(&Context<PossiblyCurrent>) + Send + Sync + 'static,
+    ) -> Self {
+        self.reset = Some(Box::new(reset));
+        self
+    }
+
+    /// Makes an idle context current on the calling thread and hands it out,
+    /// waiting for one to be released if they are all in use.
+    ///
+    /// If making the context current fails, the context is dropped from the
+    /// pool and the error is returned. Once every context has been dropped
+    /// this way, this returns [`ContextError::ContextLost`] instead of
+    /// waiting forever.
+    pub fn acquire(&self) -> Result<PooledContext<'_>, ContextError> {
+        let mut slots = self.slots.lock().unwrap();
+        let context = loop {
+            match slots.idle.pop() {
+                Some(context) => break context,
+                None if slots.total == 0 => return Err(ContextError::ContextLost),
+                None => slots = self.released.wait(slots).unwrap(),
+            }
+        };
+        drop(slots);
+        self.make_pooled(context)
+    }
+
+    /// Like [`acquire()`][Self::acquire()], but returns [`None`] right away
+    /// if every context is in use, rather than waiting.
+    pub fn try_acquire(&self) -> Option<Result<PooledContext<'_>, ContextError>> {
+        let mut slots = self.slots.lock().unwrap();
+        if slots.total == 0 {
+            return Some(Err(ContextError::ContextLost));
+        }
+        let context = slots.idle.pop()?;
+        drop(slots);
+        Some(self.make_pooled(context))
+    }
+
+    /// Returns how many contexts are waiting to be acquired.
+    pub fn idle_count(&self) -> usize {
+        self.slots.lock().unwrap().idle.len()
+    }
+
+    fn make_pooled(&self, context: Context<NotCurrent>) -> Result<PooledContext<'_>, ContextError> {
+        match unsafe { context.make_current() } {
+            Ok(context) => Ok(PooledContext { pool: self, context: Some(context) }),
+            Err((_, err)) => {
+                self.discard();
+                Err(err)
+            }
+        }
+    }
+
+    fn release(&self, context: Context<PossiblyCurrent>) -> Result<(), ContextError> {
+        if let Some(ref reset) = self.reset {
+            reset(&context);
+        }
+        let context = match unsafe { context.make_not_current() } {
+            Ok(context) => context,
+            Err((_, err)) => {
+                self.discard();
+                return Err(err);
+            }
+        };
+        self.slots.lock().unwrap().idle.push(context);
+        self.released.notify_one();
+        Ok(())
+    }
+
+    /// Accounts for a context that was dropped instead of going back to the
+    /// pool.
+    fn discard(&self) {
+        self.slots.lock().unwrap().total -= 1;
+        // Waiters have to find out if that was the last one.
+        self.released.notify_all();
+    }
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "windows",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+fn build_pooled<TE>(
+    builder: ContextBuilder<'_, NotCurrent>,
+    el: &EventLoopWindowTarget<TE>,
+) -> Result<Context<NotCurrent>, CreationError> {
+    use crate::platform_impl::HeadlessContextExt;
+
+    // Fall back to a pbuffer where surfaceless contexts aren't available,
+    // e.g. with GLX or WGL.
+    match builder.clone().build_surfaceless(el) {
+        Err(CreationError::NotSupported(_)) => {
+            builder.build_headless(el, dpi::PhysicalSize::new(1, 1))
+        }
+        result => result,
+    }
+}
+
+#[cfg(not(any(
+    target_os = "android",
+    target_os = "windows",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+fn build_pooled<TE>(
+    builder: ContextBuilder<'_, NotCurrent>,
+    el: &EventLoopWindowTarget<TE>,
+) -> Result<Context<NotCurrent>, CreationError> {
+    builder.build_headless(el, dpi::PhysicalSize::new(1, 1))
+}
+
+/// A [`Context`] acquired from a [`HeadlessContextPool`], current on the
+/// thread that acquired it.
+///
+/// Dropping it hands the context back to the pool, see
+/// [`release()`][Self::release()].
+#[derive(Debug)]
+pub struct PooledContext<'p> {
+    pool: &'p HeadlessContextPool,
+    context: Option<Context<PossiblyCurrent>>,
+}
+
+impl PooledContext<'_> {
+    /// Resets the context, makes it not current and hands it back to the
+    /// pool.
+    ///
+    /// If making it not current fails, the context is dropped instead and the
+    /// error is returned. Dropping a [`PooledContext`] does the same, but
+    /// ignores the error.
+    pub fn release(mut self) -> Result<(), ContextError> {
+        let context = self.context.take().unwrap();
+        self.pool.release(context)
+    }
+}
+
+impl Deref for PooledContext<'_> {
+    type Target = Context<PossiblyCurrent>;
+
+    fn deref(&self) -> &Self::Target {
+        self.context.as_ref().unwrap()
+    }
+}
+
+impl Drop for PooledContext<'_> {
+    fn drop(&mut self) {
+        if let Some(context) = self.context.take() {
+            let _ = self.pool.release(context);
+        }
+    }
+}
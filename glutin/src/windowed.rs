@@ -1,6 +1,5 @@
 use super::*;
 
-use std::marker::PhantomData;
 use winit::event_loop::EventLoopWindowTarget;
 use winit::window::{Window, WindowBuilder};
 
@@ -27,14 +26,29 @@ pub type WindowedContext<T> = ContextWrapper<T, Window>;
 /// Represents an OpenGL [`Context`] which has an underlying window that is
 /// stored separately.
 ///
-/// This type can only be created via one of three ways:
+/// This type can only be created via one of four ways:
 ///
 ///  * [`platform::unix::RawContextExt`]
 ///  * [`platform::windows::RawContextExt`]
+///  * [`ContextBuilder::build_raw_egl_context()`]
 ///  * [`WindowedContext<T>::split()`]
 ///
 /// Please see [`ContextWrapper<T, W>`].
 ///
+#[cfg_attr(
+    not(any(
+        target_os = "windows",
+        target_os = "linux",
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    )),
+    doc = "\
+[`ContextBuilder::build_raw_egl_context()`]: crate::ContextBuilder
+"
+)]
 #[cfg_attr(
     target_os = "windows",
     doc = "\
@@ -90,15 +104,45 @@ impl<T: ContextCurrentState> WindowedContext<T> {
         &self.window
     }
 
-    /// Split the [`Window`] apart from the OpenGL [`Context`]. Should only be
-    /// used when intending to transfer the [`RawContext<T>`] to another
-    /// thread.
+    /// Split the [`Window`] apart from the OpenGL [`Context`], e.g. to
+    /// transfer the [`RawContext<T>`] to another thread or to hand the
+    /// [`Window`] to another subsystem. The typestate `T` is kept.
     ///
     /// Unsaftey:
-    ///   - The OpenGL [`Context`] must be dropped before the [`Window`].
+    ///   - The OpenGL [`Context`] must be dropped before the [`Window`]. The
+    ///     context's surface still refers to the native window, so dropping
+    ///     the [`Window`] first, in particular while the [`RawContext<T>`] is
+    ///     current, is undefined behavior.
     pub unsafe fn split(self) -> (RawContext<T>, Window) {
         (RawContext { context: self.context, window: () }, self.window)
     }
+
+    /// Returns the size, in pixels, of the surface the context renders to.
+    ///
+    /// On HiDPI displays this is what the viewport should be set to. The size
+    /// is queried from the surface itself where the backend supports it (EGL
+    /// and GLX), and falls back to the window's
+    /// [`inner_size()`][Window::inner_size()] otherwise.
+    pub fn framebuffer_size(&self) -> dpi::PhysicalSize<u32> {
+        self.context.context.surface_size().unwrap_or_else(|| self.window.inner_size())
+    }
+
+    /// Brings the surface in line with the window's current
+    /// [`inner_size()`][Window::inner_size()], returning whether it had to be
+    /// resized.
+    ///
+    /// Calling this once per frame replaces forwarding every
+    /// [`WindowEvent::Resized`] to [`resize()`]. Only Wayland surfaces need
+    /// resizing this way; other platforms track the window by themselves and
+    /// always return `Ok(false)`, except for macOS, where [`resize()`] must
+    /// still be called.
+    ///
+    /// [`WindowEvent::Resized`]: winit::event::WindowEvent::Resized
+    /// [`resize()`]: ContextWrapper::resize()
+    pub fn update_surface_from_window(&self) -> Result<bool, ContextError> {
+        let (width, height) = self.window.inner_size().into();
+        self.context.context.update_surface_size(width, height)
+    }
 }
 
 impl<W> ContextWrapper<PossiblyCurrent, W> {
@@ -125,13 +169,55 @@ impl<W> ContextWrapper<PossiblyCurrent, W> {
     /// next time the screen is refreshed. However drivers can choose to
     /// override your vsync settings, which means that you can't know in
     /// advance whether `swap_buffers_with_damage()` will block or not.
+    ///
+    /// The rectangles are in pixels, with the origin at the bottom left of
+    /// the surface. Together with [`buffer_age()`], this lets a renderer
+    /// only redraw and present what changed, see the `damage` and
+    /// `buffer_age` examples.
+    ///
+    /// [`buffer_age()`]: Self::buffer_age()
     pub fn swap_buffers_with_damage(&self, rects: &[Rect]) -> Result<(), ContextError> {
         self.context.context.swap_buffers_with_damage(rects)
     }
 
+    /// Swaps the buffers as [`swap_buffers()`] does, then returns the age of
+    /// the back buffer the next frame will be drawn into.
+    ///
+    /// This is the age partial redraws need, see [`buffer_age()`]. `0` means
+    /// the contents are undefined, which is also returned when
+    /// [`buffer_age_supported()`] is `false`, so the whole frame has to be
+    /// redrawn in either case.
+    ///
+    /// [`swap_buffers()`]: Self::swap_buffers()
+    /// [`buffer_age()`]: Self::buffer_age()
+    /// [`buffer_age_supported()`]: Self::buffer_age_supported()
+    pub fn swap_buffers_with_age(&self) -> Result<u32, ContextError> {
+        self.context.context.swap_buffers()?;
+        Ok(self.context.context.buffer_age().unwrap_or(0))
+    }
+
+    /// Waits for `sync` to be signaled, or for `timeout_ns` nanoseconds to
+    /// pass, then swaps the buffers as [`swap_buffers()`] does.
+    ///
+    /// The swap happens even if the wait timed out, so the timeout bounds how
+    /// long a frame can be held back. `sync` must have been created on the
+    /// same EGL display, otherwise [`ContextError::OsError`] is returned.
+    ///
+    /// This is only supported with EGL, and returns
+    /// [`ContextError::FunctionUnavailable`] on other backends.
+    ///
+    /// [`swap_buffers()`]: Self::swap_buffers()
+    pub fn swap_buffers_after(&self, sync: &EglSync, timeout_ns: u64) -> Result<(), ContextError> {
+        self.context.context.swap_buffers_after(&sync.0, timeout_ns)
+    }
+
     /// Returns whether or not swap_buffer_with_damage is available. If this
     /// function returns false, any call to swap_buffers_with_damage will
     /// return an error.
+    ///
+    /// This requires `EGL_KHR_swap_buffers_with_damage` or
+    /// `EGL_EXT_swap_buffers_with_damage`, so it is always `false` on
+    /// backends other than EGL.
     pub fn swap_buffers_with_damage_supported(&self) -> bool {
         self.context.context.swap_buffers_with_damage_supported()
     }
@@ -158,10 +244,151 @@ impl<W> ContextWrapper<PossiblyCurrent, W> {
     /// Query the underlying surface back's buffer age.
     ///
     /// Return `n` is the number of frames elapsed since it was most recently
-    /// drawn.
-    pub fn buffer_age(&self) -> u32 {
+    /// drawn. `0` means that the contents are undefined. [`None`] means the
+    /// age can't be queried, e.g. because [`buffer_age_supported()`] is
+    /// `false`, so the whole frame has to be redrawn as well.
+    ///
+    /// [`buffer_age_supported()`]: Self::buffer_age_supported()
+    pub fn buffer_age(&self) -> Option<u32> {
         self.context.context.buffer_age()
     }
+
+    /// Returns whether [`buffer_age()`][Self::buffer_age()] can report the
+    /// age of the back buffer.
+    ///
+    /// This requires `EGL_EXT_buffer_age` or `EGL_KHR_partial_update` with
+    /// EGL, and `GLX_EXT_buffer_age` with GLX.
+    pub fn buffer_age_supported(&self) -> bool {
+        self.context.context.buffer_age_supported()
+    }
+
+    /// Makes a best-effort guess at whether calling
+    /// [`swap_buffers()`][Self::swap_buffers()] now would block waiting for
+    /// vsync, e.g. for a frame scheduler that would rather do other work
+    /// first.
+    ///
+    /// This is a heuristic: with EGL and `EGL_KHR_fence_sync`, glutin inserts
+    /// a fence after every swap once this has been called, and guesses that
+    /// the next swap blocks while the GPU hasn't reached the fence yet. The
+    /// first call, calls with vsync off and other backends always return
+    /// `false`, so a `false` doesn't guarantee that the swap won't block.
+    pub fn would_block_on_swap(&self) -> bool {
+        self.context.context.would_block_on_swap()
+    }
+
+    /// Sets whether the contents of the back buffer are preserved by
+    /// [`swap_buffers()`], rather than left undefined.
+    ///
+    /// The new behavior takes effect on the next swap. Changing it is cheap,
+    /// so renderers tracking damage can keep the buffer on most frames and
+    /// let it be destroyed on frames that clear everything anyway.
+    ///
+    /// Preserving needs a format supporting it, otherwise
+    /// [`ContextError::OsError`] is returned. This is only supported with
+    /// EGL, and returns [`ContextError::FunctionUnavailable`] on other
+    /// backends or for surfaceless contexts.
+    ///
+    /// [`swap_buffers()`]: Self::swap_buffers()
+    pub fn set_swap_behavior_preserved(&self, preserved: bool) -> Result<(), ContextError> {
+        self.context.context.set_swap_behavior_preserved(preserved)
+    }
+
+    /// Asks the compositor to present the next frame at `nanos`, a
+    /// timestamp on the `CLOCK_MONOTONIC` clock, to pace frames evenly.
+    ///
+    /// The time applies to the next call to [`swap_buffers()`], so call this
+    /// after rendering and right before swapping, once per frame. This needs
+    /// `EGL_ANDROID_presentation_time`, and returns
+    /// [`ContextError::FunctionUnavailable`] without it, on backends other
+    /// than EGL, or for surfaceless contexts.
+    ///
+    /// [`swap_buffers()`]: Self::swap_buffers()
+    pub fn set_presentation_time(&self, nanos: i64) -> Result<(), ContextError> {
+        self.context.context.set_presentation_time(nanos)
+    }
+
+    /// Returns the id of the frame the next call to [`swap_buffers()`] will
+    /// submit, to look up its [`frame_timestamps()`] later.
+    ///
+    /// Timestamps are only collected for frames swapped after the first call
+    /// to this or [`frame_timestamps()`]. This needs
+    /// `EGL_ANDROID_get_frame_timestamps`, and returns
+    /// [`ContextError::FunctionUnavailable`] without it, on backends other
+    /// than EGL, or for surfaceless contexts.
+    ///
+    /// [`swap_buffers()`]: Self::swap_buffers()
+    /// [`frame_timestamps()`]: Self::frame_timestamps()
+    pub fn next_frame_id(&self) -> Result<u64, ContextError> {
+        self.context.context.next_frame_id()
+    }
+
+    /// Returns when the frame `frame_id`, from
+    /// [`next_frame_id()`][Self::next_frame_id()], went through each stage
+    /// of presentation, e.g. to measure latency.
+    ///
+    /// Stages that haven't happened yet are [`None`], so query again later to
+    /// fill them in. Like [`next_frame_id()`][Self::next_frame_id()], this
+    /// needs `EGL_ANDROID_get_frame_timestamps`.
+    pub fn frame_timestamps(&self, frame_id: u64) -> Result<FrameTimestamps, ContextError> {
+        self.context.context.frame_timestamps(frame_id)
+    }
+
+    /// Returns the current `EGL_SWAP_BEHAVIOR` of the surface, e.g. to check
+    /// that [`set_swap_behavior_preserved()`] took effect.
+    ///
+    /// Like the setter, this is only supported with EGL.
+    ///
+    /// [`set_swap_behavior_preserved()`]: Self::set_swap_behavior_preserved()
+    pub fn swap_behavior(&self) -> Result<SwapBehavior, ContextError> {
+        self.context.context.swap_behavior()
+    }
+
+    /// Returns the current `EGL_MULTISAMPLE_RESOLVE` of the surface, i.e. the
+    /// filter used to resolve a multisampled surface on a swap.
+    ///
+    /// This is only supported with EGL, and returns
+    /// [`ContextError::FunctionUnavailable`] on other backends or for
+    /// surfaceless contexts.
+    pub fn multisample_resolve(&self) -> Result<MultisampleResolve, ContextError> {
+        self.context.context.multisample_resolve()
+    }
+}
+
+impl<T: ContextCurrentState> WindowedContext<T> {
+    /// Replaces the OpenGL context and the window surface with new ones for
+    /// the same window, after a reset lost them with
+    /// [`Robustness::RobustLoseContextOnReset`].
+    ///
+    /// Every GL object is gone and has to be created again, but the window
+    /// and this [`WindowedContext`] stay the same. The new context is made
+    /// current if the old one was current on this thread, and doesn't share
+    /// with any other context, even if the old one did.
+    ///
+    /// If this fails, the old context and surface are destroyed anyway and
+    /// [`make_current()`][Self::make_current()] fails until a later call
+    /// succeeds, so it can be retried. Dropping the context is always safe.
+    ///
+    /// This is only supported with EGL, and returns
+    /// [`CreationError::NotSupported`] on other backends and once the surface
+    /// was swapped out with `ContextTraitExt::replace_surface()`.
+    pub fn recreate(&mut self) -> Result<(), CreationError> {
+        self.context.context.recreate()
+    }
+
+    /// Like [`recreate()`][Self::recreate()], but keeps sharing objects with
+    /// the context this one was built sharing with.
+    ///
+    /// If this context was built sharing with another one, see
+    /// [`ContextBuilder::with_shared_lists()`], the new context shares with
+    /// that one again, so it must still exist. Otherwise the shared objects
+    /// survive, but everything only this context owned is gone.
+    ///
+    /// This is only supported with EGL, and returns
+    /// [`CreationError::NotSupported`] on other backends and once the surface
+    /// was swapped out with `ContextTraitExt::replace_surface()`.
+    pub fn recreate_surface(&mut self) -> Result<(), CreationError> {
+        self.context.context.recreate_surface()
+    }
 }
 
 impl<T: ContextCurrentState, W> ContextWrapper<T, W> {
@@ -234,6 +461,21 @@ impl<T: ContextCurrentState, W> ContextWrapper<T, W> {
         }
     }
 
+    /// Like [`make_current()`][Self::make_current()], but binds `read`'s
+    /// surface as the read surface.
+    ///
+    /// Please see [`Context::make_current_asymmetric()`].
+    pub unsafe fn make_current_asymmetric<U: ContextCurrentState>(
+        self,
+        read: &Context<U>,
+    ) -> Result<ContextWrapper<PossiblyCurrent, W>, (Self, ContextError)> {
+        let window = self.window;
+        match self.context.make_current_asymmetric(read) {
+            Ok(context) => Ok(ContextWrapper { window, context }),
+            Err((context, err)) => Err((ContextWrapper { window, context }, err)),
+        }
+    }
+
     /// If this context is current, makes this context not current. If this
     /// context is not current however, this function does nothing.
     ///
@@ -282,6 +524,49 @@ impl<T: ContextCurrentState, W> ContextWrapper<T, W> {
     pub fn get_api(&self) -> Api {
         self.context.get_api()
     }
+
+    /// Gathers everything glutin knows about this context into a multi-line
+    /// text report, to be pasted as a whole into bug reports.
+    ///
+    /// This covers the [`CreationReport`], the vsync mode and the surface
+    /// size. EGL adds its vendor, client APIs, config ID and supported swap
+    /// interval range. The exact format is not stable and not meant to be
+    /// parsed.
+    pub fn diagnostic_report(&self) -> String {
+        fn or_unknown<T: std::fmt::Debug>(value: Option<T>) -> String {
+            value.map_or_else(|| "unknown".to_string(), |value| format!("{:?}", value))
+        }
+
+        let context = &self.context.context;
+        let report = context.creation_report();
+
+        let mut lines = vec![format!("Backend: {:?}", report.backend)];
+        if let Some((major, minor)) = report.backend_version {
+            lines.push(format!("Backend version: {}.{}", major, minor));
+        }
+        lines.extend(
+            context
+                .diagnostic_details()
+                .into_iter()
+                .map(|(name, value)| format!("{}: {}", name, value)),
+        );
+        lines.push(match report.version {
+            Some((major, minor)) => format!("API: {:?} {}.{}", report.api, major, minor),
+            None => format!("API: {:?}", report.api),
+        });
+        lines.push(format!("Robustness: {}", or_unknown(report.robustness)));
+        lines.push(format!("Debug: {}", or_unknown(report.debug)));
+        lines.push(format!("VSync: {}", or_unknown(context.get_vsync_mode())));
+        lines.push(match context.surface_size() {
+            Some(size) => format!("Surface size: {}x{}", size.width, size.height),
+            None => "Surface size: none".to_string(),
+        });
+        lines.push(format!("Pixel format: {:?}", report.pixel_format));
+        lines.push(format!("Extensions ({}):", report.extensions.len()));
+        lines.extend(report.extensions.iter().map(|extension| format!("    {}", extension)));
+
+        lines.join("\n")
+    }
 }
 
 impl<W> ContextWrapper<PossiblyCurrent, W> {
@@ -290,6 +575,17 @@ impl<W> ContextWrapper<PossiblyCurrent, W> {
     pub fn get_proc_address(&self, addr: &str) -> *const core::ffi::c_void {
         self.context.get_proc_address(addr)
     }
+
+    /// Returns the address of an OpenGL function, or [`None`] if it can't
+    /// be loaded.
+    ///
+    /// Turning the address into a function pointer is still up to the
+    /// caller, with [`std::mem::transmute()`] to the function's signature
+    /// and an `extern "system"` ABI.
+    #[inline]
+    pub fn get_proc_address_opt(&self, addr: &str) -> Option<*const core::ffi::c_void> {
+        self.context.get_proc_address_opt(addr)
+    }
 }
 
 impl<T: ContextCurrentState, W> std::ops::Deref for ContextWrapper<T, W> {
@@ -305,19 +601,184 @@ impl<'a, T: ContextCurrentState> ContextBuilder<'a, T> {
     ///
     /// Errors can occur in two scenarios:
     ///  - If the window could not be created (via permission denied,
-    ///  incompatible system, out of memory, etc.). This should be very rare.
+    ///    incompatible system, out of memory, etc.). This should be very rare.
     ///  - If the OpenGL [`Context`] could not be created. This generally
-    ///    happens
-    ///  because the underlying platform doesn't support a requested feature.
+    ///    happens because the underlying platform doesn't support a requested
+    ///    feature.
     pub fn build_windowed<TE>(
         self,
         wb: WindowBuilder,
         el: &EventLoopWindowTarget<TE>,
     ) -> Result<WindowedContext<NotCurrent>, CreationError> {
-        let ContextBuilder { pf_reqs, gl_attr } = self;
+        let ContextBuilder { pf_reqs, gl_attr, software_fallback } = self;
         let gl_attr = gl_attr.map_sharing(|ctx| &ctx.context);
-        platform_impl::Context::new_windowed(wb, el, &pf_reqs, &gl_attr).map(|(window, context)| {
-            WindowedContext { window, context: Context { context, phantom: PhantomData } }
+        SoftwareFallback::retry(software_fallback.as_ref(), &pf_reqs, |pf_reqs| {
+            platform_impl::Context::new_windowed(wb.clone(), el, pf_reqs, &gl_attr)
+        })
+        .map(|(window, context)| WindowedContext {
+            window,
+            context: Context::new(context, gl_attr.thread_affinity),
         })
     }
+
+    /// Like [`build_windowed()`][Self::build_windowed()], but also returns a
+    /// [`CreationReport`] describing what glutin actually did to create the
+    /// context, e.g. which GL version it fell back to or whether the
+    /// requested robustness was available.
+    ///
+    /// This is meant for diagnostics such as logging the environment or
+    /// attaching it to bug reports.
+    pub fn build_windowed_with_report<TE>(
+        self,
+        wb: WindowBuilder,
+        el: &EventLoopWindowTarget<TE>,
+    ) -> Result<(WindowedContext<NotCurrent>, CreationReport), CreationError> {
+        let windowed_context = self.build_windowed(wb, el)?;
+        let report = windowed_context.context.context.creation_report();
+        Ok((windowed_context, report))
+    }
+
+    /// Like [`build_windowed()`][Self::build_windowed()], but if no pixel
+    /// format matches, drops the optional requirements one at a time and
+    /// tries again: first multisampling, then the stencil bits, the alpha
+    /// bits and finally the depth bits.
+    ///
+    /// Returns the requirements that had to be dropped, in that order, so
+    /// the application can warn about them or adapt its rendering. Errors
+    /// other than [`CreationError::NoAvailablePixelFormat`] are returned
+    /// right away, as is that one once there's nothing left to drop.
+    pub fn build_windowed_relaxed<TE>(
+        self,
+        wb: WindowBuilder,
+        el: &EventLoopWindowTarget<TE>,
+    ) -> Result<(WindowedContext<NotCurrent>, Vec<RelaxedRequirement>), CreationError> {
+        const ORDER: [RelaxedRequirement; 4] = [
+            RelaxedRequirement::Multisampling,
+            RelaxedRequirement::Stencil,
+            RelaxedRequirement::Alpha,
+            RelaxedRequirement::Depth,
+        ];
+
+        let ContextBuilder { mut pf_reqs, gl_attr, software_fallback } = self;
+        let gl_attr = gl_attr.map_sharing(|ctx| &ctx.context);
+        let mut relaxed = Vec::new();
+        let mut remaining = ORDER.iter();
+        loop {
+            let result = SoftwareFallback::retry(software_fallback.as_ref(), &pf_reqs, |pf_reqs| {
+                platform_impl::Context::new_windowed(wb.clone(), el, pf_reqs, &gl_attr)
+            });
+            match result {
+                Ok((window, context)) => {
+                    let context = Context::new(context, gl_attr.thread_affinity);
+                    return Ok((WindowedContext { window, context }, relaxed));
+                }
+                Err(CreationError::NoAvailablePixelFormat) => {}
+                Err(err) => return Err(err),
+            }
+
+            match remaining.by_ref().find(|req| req.relax(&mut pf_reqs)) {
+                Some(&req) => relaxed.push(req),
+                None => return Err(CreationError::NoAvailablePixelFormat),
+            }
+        }
+    }
+
+    /// Builds the window right away, but defers creating its OpenGL
+    /// [`Context`] until the first call to
+    /// [`LazyWindowedContext::make_current()`].
+    ///
+    /// This saves GPU memory for windows that might never be rendered to,
+    /// such as the secondary windows of an editor. Errors from creating the
+    /// context are only reported by that first
+    /// [`make_current()`][LazyWindowedContext::make_current()].
+    pub fn build_windowed_lazy<TE>(
+        self,
+        wb: WindowBuilder,
+        el: &EventLoopWindowTarget<TE>,
+    ) -> Result<LazyWindowedContext<'a, T>, CreationError> {
+        let gl_attr = self.gl_attr.clone().map_sharing(|ctx| &ctx.context);
+        let window = platform_impl::Context::new_window(wb, el, &self.pf_reqs, &gl_attr)?;
+        Ok(LazyWindowedContext { window, builder: self })
+    }
+}
+
+#[cfg(any(
+    target_os = "windows",
+    target_os = "linux",
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+impl<'a, T: ContextCurrentState> ContextBuilder<'a, T> {
+    /// Creates an EGL context rendering to `nwin` on `display`, without going
+    /// through winit, e.g. for compositors or embedded devices that manage
+    /// their own native windows.
+    ///
+    /// Only EGL contexts can be shared with, and the window surface is
+    /// created with the first config matching the builder's requirements.
+    ///
+    /// # Safety
+    ///
+    /// `display` and `nwin` must be valid for EGL, and `nwin` must belong to
+    /// `display`. Both must outlive the returned context.
+    pub unsafe fn build_raw_egl_context(
+        self,
+        display: crate::platform::NativeDisplay,
+        nwin: crate::platform::EGLNativeWindowType,
+    ) -> Result<RawContext<NotCurrent>, CreationError> {
+        let ContextBuilder { pf_reqs, gl_attr, .. } = self;
+        let gl_attr = gl_attr.map_sharing(|ctx| &ctx.context);
+        platform_impl::Context::new_raw_egl_context(display, nwin, &pf_reqs, &gl_attr)
+            .map(|context| Context::new(context, gl_attr.thread_affinity))
+            .map(|context| RawContext { context, window: () })
+    }
+}
+
+/// A [`Window`] whose OpenGL [`Context`] is created the first time it is made
+/// current.
+///
+/// Please see [`ContextBuilder::build_windowed_lazy()`].
+#[derive(Debug)]
+pub struct LazyWindowedContext<'a, T: ContextCurrentState> {
+    window: Window,
+    builder: ContextBuilder<'a, T>,
+}
+
+impl<'a, T: ContextCurrentState> LazyWindowedContext<'a, T> {
+    /// Borrow the inner [`Window`].
+    pub fn window(&self) -> &Window {
+        &self.window
+    }
+
+    /// Creates the OpenGL [`Context`] for the window and makes it current.
+    ///
+    /// On success this turns into a regular [`WindowedContext`], so only the
+    /// first call pays for creating the context. If creating the context
+    /// fails, the error is returned as [`ContextError::OsError`] and the call
+    /// can be retried.
+    ///
+    /// Please see [`ContextWrapper::make_current()`].
+    pub unsafe fn make_current(
+        self,
+    ) -> Result<WindowedContext<PossiblyCurrent>, (Self, ContextError)> {
+        let LazyWindowedContext { window, builder } = self;
+
+        let gl_attr = builder.gl_attr.clone().map_sharing(|ctx| &ctx.context);
+        let context =
+            match platform_impl::Context::new_for_window(&window, &builder.pf_reqs, &gl_attr) {
+                Ok(context) => Context::<NotCurrent>::new(context, gl_attr.thread_affinity),
+                Err(err) => {
+                    let err = ContextError::OsError(err.to_string());
+                    return Err((LazyWindowedContext { window, builder }, err));
+                }
+            };
+
+        match context.make_current() {
+            Ok(context) => Ok(ContextWrapper { window, context }),
+            // The context is dropped here and recreated by the next call.
+            Err((_, err)) => Err((LazyWindowedContext { window, builder }, err)),
+        }
+    }
 }
@@ -27,18 +27,36 @@ fn main() {
             [
                 "EGL_EXT_buffer_age",
                 "EGL_EXT_create_context_robustness",
+                "EGL_EXT_device_base",
+                "EGL_EXT_device_drm",
+                "EGL_EXT_device_enumeration",
+                "EGL_EXT_device_query",
+                "EGL_EXT_multiview_window",
+                "EGL_EXT_output_base",
+                "EGL_EXT_output_drm",
                 "EGL_EXT_platform_base",
                 "EGL_EXT_platform_device",
                 "EGL_EXT_platform_wayland",
+                "EGL_EXT_pixel_format_float",
                 "EGL_EXT_platform_x11",
+                "EGL_EXT_stream_consumer_egloutput",
+                "EGL_EXT_swap_buffers_with_damage",
+                "EGL_IMG_context_priority",
+                "EGL_KHR_context_flush_control",
                 "EGL_KHR_create_context",
                 "EGL_KHR_create_context_no_error",
+                "EGL_KHR_fence_sync",
+                "EGL_KHR_gl_colorspace",
+                "EGL_KHR_no_config_context",
                 "EGL_KHR_platform_android",
                 "EGL_KHR_platform_gbm",
                 "EGL_KHR_platform_wayland",
                 "EGL_KHR_platform_x11",
+                "EGL_KHR_stream",
+                "EGL_KHR_stream_producer_eglsurface",
                 "EGL_KHR_swap_buffers_with_damage",
                 "EGL_MESA_platform_gbm",
+                "EGL_NV_robustness_video_memory_purge",
             ],
         );
 
@@ -31,7 +31,7 @@ fn main() {
             },
             Event::RedrawRequested(_) => {
                 gl.draw_frame([1.0, 0.5, 0.7, 1.0]);
-                println!("Buffer age: {}", windowed_context.buffer_age());
+                println!("Buffer age: {:?}", windowed_context.buffer_age());
                 windowed_context.swap_buffers().unwrap();
                 windowed_context.window().request_redraw();
             }
@@ -38,6 +38,7 @@ fn main() {
                 "GLX_EXT_framebuffer_sRGB",
                 "GLX_EXT_swap_control",
                 "GLX_MESA_swap_control",
+                "GLX_OML_swap_method",
                 "GLX_SGI_swap_control",
             ],
         )